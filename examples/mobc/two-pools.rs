@@ -0,0 +1,44 @@
+use futures::TryStreamExt;
+use mobc_reql::{GetSession, Pool, SessionManager};
+use reql::cmd::connect::Options;
+use reql::r;
+use std::env;
+
+// Each pool is a value of its own, so one process can talk to several
+// clusters at once, such as a production and an analytics one
+#[tokio::main]
+async fn main() -> reql::Result<()> {
+    env_logger::init();
+
+    let prod_host = env::var("PROD_HOST").unwrap_or_else(|_| "localhost".to_owned());
+    let analytics_host = env::var("ANALYTICS_HOST").unwrap_or_else(|_| "localhost".to_owned());
+
+    let prod = Pool::builder()
+        .max_open(20)
+        .build(SessionManager::new(Options::new().host(prod_host)));
+    let analytics = Pool::builder().max_open(5).build(SessionManager::new(
+        Options::new().host(analytics_host).db("analytics"),
+    ));
+
+    let session = prod.session().await?;
+    let name: Option<String> = r
+        .db("rethinkdb")
+        .table("server_config")
+        .get_field("name")
+        .run(&session)
+        .try_next()
+        .await?;
+    println!("prod server: {:?}", name);
+
+    let session = analytics.session().await?;
+    let name: Option<String> = r
+        .db("rethinkdb")
+        .table("server_config")
+        .get_field("name")
+        .run(&session)
+        .try_next()
+        .await?;
+    println!("analytics server: {:?}", name);
+
+    Ok(())
+}
@@ -1,20 +1,27 @@
+use async_stream::try_stream;
 use blocking::unblock;
 use futures::lock::Mutex;
-use futures::{Future, TryStreamExt};
+use futures::stream::{Stream, StreamExt};
+use futures::{pin_mut, Future, TryStreamExt};
 use futures_timer::Delay;
 use log::trace;
 use mobc::{async_trait, Manager};
+use rand::Rng;
 use reql::cmd::connect::Options;
 use reql::cmd::run::{self, Arg};
 use reql::types::{Change, ServerStatus};
 use reql::{r, Command, Connection, Driver, Error, Result};
+use serde::de::DeserializeOwned;
 use std::cmp::Ordering;
 use std::io;
 use std::net::{IpAddr, TcpStream};
 use std::ops::Deref;
+use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+const RETRY_DELAY: Duration = Duration::from_millis(100);
+
 pub type Pool = mobc::Pool<SessionManager>;
 
 #[async_trait]
@@ -94,19 +101,68 @@ impl Server {
     }
 }
 
+/// How a [SessionManager] picks the server each new session connects to
+///
+/// This only applies once [SessionManager::discover_hosts] has found the
+/// servers of the cluster. If connecting to the chosen server fails, the
+/// others are tried in turn. Sessions are reused for many queries, so the
+/// load is spread per session rather than per query.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[non_exhaustive]
+pub enum LoadBalance {
+    /// Go through the servers one after the other (the default)
+    RoundRobin,
+    /// Pick a server at random
+    Random,
+    /// Connect to the server with the lowest latency while it's up
+    LowestLatency,
+}
+
+impl Default for LoadBalance {
+    fn default() -> Self {
+        Self::RoundRobin
+    }
+}
+
 #[derive(Clone)]
 pub struct SessionManager {
     opts: Options,
     servers: Arc<Mutex<Vec<Server>>>,
     pool: Option<Pool>,
+    load_balance: LoadBalance,
+    next_server: Arc<AtomicUsize>,
 }
 
 impl SessionManager {
+    /// Creates a manager whose sessions are all connected with `opts`
+    ///
+    /// Nothing is shared between managers, so pools built from different
+    /// ones are fully independent of each other.
     pub fn new(opts: Options) -> Self {
         Self {
             opts,
             servers: Arc::new(Mutex::new(Vec::new())),
             pool: None,
+            load_balance: LoadBalance::default(),
+            next_server: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Sets how the server for each new session is picked
+    pub fn load_balance(mut self, load_balance: LoadBalance) -> Self {
+        self.load_balance = load_balance;
+        self
+    }
+
+    // The index of the first server to try out of `count`; the servers
+    // are sorted by latency
+    fn first_server(&self, count: usize) -> usize {
+        match self.load_balance {
+            LoadBalance::RoundRobin => {
+                self.next_server.fetch_add(1, AtomicOrdering::Relaxed) % count
+            }
+            LoadBalance::Random => rand::thread_rng().gen_range(0..count),
+            LoadBalance::LowestLatency => 0,
         }
     }
 
@@ -177,6 +233,141 @@ async fn set_latency(servers: &mut Vec<Server>) {
     }
 }
 
+/// Check the idle sessions of a pool in the background
+///
+/// Every `interval`, the sessions sitting idle in the pool are checked out
+/// and handed back. Checking a session out runs a small query on it, so
+/// sessions whose server went away, for example because it restarted,
+/// are dropped here and replaced with fresh ones instead of failing the
+/// next query that gets them.
+///
+/// Unless it's the only one, an idle session is left alone in each round
+/// so the checks don't keep other tasks waiting for a session. Which one
+/// that is changes as sessions move through the pool, so it gets checked
+/// in a later round.
+///
+/// This relies on the pool checking sessions on checkout, which it does
+/// unless `test_on_check_out` is turned off. A `health_check_interval`
+/// longer than `interval` makes the pool skip some of the checks.
+///
+/// The returned future loops forever and keeps a handle to the pool, so
+/// spawn it as its own task and abort that task to stop it, which also
+/// lets the pool be dropped.
+pub fn check_idle_sessions(pool: &Pool, interval: Duration) -> impl Future<Output = ()> {
+    let pool = pool.clone();
+    async move {
+        loop {
+            Delay::new(interval).await;
+            let idle = pool.state().await.idle;
+            trace!("checking idle sessions; idle: {}", idle);
+            let count = if idle > 1 { idle - 1 } else { idle };
+            // Hold on to the sessions until all of them have been checked
+            // so we don't get the same one twice
+            let mut sessions = Vec::new();
+            for _ in 0..count {
+                match pool.session().await {
+                    Ok(session) => sessions.push(session),
+                    Err(error) => {
+                        trace!("checking idle sessions; error: {}", error);
+                        break;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// When [run_retrying] runs a query again
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[non_exhaustive]
+pub struct Retry {
+    /// How many more times to run the query after the first attempt
+    /// (default `0`)
+    pub retries: u8,
+    /// Whether queries that write are run again too (default `false`)
+    pub writes: bool,
+}
+
+impl Retry {
+    pub fn new(retries: u8) -> Self {
+        Self {
+            retries,
+            writes: false,
+        }
+    }
+
+    /// Marks the writes in the query as safe to run more than once
+    pub fn writes(mut self, writes: bool) -> Self {
+        self.writes = writes;
+        self
+    }
+
+    fn retries(self, query: &Command) -> u8 {
+        if query.is_write() && !self.writes {
+            return 0;
+        }
+        self.retries
+    }
+}
+
+/// Run a query on a session from the pool, running it again on a fresh
+/// session if the connection fails
+///
+/// This helps queries ride out a server failing over to another one.
+/// Only connection errors, such as a broken connection, lead to a retry,
+/// never timeouts or errors reported by the server. The query is
+/// also not run again once it has yielded rows, as those would be
+/// yielded twice.
+///
+/// When the connection fails, there is no telling whether the server got
+/// to run the query. That's harmless for reads, but a write may then be
+/// applied twice, so writes are only retried when `retry` says so. Only
+/// do that for writes which are idempotent, such as replacing a document
+/// by its primary key, unlike inserting a document without one or
+/// incrementing a counter.
+pub fn run_retrying<T>(pool: &Pool, query: Command, retry: Retry) -> impl Stream<Item = Result<T>>
+where
+    T: Unpin + DeserializeOwned,
+{
+    let pool = pool.clone();
+    let retries = retry.retries(&query);
+    try_stream! {
+        let mut attempt = 0;
+        'query: loop {
+            if attempt > 0 {
+                Delay::new(RETRY_DELAY * u32::from(attempt)).await;
+            }
+            let session = match pool.session().await {
+                Ok(session) => session,
+                Err(error) if attempt < retries && error.is_connection_error() => {
+                    trace!("getting a session to run a query; error: {}, attempt: {}", error, attempt);
+                    attempt += 1;
+                    continue;
+                }
+                Err(error) => Err(error)?,
+            };
+            let rows = query.clone().run::<_, T>(&session);
+            pin_mut!(rows);
+            let mut yielded = false;
+            while let Some(row) = rows.next().await {
+                match row {
+                    Ok(row) => {
+                        yielded = true;
+                        yield row;
+                    }
+                    Err(error) if !yielded && attempt < retries && error.is_connection_error() => {
+                        trace!("running a query; error: {}, attempt: {}", error, attempt);
+                        attempt += 1;
+                        continue 'query;
+                    }
+                    Err(error) => Err(error)?,
+                }
+            }
+            break;
+        }
+    }
+}
+
 fn server_status() -> Command {
     r.db("rethinkdb").table("server_status")
 }
@@ -197,7 +388,9 @@ impl Manager for SessionManager {
             );
             return r.connect(opts.clone()).await;
         } else {
-            for server in servers.iter() {
+            let first = self.first_server(servers.len());
+            let turns = servers.iter().cycle().skip(first).take(servers.len());
+            for server in turns {
                 for host in &server.addresses {
                     trace!(
                         "discovered server {}; host: {}, port: {}",
@@ -220,6 +413,9 @@ impl Manager for SessionManager {
     }
 
     async fn check(&self, conn: Self::Connection) -> Result<Self::Connection> {
+        // The query gets a token of its own, so a response left unread on
+        // the session fails the check, and marks it as broken, instead of
+        // being mistaken for the answer
         let msg = 200;
         match r.expr(msg).run(&conn).try_next().await? {
             Some(res) => verify(res, msg)?,
@@ -249,3 +445,189 @@ fn to_reql(error: mobc::Error<Error>) -> Error {
         mobc::Error::BadConn => Driver::ConnectionBroken.into(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        check_idle_sessions, run_retrying, GetSession, LoadBalance, Pool, Retry, Server,
+        SessionManager,
+    };
+    use futures::TryStreamExt;
+    use futures_timer::Delay;
+    use reql::cmd::connect::Options;
+    use reql::r;
+    use serde_json::{json, Value};
+    use std::io::{self, Read, Write};
+    use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream};
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    // A server speaking just enough of the protocol to open sessions on
+    // it, which answers each query with its term, as if it was
+    // `r.expr(term)`, unless `hang_up` says to drop the connection instead
+    struct MockServer {
+        addr: SocketAddr,
+        conns: Arc<Mutex<Vec<TcpStream>>>,
+    }
+
+    impl MockServer {
+        fn start(hang_up: fn(&Value) -> bool) -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let addr = listener.local_addr().unwrap();
+            let conns = Arc::new(Mutex::new(Vec::new()));
+            let accepted = conns.clone();
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    let stream = stream.unwrap();
+                    accepted.lock().unwrap().push(stream.try_clone().unwrap());
+                    thread::spawn(move || serve(stream, hang_up));
+                }
+            });
+            Self { addr, conns }
+        }
+
+        fn opts(&self) -> Options {
+            Options::new().host("127.0.0.1").port(self.addr.port())
+        }
+
+        fn server(&self, name: &str) -> Server {
+            Server {
+                name: name.to_owned(),
+                addresses: vec![self.addr.ip()],
+                port: self.addr.port(),
+                latency: Duration::from_millis(0),
+            }
+        }
+
+        fn accepted(&self) -> usize {
+            self.conns.lock().unwrap().len()
+        }
+
+        // Hangs up on the `i`th session, as if the server went away
+        fn kill(&self, i: usize) {
+            self.conns.lock().unwrap()[i]
+                .shutdown(Shutdown::Both)
+                .unwrap();
+        }
+    }
+
+    fn serve(mut stream: TcpStream, hang_up: fn(&Value) -> bool) -> io::Result<()> {
+        let mut version = [0u8; 4];
+        stream.read_exact(&mut version)?;
+        let client_first: Value = serde_json::from_slice(&read_message(&mut stream)?)?;
+        let auth = client_first["authentication"].as_str().unwrap_or_default();
+        let nonce = auth.split("r=").nth(1).unwrap_or_default();
+        let info = json!({
+            "success": true,
+            "min_protocol_version": 0,
+            "max_protocol_version": 0,
+            "server_version": "2.4.1",
+        });
+        let server_first = json!({
+            "success": true,
+            "authentication": format!("r={}server,s=c2FsdA==,i=4096", nonce),
+        });
+        // The driver expects messages 2 and 4 to arrive together
+        let mut msgs = serde_json::to_vec(&info)?;
+        msgs.push(b'\0');
+        msgs.extend(serde_json::to_vec(&server_first)?);
+        msgs.push(b'\0');
+        stream.write_all(&msgs)?;
+        read_message(&mut stream)?;
+        // Leaving out the server signature spares us checking the proof
+        stream.write_all(b"{\"success\":true}\0")?;
+        loop {
+            let mut header = [0u8; 12];
+            stream.read_exact(&mut header)?;
+            let len = u32::from_le_bytes([header[8], header[9], header[10], header[11]]);
+            let mut body = vec![0u8; len as usize];
+            stream.read_exact(&mut body)?;
+            let query: Value = serde_json::from_slice(&body)?;
+            if hang_up(&query[1]) {
+                return stream.shutdown(Shutdown::Both);
+            }
+            let resp = serde_json::to_vec(&json!({"t": 1, "r": [query[1]]}))?;
+            stream.write_all(&header[..8])?;
+            stream.write_all(&(resp.len() as u32).to_le_bytes())?;
+            stream.write_all(&resp)?;
+        }
+    }
+
+    fn read_message(stream: &mut TcpStream) -> io::Result<Vec<u8>> {
+        let mut msg = Vec::new();
+        let mut byte = [0u8; 1];
+        loop {
+            stream.read_exact(&mut byte)?;
+            if byte[0] == b'\0' {
+                return Ok(msg);
+            }
+            msg.push(byte[0]);
+        }
+    }
+
+    #[test]
+    fn writes_are_only_retried_when_allowed() {
+        let read = r.table("users").get(1);
+        let write = r.table("users").get(1).delete(());
+        assert_eq!(Retry::new(3).retries(&read), 3);
+        assert_eq!(Retry::new(3).retries(&write), 0);
+        assert_eq!(Retry::new(3).writes(true).retries(&write), 3);
+    }
+
+    #[test]
+    fn round_robin_rotates_across_servers() {
+        let manager = SessionManager::new(Default::default());
+        let picks: Vec<_> = (0..5).map(|_| manager.first_server(2)).collect();
+        assert_eq!(picks, [0, 1, 0, 1, 0]);
+        // Clones share the rotation, as the pool holds one of its own
+        let clone = manager.clone();
+        assert_eq!(clone.first_server(2), 1);
+        assert_eq!(manager.first_server(2), 0);
+    }
+
+    #[test]
+    fn other_strategies_stay_in_range() {
+        let manager = SessionManager::new(Default::default()).load_balance(LoadBalance::Random);
+        assert!((0..100).all(|_| manager.first_server(3) < 3));
+        let manager = manager.load_balance(LoadBalance::LowestLatency);
+        assert!((0..5).all(|_| manager.first_server(3) == 0));
+    }
+
+    #[tokio::test]
+    async fn dead_idle_session_is_dropped() {
+        let server = MockServer::start(|_| false);
+        let pool = Pool::builder()
+            .max_open(2)
+            .build(SessionManager::new(server.opts()));
+        let sessions = (pool.session().await.unwrap(), pool.session().await.unwrap());
+        drop(sessions);
+        server.kill(1);
+        let checks = tokio::spawn(check_idle_sessions(&pool, Duration::from_millis(50)));
+        // The dead session is gone once the pool either has one session
+        // less or has opened another one in its place
+        let start = Instant::now();
+        while pool.state().await.connections == 2 && server.accepted() == 2 {
+            assert!(start.elapsed() < Duration::from_secs(5));
+            Delay::new(Duration::from_millis(10)).await;
+        }
+        checks.abort();
+    }
+
+    #[tokio::test]
+    async fn query_fails_over_to_another_server() {
+        // The first server passes the pool's checks but goes away as soon
+        // as it gets the query itself
+        let first = MockServer::start(|term| *term != 200);
+        let second = MockServer::start(|_| false);
+        let manager = SessionManager::new(Options::new());
+        *manager.servers.lock().await = vec![first.server("first"), second.server("second")];
+        let pool = Pool::builder().build(manager);
+        let rows: Vec<u32> = run_retrying::<u32>(&pool, r.expr(7), Retry::new(1))
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(rows, [7]);
+        assert_eq!((first.accepted(), second.accepted()), (1, 1));
+    }
+}
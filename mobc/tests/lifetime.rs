@@ -0,0 +1,28 @@
+use futures::TryStreamExt;
+use mobc_reql::{GetSession, Pool, SessionManager};
+use reql::r;
+use std::time::Duration;
+use tokio::time::sleep;
+
+async fn ping(pool: &Pool) -> reql::Result<()> {
+    let session = pool.session().await?;
+    r.expr(1).run::<_, u8>(&session).try_next().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn old_sessions_are_replaced() -> reql::Result<()> {
+    let pool = Pool::builder()
+        .max_lifetime(Some(Duration::from_millis(100)))
+        .build(SessionManager::new(Default::default()));
+
+    ping(&pool).await?;
+    assert_eq!(pool.state().await.max_lifetime_closed, 0);
+
+    sleep(Duration::from_millis(200)).await;
+    ping(&pool).await?;
+    assert_eq!(pool.state().await.max_lifetime_closed, 1);
+    assert_eq!(pool.state().await.connections, 1);
+
+    Ok(())
+}
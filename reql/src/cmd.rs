@@ -207,6 +207,32 @@ pub enum ReadMode {
     Outdated,
 }
 
+/// Units for distances in geospatial commands
+#[derive(Debug, Clone, Copy, Serialize, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[non_exhaustive]
+pub enum Unit {
+    #[serde(rename = "m")]
+    Meter,
+    #[serde(rename = "km")]
+    Kilometer,
+    #[serde(rename = "mi")]
+    InternationalMile,
+    #[serde(rename = "nm")]
+    NauticalMile,
+    #[serde(rename = "ft")]
+    InternationalFoot,
+}
+
+/// The reference ellipsoid distances are measured on
+#[derive(Debug, Clone, Copy, Serialize, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[non_exhaustive]
+pub enum GeoSystem {
+    #[serde(rename = "WGS84")]
+    Wgs84,
+    #[serde(rename = "unit_sphere")]
+    UnitSphere,
+}
+
 pub trait StaticString {
     fn static_string(self) -> Cow<'static, str>;
 }
@@ -223,6 +249,12 @@ impl StaticString for String {
     }
 }
 
+impl StaticString for &String {
+    fn static_string(self) -> Cow<'static, str> {
+        Cow::from(self.to_owned())
+    }
+}
+
 impl StaticString for &Cow<'static, str> {
     fn static_string(self) -> Cow<'static, str> {
         match self {
@@ -345,6 +377,20 @@ impl<'a> Command {
         arg.arg().into_cmd().with_parent(self)
     }
 
+    /// Wait for the given secondary indexes to be ready, or for every
+    /// index on the table when passed `()`
+    ///
+    /// The server only responds once the indexes have been built, so the
+    /// query resolves as soon as they can be used. Each index's status
+    /// deserialises into [IndexStatus](crate::types::IndexStatus).
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # reql::example(|r, conn| async_stream::stream! {
+    /// r.table("comments").index_wait("author_name").run(conn)
+    /// # });
+    /// ```
     pub fn index_wait<T>(self, arg: T) -> Self
     where
         T: index_wait::Arg,
@@ -352,6 +398,21 @@ impl<'a> Command {
         arg.arg().into_cmd().with_parent(self)
     }
 
+    /// Set a function the server runs on every write to the table
+    ///
+    /// The function is called with the write's context, the old value and
+    /// the new value of the document, and whatever it returns is written
+    /// instead of the new value. Raising an error with `r.error` rejects
+    /// the write. Pass `None` to remove the hook. Write hooks need
+    /// RethinkDB 2.4 or later; running this on an older server fails with
+    /// a driver error.
+    ///
+    /// ```
+    /// # use reql::{func, r, Command};
+    /// let stamp = func!(|_context, _old_val, new_val| new_val
+    ///     .merge(Command::from([("modified_at", r.now())])));
+    /// let query = r.table("comments").set_write_hook(stamp);
+    /// ```
     pub fn set_write_hook<T>(self, arg: T) -> Self
     where
         T: set_write_hook::Arg,
@@ -359,6 +420,7 @@ impl<'a> Command {
         arg.arg().into_cmd().with_parent(self)
     }
 
+    /// Get the write hook of the table, or `null` if it has none
     pub fn get_write_hook(self) -> Self {
         Self::new(TermType::GetWriteHook).with_parent(self)
     }
@@ -370,6 +432,50 @@ impl<'a> Command {
         arg.arg().into_cmd().with_parent(self)
     }
 
+    /// Insert documents, updating the ones whose primary key already exists
+    ///
+    /// This is `insert` with `conflict` set to `update`, so the existing
+    /// document is merged with the new one rather than the write failing.
+    /// It returns the same `WriteStatus`.
+    ///
+    /// Passing a function alongside the documents resolves conflicts with
+    /// it instead. It's called with the primary key, the old document and
+    /// the new one, and returns the document to store.
+    ///
+    /// ```
+    /// # use reql::{func, r};
+    /// // Add up the visits instead of overwriting them
+    /// let query = r.table("pages").upsert(r.args((
+    ///     serde_json::json!({"id": 1, "visits": 1}),
+    ///     func!(|_id, old, new| {
+    ///         let visits = old.clone().get_field("visits") + new.get_field("visits");
+    ///         old.merge(r.object(r.args([r.expr("visits"), visits])))
+    ///     }),
+    /// )));
+    /// ```
+    pub fn upsert<T>(self, arg: T) -> Self
+    where
+        T: insert::Arg,
+    {
+        insert::upsert(self, arg.arg())
+    }
+
+    /// Insert a single document and get back its primary key
+    ///
+    /// The key is the one the server generated when the document doesn't
+    /// have one, or its own otherwise, so this saves reading the document
+    /// back. Deserialise the result straight into the key type, such as a
+    /// `Uuid` for generated keys. The query fails with the server's
+    /// message when the document could not be inserted.
+    ///
+    /// `return_changes` is always set, overriding it in the options.
+    pub fn insert_and_return<T>(self, arg: T) -> Self
+    where
+        T: insert::Arg,
+    {
+        insert::and_return(self, arg.arg())
+    }
+
     pub fn update<T>(self, arg: T) -> Self
     where
         T: update::Arg,
@@ -462,6 +568,10 @@ impl<'a> Command {
         arg.arg().into_cmd().with_parent(self)
     }
 
+    /// Map each element of a sequence to a sequence, and flatten the
+    /// results into one
+    ///
+    /// Handy for turning an array field into one row per element.
     pub fn concat_map<T>(self, arg: T) -> Self
     where
         T: concat_map::Arg,
@@ -532,6 +642,8 @@ impl<'a> Command {
         arg.arg().into_cmd().with_parent(self)
     }
 
+    /// Get the indexes of the elements of a sequence that are equal to a
+    /// value or satisfy a predicate function
     pub fn offsets_of<T>(self, arg: T) -> Self
     where
         T: offsets_of::Arg,
@@ -539,6 +651,7 @@ impl<'a> Command {
         arg.arg().into_cmd().with_parent(self)
     }
 
+    /// Whether a sequence has no elements
     pub fn is_empty(self) -> Self {
         Self::new(TermType::IsEmpty).with_parent(self)
     }
@@ -550,6 +663,10 @@ impl<'a> Command {
         arg.arg().into_cmd().with_parent(self)
     }
 
+    /// Pick a number of random elements from a sequence
+    ///
+    /// The elements come back in no particular order. Asking for more
+    /// elements than the sequence has returns all of them.
     pub fn sample<T>(self, arg: T) -> Self
     where
         T: sample::Arg,
@@ -557,6 +674,27 @@ impl<'a> Command {
         arg.arg().into_cmd().with_parent(self)
     }
 
+    /// Group elements by the values of the given fields or the result
+    /// of a function
+    ///
+    /// Commands chained after `group` are run on each group separately,
+    /// so `group("dept").count(())` counts the documents in each
+    /// department. The server returns grouped results as a
+    /// `GROUPED_DATA` pseudotype, which deserialises into
+    /// [GroupedData](crate::types::GroupedData).
+    ///
+    /// ## Example
+    ///
+    /// Count the games of each player, by player and type of game.
+    ///
+    /// ```
+    /// # reql::example(|r, conn| async_stream::stream! {
+    /// r.table("games").group(r.args(["player", "type"])).count(()).run(conn)
+    /// # });
+    /// ```
+    ///
+    /// ## Related commands
+    /// * [ungroup](Self::ungroup)
     pub fn group<T>(self, arg: T) -> Self
     where
         T: group::Arg,
@@ -564,6 +702,27 @@ impl<'a> Command {
         arg.arg().into_cmd().with_parent(self)
     }
 
+    /// Turn grouped data into an array of `{"group": .., "reduction": ..}`
+    /// objects
+    ///
+    /// Use this to keep working on grouped results as a whole, for
+    /// example to order groups by their reduction, rather than on each
+    /// group separately.
+    ///
+    /// ## Example
+    ///
+    /// Find the players with the most games.
+    ///
+    /// ```
+    /// # reql::example(|r, conn| async_stream::stream! {
+    /// r.table("games")
+    ///     .group("player")
+    ///     .count(())
+    ///     .ungroup()
+    ///     .order_by(r.desc("reduction"))
+    ///     .run(conn)
+    /// # });
+    /// ```
     pub fn ungroup(self) -> Self {
         Self::new(TermType::Ungroup).with_parent(self)
     }
@@ -624,6 +783,16 @@ impl<'a> Command {
         arg.arg().into_cmd().with_parent(self)
     }
 
+    /// Whether a sequence contains the given values
+    ///
+    /// Pass a single value, several in `r.args([...])` which all have to
+    /// be in the sequence, or a predicate function that at least one
+    /// element has to satisfy.
+    ///
+    /// ```
+    /// # use reql::{func, r};
+    /// let urgent = r.table("tasks").filter(func!(|task| task.get_field("tags").contains("urgent")));
+    /// ```
     pub fn contains<T>(self, arg: T) -> Self
     where
         T: contains::Arg,
@@ -701,6 +870,13 @@ impl<'a> Command {
         arg.arg().into_cmd().with_parent(self)
     }
 
+    /// Get a single field from an object, or an element from an array
+    ///
+    /// On a sequence of objects, the field is taken from each of them.
+    /// Like [get_field](Self::get_field), a missing field is a
+    /// [NonExistence](crate::err::Runtime::NonExistence) error unless
+    /// guarded with [default](Self::default) or
+    /// [has_fields](Self::has_fields).
     pub fn bracket<T>(self, arg: T) -> Self
     where
         T: bracket::Arg,
@@ -708,6 +884,11 @@ impl<'a> Command {
         arg.arg().into_cmd().with_parent(self)
     }
 
+    /// Get a single field from an object
+    ///
+    /// On a sequence of objects, the field is taken from each of them and
+    /// objects without it are skipped. On a single object a missing field
+    /// is a [NonExistence](crate::err::Runtime::NonExistence) error.
     pub fn get_field<T>(self, arg: T) -> Self
     where
         T: get_field::Arg,
@@ -715,6 +896,24 @@ impl<'a> Command {
         arg.arg().into_cmd().with_parent(self)
     }
 
+    /// Get only the `id` of each document, such as to feed them to a
+    /// follow-up query without sending the whole documents over
+    ///
+    /// This is `get_field("id")`, so for a table with another primary key
+    /// use [get_field](Self::get_field) with that key instead.
+    ///
+    /// ```
+    /// # use reql::{func, r};
+    /// let banned = r.table("users").filter(func!(|user| user.get_field("banned"))).ids();
+    /// ```
+    pub fn ids(self) -> Self {
+        self.get_field("id")
+    }
+
+    /// Test whether an object has all of the given fields, or keep only
+    /// the objects of a sequence that do
+    ///
+    /// A field set to `null` counts as missing.
     pub fn has_fields<T>(self, arg: T) -> Self
     where
         T: has_fields::Arg,
@@ -758,6 +957,13 @@ impl<'a> Command {
         Self::new(TermType::Values).with_parent(self)
     }
 
+    /// Match a string against a regular expression
+    ///
+    /// Returns an object describing the match, with the matched string,
+    /// its position and the capture groups, or `null` when the string
+    /// does not match, which deserialises as `None`. The regular
+    /// expression uses the [RE2](https://github.com/google/re2/wiki/Syntax)
+    /// syntax.
     pub fn match_<T>(self, arg: T) -> Self
     where
         T: match_::Arg,
@@ -765,6 +971,10 @@ impl<'a> Command {
         arg.arg().into_cmd().with_parent(self)
     }
 
+    /// Split a string into an array of substrings
+    ///
+    /// Pass `()` to split on whitespace, a separator to split on it, or
+    /// `r.args((separator, limit))` to split at most `limit` times.
     pub fn split<T>(self, arg: T) -> Self
     where
         T: split::Arg,
@@ -780,6 +990,12 @@ impl<'a> Command {
         Self::new(TermType::Downcase).with_parent(self)
     }
 
+    /// Logical and of this value and one or more others
+    ///
+    /// Several values can be passed at once with
+    /// `r.args([r.expr(a), r.expr(b)])`. The same goes for
+    /// [or](Self::or) and the comparison commands, such as
+    /// [eq](Self::eq) and [lt](Self::lt).
     pub fn and<T>(self, arg: T) -> Self
     where
         T: and::Arg,
@@ -836,6 +1052,9 @@ impl<'a> Command {
         arg.arg().into_cmd().with_parent(self)
     }
 
+    /// Logical negation, called with `()`
+    ///
+    /// `!query` does the same.
     pub fn not<T>(self, arg: T) -> Self
     where
         T: not::Arg,
@@ -981,6 +1200,18 @@ impl<'a> Command {
         arg.arg().into_cmd().with_parent(self)
     }
 
+    /// Run a write for each element of a sequence, on the server
+    ///
+    /// The function is called with each element and returns a write, such
+    /// as an `insert`, `update` or `delete`, or an array of them. The
+    /// result adds up the [WriteStatus](crate::types::WriteStatus) of all
+    /// the writes.
+    ///
+    /// ```
+    /// # use reql::{func, r};
+    /// // Copy every document of `posts` into `archive`
+    /// let query = r.table("posts").for_each(func!(|post| r.table("archive").insert(post)));
+    /// ```
     pub fn for_each<T>(self, arg: T) -> Self
     where
         T: for_each::Arg,
@@ -988,6 +1219,13 @@ impl<'a> Command {
         arg.arg().into_cmd().with_parent(self)
     }
 
+    /// Use a default value when this one is `null` or missing
+    ///
+    /// Catches [NonExistence](crate::err::Runtime::NonExistence) errors,
+    /// such as a missing field read with [get_field](Self::get_field), as
+    /// well as fields explicitly set to `null`. Pass a
+    /// [Func](crate::Func) to compute the default from the error message
+    /// instead.
     pub fn default<T>(self, arg: T) -> Self
     where
         T: default::Arg,
@@ -995,6 +1233,12 @@ impl<'a> Command {
         arg.arg().into_cmd().with_parent(self)
     }
 
+    /// Convert a value to another type
+    ///
+    /// Sequences such as a table or the result of
+    /// [get_all](Self::get_all) can be turned into an `"array"`, and an
+    /// array of `[key, value]` pairs into an `"object"`. Anything can be
+    /// coerced to a `"string"`, and strings of digits to a `"number"`.
     pub fn coerce_to<T>(self, arg: T) -> Self
     where
         T: coerce_to::Arg,
@@ -1002,10 +1246,17 @@ impl<'a> Command {
         arg.arg().into_cmd().with_parent(self)
     }
 
+    /// Get the ReQL type of a value as a string, such as `"NUMBER"`,
+    /// `"ARRAY"`, `"OBJECT"` or `"TABLE"`
     pub fn type_of(self) -> Self {
         Self::new(TermType::TypeOf).with_parent(self)
     }
 
+    /// Get information about a value, such as its type
+    ///
+    /// On a table this includes its database, primary key and secondary
+    /// indexes, which [TableInfo](crate::types::TableInfo) deserialises.
+    /// A database gives a [DbInfo](crate::types::DbInfo).
     pub fn info(self) -> Self {
         Self::new(TermType::Info).with_parent(self)
     }
@@ -1029,6 +1280,10 @@ impl<'a> Command {
         Self::new(TermType::ToGeojson).with_parent(self)
     }
 
+    /// Documents whose geometry, in a geospatial index, intersects the given one
+    ///
+    /// Pass the geometry along with the index, as in
+    /// `r.args((polygon, r.index("area")))`.
     pub fn get_intersecting<T>(self, arg: T) -> Self
     where
         T: get_intersecting::Arg,
@@ -1036,6 +1291,12 @@ impl<'a> Command {
         arg.arg().into_cmd().with_parent(self)
     }
 
+    /// Documents closest to a point, using a geospatial index
+    ///
+    /// Returns an array of objects with the distance from the point in
+    /// `dist`, in the `unit` of the options (meters by default), and the
+    /// document itself in `doc`, closest first. `types::geo::Nearest`
+    /// deserialises these.
     pub fn get_nearest<T>(self, arg: T) -> Self
     where
         T: get_nearest::Arg,
@@ -1071,14 +1332,29 @@ impl<'a> Command {
         arg.arg().into_cmd().with_parent(self)
     }
 
+    /// Get the configuration of a table or database
+    ///
+    /// The result deserialises into [TableConfig](crate::types::TableConfig)
+    /// or [DbConfig](crate::types::DbConfig). It can also be updated to
+    /// change the configuration.
     pub fn config(self) -> Self {
         Self::new(TermType::Config).with_parent(self)
     }
 
+    /// Rebalance the shards of a table, or of all the tables in a
+    /// database, so they hold about the same number of documents
+    ///
+    /// The result deserialises into
+    /// [RebalanceStatus](crate::types::RebalanceStatus).
     pub fn rebalance(self) -> Self {
         Self::new(TermType::Rebalance).with_parent(self)
     }
 
+    /// Change the number of shards and replicas of a table, or of all
+    /// the tables in a database
+    ///
+    /// The result deserialises into
+    /// [ReconfigureStatus](crate::types::ReconfigureStatus).
     pub fn reconfigure<T>(self, arg: T) -> Self
     where
         T: reconfigure::Arg,
@@ -1086,10 +1362,20 @@ impl<'a> Command {
         arg.arg().into_cmd().with_parent(self)
     }
 
+    /// Get the status of a table, such as whether it is ready for reads
+    /// and writes and the state of each replica
+    ///
+    /// The result deserialises into [TableStatus](crate::types::TableStatus).
     pub fn status(self) -> Self {
         Self::new(TermType::Status).with_parent(self)
     }
 
+    /// Wait for a table, or all the tables in a database, to be ready
+    ///
+    /// Pass `()` to wait until all replicas are ready, or
+    /// [Options](wait::Options) to wait for another state or give up
+    /// after a timeout. The server only responds once the tables are
+    /// ready.
     pub fn wait<T>(self, arg: T) -> Self
     where
         T: wait::Arg,
@@ -38,3 +38,24 @@ where
         arg.arg().with_parent(self).into_cmd()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{cmd, r};
+
+    #[test]
+    fn r_expr_add_strings() {
+        let query = r.expr("foo") + "bar";
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[24,["foo","bar"]]"#;
+        assert_eq!(serialised, expected);
+    }
+
+    #[test]
+    fn r_expr_arithmetic() {
+        let query = (r.expr(10) - 4) * 3 / 2 % 5;
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[28,[[27,[[26,[[25,[10,4]],3]],2]],5]]"#;
+        assert_eq!(serialised, expected);
+    }
+}
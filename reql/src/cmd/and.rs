@@ -1,5 +1,7 @@
+use super::args::Args;
 use crate::{cmd, Command};
 use ql2::term::TermType;
+use serde::Serialize;
 
 pub trait Arg {
     fn arg(self) -> cmd::Arg<()>;
@@ -16,3 +18,67 @@ impl Arg for Command {
         Self::new(TermType::And).with_arg(self).into_arg()
     }
 }
+
+impl<T> Arg for T
+where
+    T: Serialize,
+{
+    fn arg(self) -> cmd::Arg<()> {
+        Command::from_json(self).arg()
+    }
+}
+
+impl<const N: usize> Arg for Args<[Command; N]> {
+    fn arg(self) -> cmd::Arg<()> {
+        let Args(arr) = self;
+        let mut query = Command::new(TermType::And);
+        for arg in IntoIterator::into_iter(arr) {
+            query = query.with_arg(arg);
+        }
+        query.into_arg()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{self as reql, cmd, func, r};
+    use serde_json::{json, Value};
+
+    #[test]
+    fn r_table_filter_and() {
+        let query = r.table("users").filter(func!(|user| {
+            let adult = user.clone().bracket("age").ge(18);
+            adult.and(user.bracket("active").eq(true))
+        }));
+        let serialised: Value = serde_json::from_str(&cmd::serialise(&query)).unwrap();
+        let var = serialised[1][1][1][0][1][0].clone();
+        let expected = json!([
+            39,
+            [
+                [15, ["users"]],
+                [
+                    69,
+                    [
+                        [2, [var]],
+                        [
+                            67,
+                            [
+                                [22, [[170, [[10, [var]], "age"]], 18]],
+                                [17, [[170, [[10, [var]], "active"]], true]]
+                            ]
+                        ]
+                    ]
+                ]
+            ]
+        ]);
+        assert_eq!(serialised, expected);
+    }
+
+    #[test]
+    fn r_expr_and_variadic() {
+        let query = r.expr(true).and(r.args([r.expr(true), r.expr(false)]));
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[67,[true,true,false]]"#;
+        assert_eq!(serialised, expected);
+    }
+}
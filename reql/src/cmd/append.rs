@@ -1,5 +1,6 @@
 use crate::{cmd, Command};
 use ql2::term::TermType;
+use serde::Serialize;
 
 pub trait Arg {
     fn arg(self) -> cmd::Arg<()>;
@@ -16,3 +17,25 @@ impl Arg for Command {
         Self::new(TermType::Append).with_arg(self).into_arg()
     }
 }
+
+impl<T> Arg for T
+where
+    T: Serialize,
+{
+    fn arg(self) -> cmd::Arg<()> {
+        Command::from_json(self).arg()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{cmd, r};
+
+    #[test]
+    fn r_expr_append() {
+        let query = r.expr([1, 2]).append(3);
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[29,[[2,[1,2]],3]]"#;
+        assert_eq!(serialised, expected);
+    }
+}
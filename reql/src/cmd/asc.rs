@@ -4,6 +4,12 @@ use ql2::term::TermType;
 #[derive(Debug, Clone)]
 pub struct Asc(pub(crate) Command);
 
+impl From<Asc> for Command {
+    fn from(Asc(cmd): Asc) -> Self {
+        cmd
+    }
+}
+
 pub trait Arg {
     fn arg(self) -> cmd::Arg<()>;
 }
@@ -1,4 +1,4 @@
-use crate::{cmd, Command};
+use crate::{cmd, Command, Func};
 use ql2::term::TermType;
 
 pub trait Arg {
@@ -10,3 +10,28 @@ impl Arg for Command {
         Self::new(TermType::Avg).with_arg(self).into_arg()
     }
 }
+
+impl Arg for () {
+    fn arg(self) -> cmd::Arg<()> {
+        Command::new(TermType::Avg).into_arg()
+    }
+}
+
+impl Arg for &str {
+    fn arg(self) -> cmd::Arg<()> {
+        Command::from_json(self).arg()
+    }
+}
+
+impl Arg for String {
+    fn arg(self) -> cmd::Arg<()> {
+        Command::from_json(self).arg()
+    }
+}
+
+impl Arg for Func {
+    fn arg(self) -> cmd::Arg<()> {
+        let Func(func) = self;
+        func.arg()
+    }
+}
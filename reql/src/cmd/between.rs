@@ -1,8 +1,11 @@
 use super::args::Args;
-use crate::{cmd, Command};
+use crate::{cmd, err, r, Command, Result, Session};
+use futures::TryStreamExt;
 use ql2::term::TermType;
 use reql_macros::CommandOptions;
+use serde::de::DeserializeOwned;
 use serde::Serialize;
+use serde_json::Value;
 
 #[derive(
     Debug, Clone, Copy, CommandOptions, Serialize, Default, Eq, PartialEq, Ord, PartialOrd, Hash,
@@ -42,6 +45,20 @@ impl<'a> Arg<'a> for Args<(Command, Options<'a>)> {
     }
 }
 
+impl<'a> Arg<'a> for Args<(Command, Command)> {
+    fn arg(self) -> cmd::Arg<Options<'a>> {
+        let Args((min, max)) = self;
+        min.arg().with_arg(max)
+    }
+}
+
+impl<'a> Arg<'a> for Args<(Command, Command, Options<'a>)> {
+    fn arg(self) -> cmd::Arg<Options<'a>> {
+        let Args((min, max, opts)) = self;
+        min.arg().with_arg(max).with_opts(opts)
+    }
+}
+
 impl<'a, T> Arg<'a> for Args<(T, T)>
 where
     T: Serialize,
@@ -63,3 +80,142 @@ where
         Command::from_json(min).arg().with_arg(max).with_opts(opts)
     }
 }
+
+/// A page of rows read by [get_page]
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct Page<T> {
+    pub rows: Vec<T>,
+    /// The key to pass to [get_page] for the following page, `None` on
+    /// the last page
+    pub next: Option<Value>,
+}
+
+/// Read a page of rows from `table`, in the order of `index`, starting
+/// after `start_key`
+///
+/// Pass `None` for the first page, then the `next` key of each page to
+/// get the one after it. Unlike paging with `skip`, the server seeks
+/// straight to the start of the page on the index, so later pages are as
+/// cheap to read as the first.
+///
+/// `index` has to be the primary key or a secondary index on a field of
+/// the same name, since the key is read from that field of the last row.
+/// Rows sharing an index value with the last row of a page are skipped,
+/// so the field should be unique.
+///
+/// ## Example
+///
+/// ```
+/// use reql::cmd::between::get_page;
+/// use reql::r;
+/// use serde_json::Value;
+///
+/// # async fn example() -> reql::Result<()> {
+/// # let session = r.connect(()).await?;
+/// let mut key: Option<Value> = None;
+/// loop {
+///     let page = get_page::<Value, _>(&session, r.table("posts"), "id", key, 20).await?;
+///     // render page.rows
+///     match page.next {
+///         Some(next) => key = Some(next),
+///         None => break,
+///     }
+/// }
+/// # Ok(()) }
+/// ```
+pub async fn get_page<T, K>(
+    session: &Session,
+    table: Command,
+    index: &'static str,
+    start_key: Option<K>,
+    page_size: usize,
+) -> Result<Page<T>>
+where
+    T: DeserializeOwned,
+    K: Serialize,
+{
+    // One row more than the page tells whether there is a next page
+    let query = page(table, index, start_key, page_size + 1);
+    let mut rows: Vec<Value> = query.run(session).try_collect().await?;
+    let next = if rows.len() > page_size {
+        rows.truncate(page_size);
+        let key = rows.last().and_then(|row| row.get(index)).cloned();
+        let missing = || err::Driver::Other(format!("page rows have no `{}` field", index));
+        Some(key.ok_or_else(missing)?)
+    } else {
+        None
+    };
+    let rows = rows
+        .into_iter()
+        .map(serde_json::from_value)
+        .collect::<std::result::Result<_, _>>()?;
+    Ok(Page { rows, next })
+}
+
+fn page<K>(table: Command, index: &'static str, start_key: Option<K>, limit: usize) -> Command
+where
+    K: Serialize,
+{
+    let (from, left_bound) = match start_key {
+        Some(key) => (Command::from_json(key), Status::Open),
+        None => (r.minval(), Status::Closed),
+    };
+    let opts = Options::new().index(index).left_bound(left_bound);
+    table
+        .between(r.args((from, r.maxval(), opts)))
+        .order_by(r.index(index))
+        .limit(limit as isize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Options, Status};
+    use crate::{cmd, r};
+    use serde_json::Value;
+
+    #[test]
+    fn r_table_between_index() {
+        let opts = Options::new()
+            .index("age")
+            .left_bound(Status::Closed)
+            .right_bound(Status::Open);
+        let query = r.table("foo").between(r.args((18, 65, opts)));
+        let serialised: Value = serde_json::from_str(&cmd::serialise(&query)).unwrap();
+        let expected: Value = serde_json::from_str(
+            r#"[182,[[15,["foo"]],18,65],{"index":"age","left_bound":"closed","right_bound":"open"}]"#,
+        )
+        .unwrap();
+        assert_eq!(serialised, expected);
+    }
+
+    #[test]
+    fn r_table_between_minval() {
+        let query = r.table("foo").between(r.args((r.minval(), r.expr(10))));
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[182,[[15,["foo"]],[180,[]],10]]"#;
+        assert_eq!(serialised, expected);
+    }
+
+    #[test]
+    fn page_after_key() {
+        let query = super::page(r.table("posts"), "id", Some(20), 11);
+        let serialised: Value = serde_json::from_str(&cmd::serialise(&query)).unwrap();
+        let expected: Value = serde_json::from_str(
+            r#"[71,[[41,[[182,[[15,["posts"]],20,[181,[]]],{"index":"id","left_bound":"open"}]],{"index":"id"}],11]]"#,
+        )
+        .unwrap();
+        assert_eq!(serialised, expected);
+    }
+
+    #[test]
+    fn first_page() {
+        let query = super::page::<u32>(r.table("posts"), "id", None, 11);
+        let serialised: Value = serde_json::from_str(&cmd::serialise(&query)).unwrap();
+        let expected: Value = serde_json::from_str(
+            r#"[71,[[41,[[182,[[15,["posts"]],[180,[]],[181,[]]],{"index":"id","left_bound":"closed"}]],{"index":"id"}],11]]"#,
+        )
+        .unwrap();
+        assert_eq!(serialised, expected);
+    }
+}
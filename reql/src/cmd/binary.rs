@@ -35,3 +35,17 @@ impl Arg for Vec<u8> {
         Binary::new(&self).arg()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{cmd, r};
+    use serde_json::{json, Value};
+
+    #[test]
+    fn r_binary_bytes() {
+        let query = r.binary(vec![1, 2, 3]);
+        let serialised: Value = serde_json::from_str(&cmd::serialise(&query)).unwrap();
+        let expected = json!([155, [{"$reql_type$": "BINARY", "data": "AQID"}]]);
+        assert_eq!(serialised, expected);
+    }
+}
@@ -6,12 +6,39 @@ pub trait Arg {
     fn arg(self) -> cmd::Arg<()>;
 }
 
+impl Arg for Command {
+    fn arg(self) -> cmd::Arg<()> {
+        Self::new(TermType::Bracket).with_arg(self).into_arg()
+    }
+}
+
+/// A field name for objects, or an index for arrays
 impl<T> Arg for T
 where
     T: Serialize,
 {
     fn arg(self) -> cmd::Arg<()> {
-        let arg = Command::from_json(self);
-        Command::new(TermType::Bracket).with_arg(arg).into_arg()
+        Command::from_json(self).arg()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{cmd, r};
+
+    #[test]
+    fn r_expr_bracket_nested() {
+        let query = r.table("users").get(1).bracket("address").bracket("city");
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[170,[[170,[[16,[[15,["users"]],1]],"address"]],"city"]]"#;
+        assert_eq!(serialised, expected);
+    }
+
+    #[test]
+    fn r_expr_bracket_index() {
+        let query = r.expr([10, 20, 30]).bracket(1);
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[170,[[2,[10,20,30]],1]]"#;
+        assert_eq!(serialised, expected);
     }
 }
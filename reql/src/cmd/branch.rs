@@ -1,6 +1,7 @@
 use super::args::Args;
 use crate::{cmd, Command};
 use ql2::term::TermType;
+use serde::Serialize;
 
 pub trait Arg {
     fn arg(self) -> cmd::Arg<()>;
@@ -19,16 +20,75 @@ impl Arg for Args<(Command, Command, Command)> {
     }
 }
 
-#[allow(array_into_iter)]
-#[allow(clippy::into_iter_on_ref)]
+impl<T> Arg for Args<(Command, T, T)>
+where
+    T: Serialize,
+{
+    fn arg(self) -> cmd::Arg<()> {
+        let Args((test, true_action, false_action)) = self;
+        let true_action = Command::from_json(true_action);
+        let false_action = Command::from_json(false_action);
+        Args((test, true_action, false_action)).arg()
+    }
+}
+
+/// The actions when branching on a query, as in
+/// `query.branch(r.args((true_action, false_action)))`
+impl Arg for Args<(Command, Command)> {
+    fn arg(self) -> cmd::Arg<()> {
+        let Args((true_action, false_action)) = self;
+        Command::new(TermType::Branch)
+            .with_arg(true_action)
+            .with_arg(false_action)
+            .into_arg()
+    }
+}
+
+/// Several tests, each with its action, tried in order before falling
+/// back to the last action
 impl<const N: usize> Arg for Args<([(Command, Command); N], Command)> {
     fn arg(self) -> cmd::Arg<()> {
         let Args((arr, false_action)) = self;
         let mut query = Command::new(TermType::Branch);
-        // TODO remove the clone in Rust v1.53
-        for (test, true_action) in arr.into_iter().cloned() {
+        for (test, true_action) in IntoIterator::into_iter(arr) {
             query = query.with_arg(test).with_arg(true_action);
         }
         query.with_arg(false_action).into_arg()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{cmd, r};
+
+    #[test]
+    fn r_branch() {
+        let query = r.branch(r.args((r.expr(15).gt(10), "big", "small")));
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[65,[[21,[15,10]],"big","small"]]"#;
+        assert_eq!(serialised, expected);
+    }
+
+    #[test]
+    fn r_branch_else_if() {
+        let n = || r.expr(15);
+        let query = r.branch(r.args((
+            [(n().gt(100), r.expr("huge")), (n().gt(10), r.expr("big"))],
+            r.expr("small"),
+        )));
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[65,[[21,[15,100]],"huge",[21,[15,10]],"big","small"]]"#;
+        assert_eq!(serialised, expected);
+    }
+
+    #[test]
+    fn r_expr_branch() {
+        let query = r
+            .expr(15)
+            .gt(10)
+            .branch(r.args((r.expr("big"), r.expr("small"))));
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[65,[[21,[15,10]],"big","small"]]"#;
+        assert_eq!(serialised, expected);
+    }
+}
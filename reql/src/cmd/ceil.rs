@@ -10,3 +10,9 @@ impl Arg for Command {
         Self::new(TermType::Ceil).with_arg(self).into_arg()
     }
 }
+
+impl Arg for f64 {
+    fn arg(self) -> cmd::Arg<()> {
+        Command::from_json(self).arg()
+    }
+}
@@ -44,13 +44,27 @@
 //! If the buffer limit is hit, early changes will be discarded, and the client will
 //! receive an object of the form
 //! `{error: "Changefeed cache over array size limit, skipped X elements."}`
-//! where `X` is the number of elements skipped.
+//! where `X` is the number of elements skipped. The driver turns that
+//! object into a [ChangefeedOverflow](crate::err::Runtime::ChangefeedOverflow)
+//! error, ending the feed, since changes have been lost.
 //!
 //! Commands that operate on streams (such as [filter](super::filter) or [map](super::map))
 //! can usually be chained after `changes`.  However, since the stream produced by
 //! `changes` has no ending, commands that need to consume the entire stream before
 //! returning (such as [reduce](super::reduce) or [count](super::count)) cannot.
 //!
+//! Each notification deserialises into [Change](crate::types::Change),
+//! whose `result_type` and `state` are typed as
+//! [ChangeType](crate::types::ChangeType) and [State](crate::types::State).
+//! With `include_initial` a feed first yields the current value of the
+//! selection as `initial` changes and then live changes; adding
+//! `include_states` brackets those initial values with `initializing` and
+//! `ready` state notifications, so `Change::is_ready` tells when the
+//! backfill is complete.
+//! A changefeed holds on to the connection it runs on, so run other
+//! queries on a separate session while it is open. Dropping the stream
+//! stops the changefeed on the server and frees the connection again.
+//!
 //! # Examples
 //!
 //! Subscribe to the changes on a table.
@@ -78,10 +92,22 @@
 //! {old_val: null, new_val: {id: 1}}
 //! ```
 
-use crate::{cmd, Command};
+use super::between::{self, Status};
+use crate::types::{Change, State};
+use crate::{cmd, r, Command, Result, Session};
+use async_stream::try_stream;
+use futures::stream::{Stream, StreamExt, TryStreamExt};
+use futures::{pin_mut, Future};
+use futures_timer::Delay;
+use log::trace;
 use ql2::term::TermType;
 use reql_macros::CommandOptions;
+use serde::de::DeserializeOwned;
 use serde::Serialize;
+use std::time::Duration;
+
+const RECONNECT_BASE_DELAY: Duration = Duration::from_millis(100);
+const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(10);
 
 /// Optional arguments to `changes`
 #[derive(Debug, Clone, Copy, CommandOptions, Serialize, Default, PartialEq, PartialOrd)]
@@ -177,3 +203,204 @@ impl Arg for Options {
         ().arg().with_opts(self)
     }
 }
+
+/// Run a changefeed that carries on when its connection fails
+///
+/// `connect` is called for the session the feed runs on, and again
+/// whenever the connection fails, for example with
+/// `|| r.connect(opts.clone())` or a pool's `session`. Connecting again
+/// is retried with a growing delay for as long as the server can't be
+/// reached. Errors reported by the server end the stream as usual, as
+/// does an error from the first call to `connect`.
+///
+/// Changes made while the feed was down are lost, so each time it is
+/// started again a change whose `state` is [State::Reconnected] is
+/// yielded first. Start the feed with `include_initial` to have it send
+/// the current values again after that, so consumers can resync,
+/// dropping the ones they already have.
+///
+/// ```
+/// # use reql::cmd::changes::{self, Options};
+/// # use reql::r;
+/// # use serde_json::Value;
+/// let query = r.table("games").changes(Options::new().include_initial(true));
+/// let feed = changes::reconnecting::<Value, _, _, _>(query, || r.connect(()));
+/// ```
+pub fn reconnecting<T, F, Fut, S>(
+    query: Command,
+    mut connect: F,
+) -> impl Stream<Item = Result<Change<T>>>
+where
+    T: Unpin + DeserializeOwned,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<S>>,
+    S: AsRef<Session>,
+{
+    try_stream! {
+        let mut session = connect().await?;
+        loop {
+            // The feed borrows the session, so it is dropped before the
+            // session is replaced
+            let failed = {
+                let feed = query.clone().run::<_, Change<T>>(session.as_ref());
+                pin_mut!(feed);
+                let mut failed = false;
+                while let Some(change) = feed.next().await {
+                    match change {
+                        Ok(change) => yield change,
+                        Err(error) if error.is_connection_error() => {
+                            trace!("changefeed connection failed; error: {}", error);
+                            failed = true;
+                            break;
+                        }
+                        Err(error) => Err(error)?,
+                    }
+                }
+                failed
+            };
+            if !failed {
+                // The feed was closed rather than cut off
+                break;
+            }
+            let mut attempt = 0;
+            session = loop {
+                match connect().await {
+                    Ok(session) => break session,
+                    Err(error) if error.is_connection_error() => {
+                        attempt += 1;
+                        let delay = (RECONNECT_BASE_DELAY * attempt).min(RECONNECT_MAX_DELAY);
+                        trace!("reconnecting changefeed; error: {}, attempt: {}", error, attempt);
+                        Delay::new(delay).await;
+                    }
+                    Err(error) => Err(error)?,
+                }
+            };
+            yield Change::from_state(State::Reconnected);
+        }
+    }
+}
+
+/// Resume processing the changes to a table after a restart, without
+/// missing the ones made while the consumer was down
+///
+/// `index` must be a secondary index on a field that grows with every
+/// write, such as an `updated_at` time set by all writers or by a write
+/// hook. Once a change has been processed, store that field of its
+/// `new_val` and pass the last one stored as `checkpoint`, or `None` to
+/// start from the beginning of the table.
+///
+/// The feed is started on `feed` first. The documents written after the
+/// checkpoint are then read on `session`, in index order, and yielded as
+/// `initial` changes, followed by the live changes. A document written
+/// while they are being read may be yielded twice, so processing must be
+/// idempotent. Deletions made while the consumer was down can't be
+/// replayed.
+pub fn resume_from<T, C>(
+    table: Command,
+    index: &'static str,
+    checkpoint: Option<C>,
+    feed: &Session,
+    session: &Session,
+) -> impl Stream<Item = Result<Change<T>>>
+where
+    T: Unpin + DeserializeOwned,
+    C: Serialize,
+{
+    let missed = missed(table.clone(), index, checkpoint);
+    let live = table.changes(Options::new().include_states(true));
+    let (feed, session) = (feed.clone(), session.clone());
+    try_stream! {
+        let live = live.run::<_, Change<T>>(&feed);
+        pin_mut!(live);
+        // Nothing written once the feed is ready will be missed
+        while let Some(change) = live.try_next().await? {
+            if change.is_ready() {
+                break;
+            }
+        }
+        let missed = missed.run::<_, T>(&session);
+        pin_mut!(missed);
+        while let Some(doc) = missed.try_next().await? {
+            yield Change::from_initial(doc);
+        }
+        while let Some(change) = live.try_next().await? {
+            if !change.is_state() {
+                yield change;
+            }
+        }
+    }
+}
+
+// The documents written after the checkpoint, in the order they were
+fn missed<C>(table: Command, index: &'static str, checkpoint: Option<C>) -> Command
+where
+    C: Serialize,
+{
+    let from = match checkpoint {
+        Some(checkpoint) => Command::from_json(checkpoint),
+        None => r.minval(),
+    };
+    let opts = between::Options::new()
+        .index(index)
+        .left_bound(Status::Open);
+    table
+        .between(r.args((from, r.maxval(), opts)))
+        .order_by(r.index(index))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{cmd, r};
+    use serde_json::{json, Value};
+
+    #[test]
+    fn r_table_changes_queue_size() {
+        let opts = super::Options::new().changefeed_queue_size(500);
+        let query = r.table("events").changes(opts);
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[152,[[15,["events"]]],{"changefeed_queue_size":500}]"#;
+        assert_eq!(serialised, expected);
+    }
+
+    #[test]
+    fn r_table_filter_changes() {
+        let query = r.table("users").filter(json!({"active": true})).changes(());
+        assert!(query.change_feed());
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[152,[[39,[[15,["users"]],{"active":true}]]]]"#;
+        assert_eq!(serialised, expected);
+    }
+
+    #[test]
+    fn r_table_get_all_changes() {
+        let query = r
+            .table("users")
+            .get_all(r.args((["alice", "bob"], r.index("name"))))
+            .changes(());
+        assert!(query.change_feed());
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[152,[[78,[[15,["users"]],"alice","bob"],{"index":"name"}]]]"#;
+        assert_eq!(serialised, expected);
+    }
+
+    #[test]
+    fn r_table_get_changes() {
+        let query = r.table("users").get("alice").changes(());
+        assert!(query.change_feed());
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[152,[[16,[[15,["users"]],"alice"]]]]"#;
+        assert_eq!(serialised, expected);
+    }
+
+    #[test]
+    fn missed_changes() {
+        let query = super::missed(r.table("events"), "at", Some(5));
+        let serialised: Value = serde_json::from_str(&cmd::serialise(&query)).unwrap();
+        let expected = json!([41,[[182,[[15,["events"]],5,[181,[]]],{"index":"at","left_bound":"open"}]],{"index":"at"}]);
+        assert_eq!(serialised, expected);
+        let query = super::missed::<u8>(r.table("events"), "at", None);
+        let serialised: Value = serde_json::from_str(&cmd::serialise(&query)).unwrap();
+        let expected = json!([41,[[182,[[15,["events"]],[180,[]],[181,[]]],{"index":"at","left_bound":"open"}]],{"index":"at"}]);
+        assert_eq!(serialised, expected);
+    }
+}
@@ -1,5 +1,9 @@
 //! Close an open connection
 //!
+//! [Session::close](crate::Session::close) closes the whole session while
+//! [Connection::close](crate::Connection::close) stops the changefeed
+//! running on a connection so it can be reused.
+//!
 //! Closing a connection normally waits until all outstanding requests have
 //! finished and then frees any open resources associated with the
 //! connection. By passing `SkipNoreplyWait` as the argument, the connection
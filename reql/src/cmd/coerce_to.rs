@@ -10,3 +10,34 @@ impl Arg for Command {
         Self::new(TermType::CoerceTo).with_arg(self).into_arg()
     }
 }
+
+/// A type name such as `"array"`, `"object"`, `"string"` or `"number"`
+impl<T> Arg for T
+where
+    T: Into<String>,
+{
+    fn arg(self) -> cmd::Arg<()> {
+        Command::from_json(self.into()).arg()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{cmd, r};
+
+    #[test]
+    fn r_table_coerce_to_array() {
+        let query = r.table("foo").coerce_to("array");
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[51,[[15,["foo"]],"array"]]"#;
+        assert_eq!(serialised, expected);
+    }
+
+    #[test]
+    fn r_expr_type_of() {
+        let query = r.expr([1, 2]).type_of();
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[52,[[2,[1,2]]]]"#;
+        assert_eq!(serialised, expected);
+    }
+}
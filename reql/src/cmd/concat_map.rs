@@ -1,5 +1,42 @@
-use crate::cmd;
+use crate::{cmd, Command, Func};
+use ql2::term::TermType;
 
 pub trait Arg {
     fn arg(self) -> cmd::Arg<()>;
 }
+
+impl Arg for Command {
+    fn arg(self) -> cmd::Arg<()> {
+        Self::new(TermType::ConcatMap).with_arg(self).into_arg()
+    }
+}
+
+impl Arg for Func {
+    fn arg(self) -> cmd::Arg<()> {
+        let Func(func) = self;
+        func.arg()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{self as reql, cmd, func, r};
+    use serde_json::{json, Value};
+
+    #[test]
+    fn r_table_concat_map() {
+        let query = r
+            .table("marvel")
+            .concat_map(func!(|hero| hero.bracket("defeatedMonsters")));
+        let serialised: Value = serde_json::from_str(&cmd::serialise(&query)).unwrap();
+        let var = serialised[1][1][1][0][1][0].clone();
+        let expected = json!([
+            40,
+            [
+                [15, ["marvel"]],
+                [69, [[2, [var]], [170, [[10, [var]], "defeatedMonsters"]]]]
+            ]
+        ]);
+        assert_eq!(serialised, expected);
+    }
+}
@@ -1,25 +1,42 @@
 //! Create a new connection to the database server
+//!
+//! Queries and responses are sent as uncompressed JSON. The `V1_0` wire
+//! protocol, the only one spoken by RethinkDB 2.3 and later, has no way
+//! to negotiate compression, so there is no option for it. To save
+//! bandwidth on slow links, compress at the transport level instead, for
+//! example with an SSH tunnel, or fetch fewer fields with `pluck`.
 
 use super::args::Args;
-use super::{debug, StaticString};
+use super::{debug, ReadMode, StaticString};
 use crate::{err, InnerSession, Result, Session};
 use async_net::{AsyncToSocketAddrs, TcpStream};
 use dashmap::DashMap;
-use futures::io::{AsyncReadExt, AsyncWriteExt};
+use futures::future::{self, Either};
+use futures::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
 use futures::lock::Mutex;
-use log::trace;
+use futures::Future;
+use futures_timer::Delay;
+use log::{trace, warn};
 use ql2::version_dummy::Version;
+use rand::Rng;
 use reql_macros::CommandOptions;
 use scram::client::{ScramClient, ServerFinal, ServerFirst};
 use serde::{Deserialize, Serialize};
+use socket2::{SockRef, TcpKeepalive};
 use std::borrow::Cow;
 use std::net::SocketAddr;
+use std::pin::Pin;
 use std::sync::atomic::{AtomicBool, AtomicU64};
 use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use std::{fmt, io};
 
 const BUF_SIZE: usize = 1024;
 const NULL_BYTE: u8 = b'\0';
 const PROTOCOL_VERSION: usize = 0;
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(100);
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(10);
 
 pub(crate) const DEFAULT_DB: &str = "test";
 
@@ -27,7 +44,9 @@ pub(crate) const DEFAULT_DB: &str = "test";
 #[derive(Debug, Clone, CommandOptions, Eq, PartialEq, Ord, PartialOrd, Hash)]
 #[non_exhaustive]
 pub struct Options {
-    /// Host of the RethinkDB instance. The default value is `localhost`.
+    /// Host of the RethinkDB instance, optionally followed by a port that
+    /// overrides `port` (`host:port` or `[ipv6]:port`). The default value
+    /// is `localhost`.
     pub host: Cow<'static, str>,
     /// The driver port, by default `28015`.
     pub port: u16,
@@ -37,6 +56,47 @@ pub struct Options {
     pub user: Cow<'static, str>,
     /// The password for the user account to connect as (default `""`, empty).
     pub password: Cow<'static, str>,
+    /// How long to wait for the TCP connection to be established before
+    /// giving up. By default there is no timeout.
+    pub connect_timeout: Option<Duration>,
+    /// How long to wait for a response from the server before the
+    /// connection is considered broken. Change feeds waiting for their
+    /// next change are not subject to it. By default there is no timeout.
+    pub read_timeout: Option<Duration>,
+    /// How long to wait for a query to be written to the server before the
+    /// connection is considered broken. By default there is no timeout.
+    pub write_timeout: Option<Duration>,
+    /// Enables TCP keepalive, sending the first probe after the connection
+    /// has been idle for this long. Keepalive helps connection pools notice
+    /// dead peers (e.g. behind NAT gateways) before a query fails on them.
+    /// Disabled by default.
+    pub keepalive: Option<Duration>,
+    /// Disables Nagle's algorithm so small queries are sent immediately
+    /// (default `true`).
+    pub nodelay: bool,
+    /// How many more times to try establishing the TCP connection if the
+    /// first attempt fails (default `0`). Attempts are spaced out using
+    /// exponential backoff with jitter.
+    pub retries: u8,
+    /// The `read_mode` of queries that don't set their own in their run
+    /// options, which is `single` on the server unless changed.
+    ///
+    /// `single` reads the latest data from the primary replica, but may
+    /// see writes that are later lost if the primary fails. `majority`
+    /// only returns data committed to a majority of replicas, at the
+    /// cost of a round trip between them. `outdated` reads from whichever
+    /// replica is closest and may return stale data, which takes load off
+    /// the primaries for reads that don't need to be up to date.
+    pub read_mode: Option<ReadMode>,
+    /// Connects over TLS, trusting the CA certificates in this PEM file to
+    /// verify the server, whose certificate must be valid for `tls_domain`.
+    /// Needs the `tls` feature. By default the connection is unencrypted.
+    pub ca_certs: Option<Cow<'static, str>>,
+    /// The name the server's certificate is verified against, by default
+    /// the host in `host`. Set it when passing the address to connect to
+    /// separately, as in `r.args((addr, opts))`, since `host` isn't the
+    /// server dialled then.
+    pub tls_domain: Option<Cow<'static, str>>,
 }
 
 impl Default for Options {
@@ -47,13 +107,22 @@ impl Default for Options {
             db: DEFAULT_DB.static_string(),
             user: "admin".static_string(),
             password: "".static_string(),
+            connect_timeout: None,
+            read_timeout: None,
+            write_timeout: None,
+            keepalive: None,
+            nodelay: true,
+            retries: 0,
+            read_mode: None,
+            ca_certs: None,
+            tls_domain: None,
         }
     }
 }
 
 /// The arguments accepted by [crate::r::connect]
 pub trait Arg {
-    type ToAddrs: AsyncToSocketAddrs;
+    type ToAddrs: AsyncToSocketAddrs + Clone + fmt::Debug;
 
     fn into_connect_opts(self) -> (Option<Self::ToAddrs>, Options);
 }
@@ -74,18 +143,29 @@ impl Arg for Options {
     }
 }
 
-impl<'a> Arg for &'a str {
-    type ToAddrs = (&'a str, u16);
+/// The server to connect to, as `host`, `host:port`, `[ipv6]` or `[ipv6]:port`
+///
+/// The default port is used if none is given.
+impl Arg for &str {
+    type ToAddrs = SocketAddr;
 
     fn into_connect_opts(self) -> (Option<Self::ToAddrs>, Options) {
-        let opts = Options::default();
-        (Some((self, opts.port)), opts)
+        self.to_owned().into_connect_opts()
+    }
+}
+
+/// See the implementation for `&str`
+impl Arg for String {
+    type ToAddrs = SocketAddr;
+
+    fn into_connect_opts(self) -> (Option<Self::ToAddrs>, Options) {
+        (None, Options::default().host(self))
     }
 }
 
 impl<T> Arg for Args<(T, Options)>
 where
-    T: AsyncToSocketAddrs,
+    T: AsyncToSocketAddrs + Clone + fmt::Debug,
 {
     type ToAddrs = T;
 
@@ -97,16 +177,27 @@ where
 
 pub(crate) async fn new<T>((addr, options): (Option<T>, Options)) -> Result<Session>
 where
-    T: AsyncToSocketAddrs,
+    T: AsyncToSocketAddrs + Clone + fmt::Debug,
 {
     let stream = match addr {
-        Some(addr) => TcpStream::connect(addr).await?,
-        None => TcpStream::connect((options.host.as_ref(), options.port)).await?,
+        Some(addr) => connect(addr, &options).await?,
+        None => connect(parse_server(&options.host, options.port)?, &options).await?,
     };
+    configure(&stream, &options)?;
+    let socket = stream.clone();
+    let stream = secure(stream, &options).await?;
+    let (stream, server_version) = handshake(stream, &options).await?;
     let inner = InnerSession {
-        stream: Mutex::new(handshake(stream, &options).await?),
+        stream: Mutex::new(stream),
+        socket,
+        server_version,
         db: Mutex::new(options.db),
+        read_timeout: options.read_timeout,
+        write_timeout: options.write_timeout,
+        read_mode: options.read_mode,
         channels: DashMap::new(),
+        pending_stops: Default::default(),
+        read_buf: Default::default(),
         token: AtomicU64::new(0),
         broken: AtomicBool::new(false),
         change_feed: AtomicBool::new(false),
@@ -116,36 +207,300 @@ where
     })
 }
 
+// Splits a server address into its host and port
+//
+// IPv6 addresses may be given either bare (`::1`) or in brackets (`[::1]`);
+// the brackets are required if a port is given.
+fn parse_server(server: &str, default_port: u16) -> Result<(&str, u16)> {
+    let invalid = |reason| {
+        let msg = format!("invalid server address `{}`; {}", server, reason);
+        err::Driver::Other(msg)
+    };
+    let (host, port) = if let Some(rest) = server.strip_prefix('[') {
+        let end = rest.find(']').ok_or_else(|| invalid("missing `]`"))?;
+        let port = match &rest[end + 1..] {
+            "" => None,
+            port => Some(
+                port.strip_prefix(':')
+                    .ok_or_else(|| invalid("expected `:`"))?,
+            ),
+        };
+        (&rest[..end], port)
+    } else {
+        match server.matches(':').count() {
+            0 => (server, None),
+            1 => {
+                let (host, port) = server.split_at(server.find(':').unwrap());
+                (host, Some(&port[1..]))
+            }
+            // a bare IPv6 address
+            _ => (server, None),
+        }
+    };
+    if host.is_empty() {
+        return Err(invalid("missing host").into());
+    }
+    let port = match port {
+        Some(port) => port.parse().map_err(|_| invalid("invalid port"))?,
+        None => default_port,
+    };
+    Ok((host, port))
+}
+
+async fn connect<T>(addr: T, opts: &Options) -> Result<TcpStream>
+where
+    T: AsyncToSocketAddrs + Clone + fmt::Debug,
+{
+    let mut attempt = 0;
+    loop {
+        match connect_once(addr.clone(), opts.connect_timeout).await {
+            Ok(stream) => return Ok(stream),
+            Err(error) if attempt < opts.retries => {
+                let delay = backoff(attempt);
+                attempt += 1;
+                warn!(
+                    "failed to connect to {:?}; error: {}, attempt: {}, retrying in {:?}",
+                    addr, error, attempt, delay
+                );
+                Delay::new(delay).await;
+            }
+            Err(error) => return Err(error),
+        }
+    }
+}
+
+// Exponential backoff with "equal jitter", half of the delay is fixed and
+// the other half is random
+fn backoff(attempt: u8) -> Duration {
+    let delay = RETRY_BASE_DELAY
+        .saturating_mul(1 << attempt.min(16))
+        .min(RETRY_MAX_DELAY);
+    let half = delay / 2;
+    let jitter = rand::thread_rng().gen_range(0..=half.as_millis() as u64);
+    half + Duration::from_millis(jitter)
+}
+
+async fn connect_once<T>(addr: T, timeout: Option<Duration>) -> Result<TcpStream>
+where
+    T: AsyncToSocketAddrs + fmt::Debug,
+{
+    trace!("connecting to {:?}; timeout: {:?}", addr, timeout);
+    let server = format!("{:?}", addr);
+    let stream = async {
+        TcpStream::connect(addr).await.map_err(|error| {
+            let msg = format!("failed to connect to {}; {}", server, error);
+            io::Error::new(error.kind(), msg)
+        })
+    };
+    with_timeout(stream, timeout, || format!("connecting to {}", server)).await
+}
+
+fn configure(stream: &TcpStream, opts: &Options) -> Result<()> {
+    stream.set_nodelay(opts.nodelay)?;
+    if let Some(time) = opts.keepalive {
+        let keepalive = TcpKeepalive::new().with_time(time);
+        SockRef::from(stream).set_tcp_keepalive(&keepalive)?;
+    }
+    Ok(())
+}
+
+// The stream a session talks to the server over, which is encrypted
+// if `ca_certs` is set
+pub(crate) enum Transport {
+    Tcp(TcpStream),
+    #[cfg(feature = "tls")]
+    Tls(async_native_tls::TlsStream<TcpStream>),
+}
+
+impl Transport {
+    // The socket underneath, for socket options and shutting it down
+    pub(crate) fn tcp(&self) -> &TcpStream {
+        match self {
+            Self::Tcp(stream) => stream,
+            #[cfg(feature = "tls")]
+            Self::Tls(stream) => stream.get_ref(),
+        }
+    }
+
+    // The server may send TLS records that aren't responses, such as
+    // session tickets, so bytes waiting on the socket don't mean much
+    pub(crate) fn is_tls(&self) -> bool {
+        match self {
+            Self::Tcp(_) => false,
+            #[cfg(feature = "tls")]
+            Self::Tls(_) => true,
+        }
+    }
+}
+
+impl fmt::Debug for Transport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Tcp(stream) => f.debug_tuple("Tcp").field(stream).finish(),
+            #[cfg(feature = "tls")]
+            Self::Tls(stream) => f.debug_tuple("Tls").field(stream.get_ref()).finish(),
+        }
+    }
+}
+
+impl AsyncRead for Transport {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        match self.get_mut() {
+            Self::Tcp(stream) => Pin::new(stream).poll_read(cx, buf),
+            #[cfg(feature = "tls")]
+            Self::Tls(stream) => Pin::new(stream).poll_read(cx, buf),
+        }
+    }
+}
+
+impl AsyncWrite for Transport {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        match self.get_mut() {
+            Self::Tcp(stream) => Pin::new(stream).poll_write(cx, buf),
+            #[cfg(feature = "tls")]
+            Self::Tls(stream) => Pin::new(stream).poll_write(cx, buf),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            Self::Tcp(stream) => Pin::new(stream).poll_flush(cx),
+            #[cfg(feature = "tls")]
+            Self::Tls(stream) => Pin::new(stream).poll_flush(cx),
+        }
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            Self::Tcp(stream) => Pin::new(stream).poll_close(cx),
+            #[cfg(feature = "tls")]
+            Self::Tls(stream) => Pin::new(stream).poll_close(cx),
+        }
+    }
+}
+
+// Wraps the connection in TLS if `ca_certs` is set
+async fn secure(stream: TcpStream, opts: &Options) -> Result<Transport> {
+    match &opts.ca_certs {
+        Some(ca_certs) => tls(stream, tls_domain(opts)?, ca_certs, opts).await,
+        None => Ok(Transport::Tcp(stream)),
+    }
+}
+
+// The name the server's certificate is verified against
+fn tls_domain(opts: &Options) -> Result<&str> {
+    match &opts.tls_domain {
+        Some(domain) => Ok(domain),
+        None => Ok(parse_server(&opts.host, opts.port)?.0),
+    }
+}
+
+#[cfg(feature = "tls")]
+async fn tls(stream: TcpStream, host: &str, ca_certs: &str, opts: &Options) -> Result<Transport> {
+    use async_native_tls::{Certificate, TlsConnector};
+
+    let tls_error = |msg: String| err::Error::from(err::Driver::Tls(msg));
+    let pem = std::fs::read_to_string(ca_certs).map_err(|error| {
+        tls_error(format!(
+            "failed to read CA certificates from `{}`; {}",
+            ca_certs, error
+        ))
+    })?;
+    let mut connector = TlsConnector::new();
+    let certs = certificates(&pem);
+    if certs.is_empty() {
+        let msg = format!("no CA certificates found in `{}`", ca_certs);
+        return Err(tls_error(msg));
+    }
+    for cert in certs {
+        let cert = Certificate::from_pem(cert.as_bytes())
+            .map_err(|error| tls_error(format!("invalid CA certificate; {}", error)))?;
+        connector = connector.add_root_certificate(cert);
+    }
+    trace!("starting TLS handshake; host: {}", host);
+    let msg = || format!("establishing TLS with {}", host);
+    let handshake = async { Ok(connector.connect(host, stream).await) };
+    let stream = with_timeout(handshake, opts.read_timeout, msg)
+        .await?
+        .map_err(|error| tls_error(error.to_string()))?;
+    trace!("TLS established; host: {}", host);
+    Ok(Transport::Tls(stream))
+}
+
+#[cfg(not(feature = "tls"))]
+async fn tls(_: TcpStream, _: &str, _: &str, _: &Options) -> Result<Transport> {
+    let msg = String::from("`ca_certs` needs the `tls` feature of reql to be enabled");
+    Err(err::Driver::Tls(msg).into())
+}
+
+// Splits a PEM bundle into its certificates, which native-tls only
+// parses one at a time
+#[cfg(feature = "tls")]
+fn certificates(pem: &str) -> Vec<&str> {
+    const END: &str = "-----END CERTIFICATE-----";
+    pem.split_inclusive(END)
+        .filter(|cert| cert.ends_with(END))
+        .collect()
+}
+
+// Awaits an IO future, giving up with `Driver::Timeout` if it doesn't
+// complete within `timeout`
+pub(crate) async fn with_timeout<F, T, M>(future: F, timeout: Option<Duration>, msg: M) -> Result<T>
+where
+    F: Future<Output = io::Result<T>>,
+    M: FnOnce() -> String,
+{
+    let timeout = match timeout {
+        Some(timeout) => timeout,
+        None => return Ok(future.await?),
+    };
+    futures::pin_mut!(future);
+    match future::select(future, Delay::new(timeout)).await {
+        Either::Left((result, _)) => Ok(result?),
+        Either::Right(_) => {
+            let msg = format!("timed out {} after {:?}", msg(), timeout);
+            Err(err::Driver::Timeout(msg).into())
+        }
+    }
+}
+
 // Performs the actual handshake
 //
 // This method optimises message exchange as suggested in the RethinkDB
 // documentation by sending message 3 right after message 1, without waiting
 // for message 2 first.
-async fn handshake(mut stream: TcpStream, opts: &Options) -> Result<TcpStream> {
+async fn handshake(mut stream: Transport, opts: &Options) -> Result<(Transport, String)> {
     trace!("sending supported version to RethinkDB");
-    stream
-        .write_all(&(Version::V10 as i32).to_le_bytes())
-        .await?; // message 1
+    let version = (Version::V10 as i32).to_le_bytes();
+    write(&mut stream, &version, opts).await?; // message 1
 
     let scram = ScramClient::new(opts.user.as_ref(), opts.password.as_ref(), None);
     let (scram, msg) = client_first(scram)?;
     trace!("sending client first message");
-    stream.write_all(&msg).await?; // message 3
+    write(&mut stream, &msg, opts).await?; // message 3
 
     let mut buf = [0u8; BUF_SIZE];
 
     trace!("receiving message(s) from RethinkDB");
-    stream.read(&mut buf).await?; // message 2
+    read(&mut stream, &mut buf, opts).await?; // message 2
     let (len, resp) = bytes(&buf, 0);
     trace!("received server info; info: {}", debug(resp));
-    ServerInfo::validate(resp)?;
+    let server_version = ServerInfo::validate(resp)?;
 
     let offset = len + 1;
     let resp = if offset < BUF_SIZE && buf[offset] != NULL_BYTE {
         bytes(&buf, offset).1
     } else {
         trace!("reading auth response");
-        stream.read(&mut buf).await?; // message 4
+        read(&mut stream, &mut buf, opts).await?; // message 4
         bytes(&buf, 0).1
     };
     trace!("received auth response");
@@ -160,17 +515,30 @@ async fn handshake(mut stream: TcpStream, opts: &Options) -> Result<TcpStream> {
 
     let (scram, msg) = client_final(scram, &auth)?;
     trace!("sending client final message");
-    stream.write_all(&msg).await?; // message 5
+    write(&mut stream, &msg, opts).await?; // message 5
 
     trace!("reading server final message");
-    stream.read(&mut buf).await?; // message 6
+    read(&mut stream, &mut buf, opts).await?; // message 6
     let resp = bytes(&buf, 0).1;
     trace!("received server final message");
     server_final(scram, resp)?;
 
     trace!("client connected successfully");
 
-    Ok(stream)
+    Ok((stream, server_version))
+}
+
+async fn write(stream: &mut Transport, buf: &[u8], opts: &Options) -> Result<()> {
+    let msg = || String::from("sending handshake message");
+    with_timeout(stream.write_all(buf), opts.write_timeout, msg).await
+}
+
+async fn read(stream: &mut Transport, buf: &mut [u8], opts: &Options) -> Result<usize> {
+    let msg = || String::from("waiting for handshake response");
+    match with_timeout(stream.read(buf), opts.read_timeout, msg).await? {
+        0 => Err(io::Error::from(io::ErrorKind::UnexpectedEof).into()),
+        len => Ok(len),
+    }
 }
 
 fn bytes(buf: &[u8], offset: usize) -> (usize, &[u8]) {
@@ -195,10 +563,11 @@ struct ServerInfo<'a> {
 }
 
 impl ServerInfo<'_> {
-    fn validate(resp: &[u8]) -> Result<()> {
+    // Returns the version of the server
+    fn validate(resp: &[u8]) -> Result<String> {
         let info = serde_json::from_slice::<ServerInfo>(resp)?;
         if !info.success {
-            return Err(err::Runtime::Internal(debug(resp)).into());
+            return Err(err::Runtime::Internal(debug(resp).into()).into());
         }
         #[allow(clippy::absurd_extreme_comparisons)]
         if PROTOCOL_VERSION < info.min_protocol_version
@@ -210,9 +579,9 @@ impl ServerInfo<'_> {
                 min = info.min_protocol_version,
                 max = info.max_protocol_version,
             );
-            return Err(err::Driver::Other(msg).into());
+            return Err(err::Auth::UnsupportedProtocol(msg).into());
         }
-        Ok(())
+        Ok(info.server_version.to_owned())
     }
 }
 
@@ -243,8 +612,7 @@ struct AuthConfirmation {
 fn client_final(scram: ServerFirst<'_>, auth: &str) -> Result<(ServerFinal, Vec<u8>)> {
     let scram = scram
         .handle_server_first(auth)
-        .map_err(|x| x.to_string())
-        .map_err(err::Driver::Other)?;
+        .map_err(|error| err::Auth::Scram(error.to_string()))?;
     let (scram, client_final) = scram.client_final();
     let conf = AuthConfirmation {
         authentication: client_final,
@@ -267,12 +635,12 @@ impl AuthResponse {
         let info = serde_json::from_slice::<AuthResponse>(resp)?;
         if !info.success {
             // If error code is between 10 and 20, this is an auth error
-            if let Some(10..=20) = info.error_code {
+            if let Some(code @ 10..=20) = info.error_code {
                 if let Some(msg) = info.error {
-                    return Err(err::Driver::Auth(msg).into());
+                    return Err(err::Auth::from_code(code, msg).into());
                 }
             }
-            return Err(err::Runtime::Internal(debug(resp)).into());
+            return Err(err::Runtime::Internal(debug(resp).into()).into());
         }
         Ok(info)
     }
@@ -282,8 +650,215 @@ fn server_final(scram: ServerFinal, resp: &[u8]) -> Result<()> {
     let info = AuthResponse::from_slice(resp)?;
     if let Some(auth) = info.authentication {
         if let Err(error) = scram.handle_server_final(&auth) {
-            return Err(err::Driver::Other(error.to_string()).into());
+            return Err(err::Auth::Scram(error.to_string()).into());
         }
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        client_final, client_first, configure, parse_server, Arg, AuthResponse, Options, ServerInfo,
+    };
+    use crate::{r, Auth, Driver, Error};
+    use async_net::TcpStream;
+    use scram::client::ScramClient;
+    use socket2::{Domain, SockRef, Socket, Type};
+    use std::io;
+    use std::net::{SocketAddr, TcpListener};
+    use std::time::{Duration, Instant};
+
+    #[tokio::test]
+    async fn handshake_times_out_on_silent_server() {
+        // The OS accepts the connection but nobody ever answers it
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let opts = Options::new().read_timeout(Duration::from_millis(100));
+        match r.connect(r.args((addr, opts))).await.unwrap_err() {
+            Error::Driver(Driver::Timeout(_)) => {}
+            error => panic!("{:?}", error),
+        }
+    }
+
+    #[tokio::test]
+    async fn connect_times_out_on_full_backlog() {
+        // Once the accept queue of a listener that never accepts is full,
+        // further connection attempts are left hanging by the OS
+        let listener = Socket::new(Domain::IPV4, Type::STREAM, None).unwrap();
+        listener
+            .bind(&"127.0.0.1:0".parse::<SocketAddr>().unwrap().into())
+            .unwrap();
+        listener.listen(0).unwrap();
+        let addr = listener.local_addr().unwrap();
+        let mut backlog = Vec::new();
+        for _ in 0..3 {
+            let socket = Socket::new(Domain::IPV4, Type::STREAM, None).unwrap();
+            socket.set_nonblocking(true).unwrap();
+            let _ = socket.connect(&addr);
+            backlog.push(socket);
+        }
+        let addr = addr.as_socket().unwrap();
+        let opts = Options::new().connect_timeout(Duration::from_millis(100));
+        let start = Instant::now();
+        match r.connect(r.args((addr, opts))).await.unwrap_err() {
+            Error::Driver(Driver::Timeout(msg)) => assert!(msg.contains("connecting to")),
+            error => panic!("{:?}", error),
+        }
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn unreadable_ca_certs_is_a_tls_error() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let opts = Options::new().ca_certs("/nonexistent/ca.pem");
+        match r.connect(r.args((addr, opts))).await.unwrap_err() {
+            Error::Driver(Driver::Tls(_)) => {}
+            error => panic!("{:?}", error),
+        }
+    }
+
+    #[test]
+    fn tls_domain_defaults_to_host() {
+        let opts = Options::new().host("db.example.com:28016");
+        assert_eq!(super::tls_domain(&opts).unwrap(), "db.example.com");
+        let opts = opts.tls_domain("rethinkdb.internal");
+        assert_eq!(super::tls_domain(&opts).unwrap(), "rethinkdb.internal");
+    }
+
+    #[cfg(feature = "tls")]
+    #[test]
+    fn ca_bundle_is_split_into_certificates() {
+        let pem = "\
+-----BEGIN CERTIFICATE-----
+MIIB
+-----END CERTIFICATE-----
+-----BEGIN CERTIFICATE-----
+MIIC
+-----END CERTIFICATE-----
+";
+        let certs = super::certificates(pem);
+        assert_eq!(certs.len(), 2);
+        assert!(certs[1].trim_start().starts_with("-----BEGIN"));
+        assert!(certs[1].contains("MIIC"));
+        assert!(super::certificates("").is_empty());
+    }
+
+    #[test]
+    fn options_from_runtime_strings() {
+        let config = (String::from("db.example.com"), String::from("marvel"));
+        let (addr, opts) = config.0.clone().into_connect_opts();
+        assert_eq!(addr, None);
+        assert_eq!(opts.host, config.0);
+        let opts = Options::new().host(&config.0).db(config.1);
+        assert_eq!(opts.host, "db.example.com");
+        assert_eq!(opts.db, "marvel");
+    }
+
+    #[tokio::test]
+    async fn socket_options_are_applied() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let stream = TcpStream::connect(listener.local_addr().unwrap())
+            .await
+            .unwrap();
+        let socket = SockRef::from(&stream);
+
+        configure(&stream, &Options::new()).unwrap();
+        assert!(socket.nodelay().unwrap());
+        assert!(!socket.keepalive().unwrap());
+
+        let opts = Options::new()
+            .nodelay(false)
+            .keepalive(Duration::from_secs(30));
+        configure(&stream, &opts).unwrap();
+        assert!(!socket.nodelay().unwrap());
+        assert!(socket.keepalive().unwrap());
+    }
+
+    #[tokio::test]
+    async fn retries_then_returns_last_error() {
+        // Grab a free port and close it again so connecting is refused
+        let addr = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let start = Instant::now();
+        let opts = Options::new().retries(2);
+        match r.connect(r.args((addr, opts))).await.unwrap_err() {
+            Error::Driver(Driver::Io(io::ErrorKind::ConnectionRefused, _)) => {}
+            error => panic!("{:?}", error),
+        }
+        // at least half of each of the 100ms and 200ms backoff delays
+        assert!(start.elapsed() >= Duration::from_millis(150));
+    }
+
+    #[test]
+    fn server_addresses() {
+        assert_eq!(
+            parse_server("localhost", 28015).unwrap(),
+            ("localhost", 28015)
+        );
+        assert_eq!(
+            parse_server("localhost:28016", 28015).unwrap(),
+            ("localhost", 28016)
+        );
+        assert_eq!(parse_server("::1", 28015).unwrap(), ("::1", 28015));
+        assert_eq!(parse_server("[::1]", 28015).unwrap(), ("::1", 28015));
+        assert_eq!(parse_server("[::1]:28016", 28015).unwrap(), ("::1", 28016));
+        for server in &["", ":28015", "[::1", "[::1]28015", "localhost:port"] {
+            assert!(parse_server(server, 28015).is_err(), "{}", server);
+        }
+    }
+
+    #[test]
+    fn auth_errors_are_classified() {
+        let cases = [
+            (
+                12,
+                "Wrong password",
+                Auth::Unauthorized("Wrong password".into()),
+            ),
+            (17, "Unknown user", Auth::UnknownUser("Unknown user".into())),
+            (10, "Bad nonce", Auth::Other(10, "Bad nonce".into())),
+        ];
+        for (code, msg, expected) in cases {
+            let resp = format!(
+                r#"{{"success":false,"error":"{}","error_code":{}}}"#,
+                msg, code
+            );
+            match AuthResponse::from_slice(resp.as_bytes()).unwrap_err() {
+                Error::Driver(Driver::Auth(auth)) => {
+                    assert_eq!(auth.message(), msg);
+                    assert_eq!(auth, expected);
+                }
+                error => panic!("{:?}", error),
+            }
+        }
+    }
+
+    #[test]
+    fn unsupported_protocol_is_an_auth_error() {
+        let resp = br#"{"success":true,"min_protocol_version":1,"max_protocol_version":2,"server_version":"9.9.9"}"#;
+        match ServerInfo::validate(resp).unwrap_err() {
+            Error::Driver(Driver::Auth(Auth::UnsupportedProtocol(_))) => {}
+            error => panic!("{:?}", error),
+        }
+    }
+
+    #[test]
+    fn server_version_is_kept() {
+        let resp = br#"{"success":true,"min_protocol_version":0,"max_protocol_version":0,"server_version":"2.4.1~0bionic"}"#;
+        assert_eq!(ServerInfo::validate(resp).unwrap(), "2.4.1~0bionic");
+    }
+
+    #[test]
+    fn bogus_server_first_is_an_auth_error() {
+        let scram = ScramClient::new("admin", "", None);
+        let (scram, _) = client_first(scram).unwrap();
+        match client_final(scram, "not a scram message").unwrap_err() {
+            Error::Driver(Driver::Auth(Auth::Scram(_))) => {}
+            error => panic!("{:?}", error),
+        }
+    }
+}
@@ -1,5 +1,7 @@
-use crate::{cmd, Command};
+use super::args::Args;
+use crate::{cmd, Command, Func};
 use ql2::term::TermType;
+use serde::Serialize;
 
 pub trait Arg {
     fn arg(self) -> cmd::Arg<()>;
@@ -10,3 +12,76 @@ impl Arg for Command {
         Self::new(TermType::Contains).with_arg(self).into_arg()
     }
 }
+
+impl<T> Arg for T
+where
+    T: Serialize,
+{
+    fn arg(self) -> cmd::Arg<()> {
+        Command::from_json(self).arg()
+    }
+}
+
+/// A predicate that at least one element has to satisfy
+impl Arg for Func {
+    fn arg(self) -> cmd::Arg<()> {
+        let Func(func) = self;
+        func.arg()
+    }
+}
+
+/// Values that all have to be in the sequence
+impl<T, const N: usize> Arg for Args<[T; N]>
+where
+    T: Serialize,
+{
+    fn arg(self) -> cmd::Arg<()> {
+        let Args(values) = self;
+        let mut query = Command::new(TermType::Contains);
+        for value in IntoIterator::into_iter(values) {
+            query = query.with_arg(Command::from_json(value));
+        }
+        query.into_arg()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{self as reql, cmd, func, r};
+    use serde_json::{json, Value};
+
+    #[test]
+    fn r_table_contains() {
+        let query = r.table("marvel").get_field("name").contains("Iron Man");
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[93,[[31,[[15,["marvel"]],"name"]],"Iron Man"]]"#;
+        assert_eq!(serialised, expected);
+    }
+
+    #[test]
+    fn r_expr_contains_all() {
+        let query = r
+            .expr(json!(["red", "green", "blue"]))
+            .contains(r.args(["red", "blue"]));
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[93,[[2,["red","green","blue"]],"red","blue"]]"#;
+        assert_eq!(serialised, expected);
+    }
+
+    #[test]
+    fn r_table_contains_predicate() {
+        let query = r
+            .table("marvel")
+            .contains(func!(|hero| hero.bracket("age").gt(100)));
+        let serialised: Value = serde_json::from_str(&cmd::serialise(&query)).unwrap();
+        let var = serialised[1][1][1][0][1][0].clone();
+        let expected = json!([
+            93,
+            [
+                [15, ["marvel"]],
+                [69, [[2, [var]], [21, [[170, [[10, [var]], "age"]], 100]]]]
+            ]
+        ]);
+        assert_eq!(serialised, expected);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{cmd, Command};
+use crate::{cmd, Command, Func};
 use ql2::term::TermType;
 
 pub trait Arg {
@@ -10,3 +10,48 @@ impl Arg for Command {
         Self::new(TermType::Count).with_arg(self).into_arg()
     }
 }
+
+impl Arg for () {
+    fn arg(self) -> cmd::Arg<()> {
+        Command::new(TermType::Count).into_arg()
+    }
+}
+
+/// Only count the rows for which the predicate returns `true`
+impl Arg for Func {
+    fn arg(self) -> cmd::Arg<()> {
+        let Func(func) = self;
+        func.arg()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{self as reql, cmd, func, r};
+    use serde_json::{json, Value};
+
+    #[test]
+    fn r_table_count() {
+        let query = r.table("foo").count(());
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[43,[[15,["foo"]]]]"#;
+        assert_eq!(serialised, expected);
+    }
+
+    #[test]
+    fn r_table_count_predicate() {
+        let query = r
+            .table("foo")
+            .count(func!(|doc| doc.bracket("age").gt(r.expr(18))));
+        let serialised: Value = serde_json::from_str(&cmd::serialise(&query)).unwrap();
+        let var = serialised[1][1][1][0][1][0].clone();
+        let expected = json!([
+            43,
+            [
+                [15, ["foo"]],
+                [69, [[2, [var]], [21, [[170, [[10, [var]], "age"]], 18]]]]
+            ]
+        ]);
+        assert_eq!(serialised, expected);
+    }
+}
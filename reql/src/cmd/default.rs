@@ -1,5 +1,6 @@
-use crate::{cmd, Command};
+use crate::{cmd, Command, Func};
 use ql2::term::TermType;
+use serde::Serialize;
 
 pub trait Arg {
     fn arg(self) -> cmd::Arg<()>;
@@ -10,3 +11,37 @@ impl Arg for Command {
         Self::new(TermType::Default).with_arg(self).into_arg()
     }
 }
+
+impl<T> Arg for T
+where
+    T: Serialize,
+{
+    fn arg(self) -> cmd::Arg<()> {
+        Command::from_json(self).arg()
+    }
+}
+
+/// A function computing the default from the error message
+impl Arg for Func {
+    fn arg(self) -> cmd::Arg<()> {
+        let Func(func) = self;
+        func.arg()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{cmd, r};
+
+    #[test]
+    fn r_table_get_field_default() {
+        let query = r
+            .table("users")
+            .get(1)
+            .get_field("nickname")
+            .default("anonymous");
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[92,[[31,[[16,[[15,["users"]],1]],"nickname"]],"anonymous"]]"#;
+        assert_eq!(serialised, expected);
+    }
+}
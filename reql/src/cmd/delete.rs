@@ -35,3 +35,20 @@ impl Arg for Options {
         ().arg().with_opts(self)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Options;
+    use crate::cmd::Durability;
+    use crate::{cmd, r};
+
+    #[test]
+    fn r_table_delete_soft() {
+        let query = r
+            .table("foo")
+            .delete(Options::new().durability(Durability::Soft));
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[54,[[15,["foo"]]],{"durability":"soft"}]"#;
+        assert_eq!(serialised, expected);
+    }
+}
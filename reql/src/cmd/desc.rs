@@ -4,6 +4,12 @@ use ql2::term::TermType;
 #[derive(Debug, Clone)]
 pub struct Desc(pub(crate) Command);
 
+impl From<Desc> for Command {
+    fn from(Desc(cmd): Desc) -> Self {
+        cmd
+    }
+}
+
 pub trait Arg {
     fn arg(self) -> cmd::Arg<()>;
 }
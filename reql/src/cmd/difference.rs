@@ -1,5 +1,6 @@
 use crate::{cmd, Command};
 use ql2::term::TermType;
+use serde::Serialize;
 
 pub trait Arg {
     fn arg(self) -> cmd::Arg<()>;
@@ -10,3 +11,12 @@ impl Arg for Command {
         Self::new(TermType::Difference).with_arg(self).into_arg()
     }
 }
+
+impl<T> Arg for T
+where
+    T: Serialize,
+{
+    fn arg(self) -> cmd::Arg<()> {
+        Command::from_json(self).arg()
+    }
+}
@@ -1,3 +1,4 @@
+use super::args::Args;
 use crate::{cmd, Command};
 use ql2::term::TermType;
 
@@ -10,3 +11,25 @@ impl Arg for Command {
         Self::new(TermType::Distance).with_arg(self).into_arg()
     }
 }
+
+impl Arg for Args<(Command, Command)> {
+    fn arg(self) -> cmd::Arg<()> {
+        let Args((from, to)) = self;
+        from.arg().with_arg(to)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{cmd, r};
+
+    #[test]
+    fn r_distance() {
+        let from = r.point(r.args((-122.423246, 37.779388)));
+        let to = r.point(r.args((-117.220406, 32.719464)));
+        let query = r.distance(r.args((from, to)));
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[162,[[159,[-122.423246,37.779388]],[159,[-117.220406,32.719464]]]]"#;
+        assert_eq!(serialised, expected);
+    }
+}
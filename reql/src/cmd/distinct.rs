@@ -1,3 +1,4 @@
+use super::index::Index;
 use crate::{cmd, Command};
 use ql2::term::TermType;
 
@@ -10,3 +11,32 @@ impl Arg for Command {
         Self::new(TermType::Distinct).with_arg(self).into_arg()
     }
 }
+
+impl Arg for () {
+    fn arg(self) -> cmd::Arg<()> {
+        Command::new(TermType::Distinct).into_arg()
+    }
+}
+
+/// Get the distinct values of a secondary index of a table
+impl Arg for Index {
+    fn arg(self) -> cmd::Arg<()> {
+        let Index(index) = self;
+        Command::new(TermType::Distinct)
+            .into_arg()
+            .with_term_opt("index", index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{cmd, r};
+
+    #[test]
+    fn r_table_distinct_index() {
+        let query = r.table("foo").distinct(r.index("bar"));
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[42,[[15,["foo"]]],{"index":"bar"}]"#;
+        assert_eq!(serialised, expected);
+    }
+}
@@ -33,29 +33,25 @@ impl Arg for Args<(Command, Command)> {
     }
 }
 
-#[allow(array_into_iter)]
-#[allow(clippy::into_iter_on_ref)]
 impl<const N: usize> Arg for Args<([Command; N], Command)> {
     fn arg(self, parent: Option<Command>) -> cmd::Arg<()> {
         let Args((args, expr)) = self;
         let mut cmd = expr.arg(parent);
-        for arg in args.into_iter().cloned() {
+        for arg in IntoIterator::into_iter(args) {
             cmd = cmd.with_arg(arg);
         }
         cmd
     }
 }
 
-#[allow(array_into_iter)]
-#[allow(clippy::into_iter_on_ref)]
 impl<T, const N: usize> Arg for Args<([T; N], Command)>
 where
-    T: Serialize + Clone,
+    T: Serialize,
 {
     fn arg(self, parent: Option<Command>) -> cmd::Arg<()> {
         let Args((args, expr)) = self;
         let mut cmd = expr.arg(parent);
-        for arg in args.into_iter().cloned() {
+        for arg in IntoIterator::into_iter(args) {
             let arg = Command::from_json(arg);
             cmd = cmd.with_arg(arg);
         }
@@ -70,29 +66,25 @@ impl Arg for Args<(Command, Func)> {
     }
 }
 
-#[allow(array_into_iter)]
-#[allow(clippy::into_iter_on_ref)]
 impl<const N: usize> Arg for Args<([Command; N], Func)> {
     fn arg(self, parent: Option<Command>) -> cmd::Arg<()> {
         let Args((args, Func(func))) = self;
         let mut cmd = func.arg(parent);
-        for arg in args.into_iter().cloned() {
+        for arg in IntoIterator::into_iter(args) {
             cmd = cmd.with_arg(arg);
         }
         cmd
     }
 }
 
-#[allow(array_into_iter)]
-#[allow(clippy::into_iter_on_ref)]
 impl<T, const N: usize> Arg for Args<([T; N], Func)>
 where
-    T: Serialize + Clone,
+    T: Serialize,
 {
     fn arg(self, parent: Option<Command>) -> cmd::Arg<()> {
         let Args((args, Func(func))) = self;
         let mut cmd = func.arg(parent);
-        for arg in args.into_iter().cloned() {
+        for arg in IntoIterator::into_iter(args) {
             let arg = Command::from_json(arg);
             cmd = cmd.with_arg(arg);
         }
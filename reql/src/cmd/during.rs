@@ -1,12 +1,61 @@
+use super::args::Args;
+use super::between::Status;
 use crate::{cmd, Command};
 use ql2::term::TermType;
+use reql_macros::CommandOptions;
+use serde::Serialize;
+
+#[derive(
+    Debug, Clone, Copy, CommandOptions, Serialize, Default, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[non_exhaustive]
+pub struct Options {
+    /// Whether the start time is included, `closed` by default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub left_bound: Option<Status>,
+    /// Whether the end time is included, `open` by default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub right_bound: Option<Status>,
+}
 
 pub trait Arg {
-    fn arg(self) -> cmd::Arg<()>;
+    fn arg(self) -> cmd::Arg<Options>;
 }
 
 impl Arg for Command {
-    fn arg(self) -> cmd::Arg<()> {
+    fn arg(self) -> cmd::Arg<Options> {
         Self::new(TermType::During).with_arg(self).into_arg()
     }
 }
+
+impl Arg for Args<(Command, Command)> {
+    fn arg(self) -> cmd::Arg<Options> {
+        let Args((start_time, end_time)) = self;
+        start_time.arg().with_arg(end_time)
+    }
+}
+
+impl Arg for Args<(Command, Command, Options)> {
+    fn arg(self) -> cmd::Arg<Options> {
+        let Args((start_time, end_time, opts)) = self;
+        Args((start_time, end_time)).arg().with_opts(opts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Options;
+    use crate::cmd::between::Status;
+    use crate::{cmd, r};
+
+    #[test]
+    fn r_now_during() {
+        let opts = Options::new().right_bound(Status::Closed);
+        let query = r
+            .now()
+            .during(r.args((r.time(r.args((2020, 1, 1, "Z"))), r.now(), opts)));
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[105,[[103,[]],[136,[2020,1,1,"Z"]],[103,[]]],{"right_bound":"closed"}]"#;
+        assert_eq!(serialised, expected);
+    }
+}
@@ -10,3 +10,17 @@ impl Arg for Command {
         Self::new(TermType::EpochTime).with_arg(self).into_arg()
     }
 }
+
+/// Seconds since the Unix epoch
+impl Arg for i64 {
+    fn arg(self) -> cmd::Arg<()> {
+        Command::from_json(self).arg()
+    }
+}
+
+/// Seconds since the Unix epoch, with a fractional part
+impl Arg for f64 {
+    fn arg(self) -> cmd::Arg<()> {
+        Command::from_json(self).arg()
+    }
+}
@@ -1,3 +1,4 @@
+use super::args::Args;
 use crate::{cmd, Command};
 use ql2::term::TermType;
 use serde::Serialize;
@@ -12,12 +13,28 @@ impl Arg for cmd::Arg<()> {
     }
 }
 
+impl Arg for Command {
+    fn arg(self) -> cmd::Arg<()> {
+        Self::new(TermType::Eq).with_arg(self).into_arg()
+    }
+}
+
 impl<T> Arg for T
 where
     T: Serialize,
 {
     fn arg(self) -> cmd::Arg<()> {
-        let arg = Command::from_json(self);
-        Command::new(TermType::Eq).with_arg(arg).into_arg()
+        Command::from_json(self).arg()
+    }
+}
+
+impl<const N: usize> Arg for Args<[Command; N]> {
+    fn arg(self) -> cmd::Arg<()> {
+        let Args(arr) = self;
+        let mut query = Command::new(TermType::Eq);
+        for arg in IntoIterator::into_iter(arr) {
+            query = query.with_arg(arg);
+        }
+        query.into_arg()
     }
 }
@@ -1,5 +1,109 @@
-use crate::cmd;
+use super::args::Args;
+use super::index::Index;
+use crate::{cmd, Command, Func};
+use ql2::term::TermType;
+use reql_macros::CommandOptions;
+use serde::Serialize;
+
+#[derive(
+    Debug, Clone, Copy, CommandOptions, Serialize, Default, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[non_exhaustive]
+pub struct Options {
+    /// Sort the results by the left side's join field
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ordered: Option<bool>,
+}
 
 pub trait Arg {
-    fn arg(self) -> cmd::Arg<()>;
+    fn arg(self) -> cmd::Arg<Options>;
+}
+
+impl Arg for Args<(Command, Command)> {
+    fn arg(self) -> cmd::Arg<Options> {
+        let Args((left_field, right_table)) = self;
+        Command::new(TermType::EqJoin)
+            .with_arg(left_field)
+            .with_arg(right_table)
+            .into_arg()
+    }
+}
+
+impl<T> Arg for Args<(T, Command)>
+where
+    T: Into<String>,
+{
+    fn arg(self) -> cmd::Arg<Options> {
+        let Args((left_field, right_table)) = self;
+        let left_field = Command::from_json(left_field.into());
+        Args((left_field, right_table)).arg()
+    }
+}
+
+impl Arg for Args<(Func, Command)> {
+    fn arg(self) -> cmd::Arg<Options> {
+        let Args((Func(left_field), right_table)) = self;
+        Args((left_field, right_table)).arg()
+    }
+}
+
+/// Join on a secondary index of the right table instead of its primary key
+impl<T> Arg for Args<(T, Command, Index)>
+where
+    Args<(T, Command)>: Arg,
+{
+    fn arg(self) -> cmd::Arg<Options> {
+        let Args((left_field, right_table, Index(index))) = self;
+        Args((left_field, right_table))
+            .arg()
+            .with_term_opt("index", index)
+    }
+}
+
+impl<T> Arg for Args<(T, Command, Options)>
+where
+    Args<(T, Command)>: Arg,
+{
+    fn arg(self) -> cmd::Arg<Options> {
+        let Args((left_field, right_table, opts)) = self;
+        Args((left_field, right_table)).arg().with_opts(opts)
+    }
+}
+
+impl<T> Arg for Args<(T, Command, Index, Options)>
+where
+    Args<(T, Command)>: Arg,
+{
+    fn arg(self) -> cmd::Arg<Options> {
+        let Args((left_field, right_table, index, opts)) = self;
+        Args((left_field, right_table, index)).arg().with_opts(opts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Options;
+    use crate::{cmd, r};
+
+    #[test]
+    fn r_table_eq_join() {
+        let query = r
+            .table("players")
+            .eq_join(r.args(("game_id", r.table("games"))));
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[50,[[15,["players"]],"game_id",[15,["games"]]]]"#;
+        assert_eq!(serialised, expected);
+    }
+
+    #[test]
+    fn r_table_eq_join_index_zip() {
+        let opts = Options::new().ordered(true);
+        let query = r
+            .table("players")
+            .eq_join(r.args(("game", r.table("games"), r.index("name"), opts)))
+            .zip();
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[72,[[50,[[15,["players"]],"game",[15,["games"]]],{"ordered":true,"index":"name"}]]]"#;
+        assert_eq!(serialised, expected);
+    }
 }
@@ -13,3 +13,51 @@ where
         Command::from_json(self).into_arg()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{cmd, r};
+    use serde::Serialize;
+    use serde_json::{json, Value};
+    use std::collections::HashMap;
+
+    #[test]
+    fn r_expr_map() {
+        let mut map = HashMap::new();
+        map.insert("a", 1);
+        map.insert("b", 2);
+        let query = r.expr(map);
+        // Objects don't keep the order of their keys
+        let serialised: Value = serde_json::from_str(&cmd::serialise(&query)).unwrap();
+        let expected = json!({"a": 1, "b": 2});
+        assert_eq!(serialised, expected);
+    }
+
+    #[test]
+    fn r_expr_vec() {
+        let query = r.expr(vec!["a", "b"]);
+        let serialised = cmd::serialise(&query);
+        // arrays are built with `MAKE_ARRAY` since the server reads
+        // a raw JSON array as a term
+        let expected = r#"[2,["a","b"]]"#;
+        assert_eq!(serialised, expected);
+    }
+
+    #[test]
+    fn r_expr_struct() {
+        #[derive(Serialize)]
+        struct User {
+            name: &'static str,
+            tags: Vec<&'static str>,
+        }
+
+        let user = User {
+            name: "ada",
+            tags: vec!["admin"],
+        };
+        let query = r.expr(user).get_field("name");
+        let serialised: Value = serde_json::from_str(&cmd::serialise(&query)).unwrap();
+        let expected = json!([31, [{"name": "ada", "tags": [2, ["admin"]]}, "name"]]);
+        assert_eq!(serialised, expected);
+    }
+}
@@ -23,6 +23,15 @@ impl Arg for Command {
     }
 }
 
+impl<T> Arg for T
+where
+    T: Serialize,
+{
+    fn arg(self) -> cmd::Arg<Options> {
+        Command::from_json(self).arg()
+    }
+}
+
 impl Arg for Args<(Command, Options)> {
     fn arg(self) -> cmd::Arg<Options> {
         let Args((arg, opts)) = self;
@@ -30,6 +39,16 @@ impl Arg for Args<(Command, Options)> {
     }
 }
 
+impl<T> Arg for Args<(T, Options)>
+where
+    T: Serialize,
+{
+    fn arg(self) -> cmd::Arg<Options> {
+        let Args((arg, opts)) = self;
+        Command::from_json(arg).arg().with_opts(opts)
+    }
+}
+
 impl Arg for Func {
     fn arg(self) -> cmd::Arg<Options> {
         let Func(arg) = self;
@@ -43,3 +62,39 @@ impl Arg for Args<(Func, Options)> {
         arg.arg().with_opts(opts)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Options;
+    use crate::{self as reql, cmd, func, r};
+    use serde_json::{json, Value};
+
+    #[test]
+    fn r_table_filter_object() {
+        let query = r.table("users").filter(json!({"status": "active"}));
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[39,[[15,["users"]],{"status":"active"}]]"#;
+        assert_eq!(serialised, expected);
+    }
+
+    #[test]
+    fn r_table_filter_func() {
+        let opts = Options::new().default(true);
+        let query = r
+            .table("users")
+            .filter(r.args((func!(|user| user.bracket("age").gt(r.expr(18))), opts)));
+        let serialised: Value = serde_json::from_str(&cmd::serialise(&query)).unwrap();
+        // Other tests may create variables at the same time, so read
+        // back the one this function got
+        let var = serialised[1][1][1][0][1][0].clone();
+        let expected = json!([
+            39,
+            [
+                [15, ["users"]],
+                [69, [[2, [var]], [21, [[170, [[10, [var]], "age"]], 18]]]]
+            ],
+            {"default": true}
+        ]);
+        assert_eq!(serialised, expected);
+    }
+}
@@ -1,5 +1,32 @@
-use crate::{cmd, Command};
+use super::args::Args;
+use crate::{cmd, Command, Func};
 use ql2::term::TermType;
+use reql_macros::CommandOptions;
+use serde::Serialize;
+
+/// Options for `fold`, which are functions rather than plain values
+#[derive(Debug, CommandOptions, Default)]
+#[non_exhaustive]
+pub struct Options {
+    /// Called with the accumulator, the current row and the new
+    /// accumulator, returning an array of values to emit
+    pub emit: Option<Func>,
+    /// Called with the final accumulator, returning an array of values
+    /// to emit at the end
+    pub final_emit: Option<Func>,
+}
+
+impl Options {
+    fn apply(self, mut arg: cmd::Arg<()>) -> cmd::Arg<()> {
+        if let Some(Func(emit)) = self.emit {
+            arg = arg.with_term_opt("emit", emit);
+        }
+        if let Some(Func(final_emit)) = self.final_emit {
+            arg = arg.with_term_opt("final_emit", final_emit);
+        }
+        arg
+    }
+}
 
 pub trait Arg {
     fn arg(self) -> cmd::Arg<()>;
@@ -10,3 +37,60 @@ impl Arg for Command {
         Self::new(TermType::Fold).with_arg(self).into_arg()
     }
 }
+
+impl Arg for Args<(Command, Func)> {
+    fn arg(self) -> cmd::Arg<()> {
+        let Args((base, Func(func))) = self;
+        base.arg().with_arg(func)
+    }
+}
+
+impl<T> Arg for Args<(T, Func)>
+where
+    T: Serialize,
+{
+    fn arg(self) -> cmd::Arg<()> {
+        let Args((base, func)) = self;
+        Args((Command::from_json(base), func)).arg()
+    }
+}
+
+impl Arg for Args<(Command, Func, Options)> {
+    fn arg(self) -> cmd::Arg<()> {
+        let Args((base, func, opts)) = self;
+        opts.apply(Args((base, func)).arg())
+    }
+}
+
+impl<T> Arg for Args<(T, Func, Options)>
+where
+    T: Serialize,
+{
+    fn arg(self) -> cmd::Arg<()> {
+        let Args((base, func, opts)) = self;
+        opts.apply(Args((base, func)).arg())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Options;
+    use crate::{self as reql, cmd, func, r};
+    use serde_json::{json, Value};
+
+    #[test]
+    fn r_expr_fold_emit() {
+        let opts = Options::new().emit(func!(|_acc, row, _new_acc| [row]));
+        let query = r
+            .expr([1, 2, 3])
+            .fold(r.args((0, func!(|acc, row| acc + row), opts)));
+        let serialised: Value = serde_json::from_str(&cmd::serialise(&query)).unwrap();
+        assert_eq!(serialised[0], 187);
+        assert_eq!(serialised[1][0], json!([2, [1, 2, 3]]));
+        assert_eq!(serialised[1][1], 0);
+        assert_eq!(serialised[1][2][0], 69);
+        let emit = &serialised[2]["emit"];
+        let row = emit[1][0][1][1].clone();
+        assert_eq!(emit[1][1], json!([2, [[10, [row]]]]));
+    }
+}
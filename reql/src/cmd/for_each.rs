@@ -1,4 +1,4 @@
-use crate::{cmd, Command};
+use crate::{cmd, Command, Func};
 use ql2::term::TermType;
 
 pub trait Arg {
@@ -10,3 +10,46 @@ impl Arg for Command {
         Self::new(TermType::ForEach).with_arg(self).into_arg()
     }
 }
+
+impl Arg for Func {
+    fn arg(self) -> cmd::Arg<()> {
+        let Func(func) = self;
+        func.arg()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{self as reql, cmd, func, r};
+    use serde_json::{json, Value};
+
+    #[test]
+    fn r_table_for_each() {
+        let query = r.table("marvel").for_each(func!(|hero| r
+            .table("villains")
+            .get(hero.bracket("villainDefeated"))
+            .delete(())));
+        let serialised: Value = serde_json::from_str(&cmd::serialise(&query)).unwrap();
+        let var = serialised[1][1][1][0][1][0].clone();
+        let expected = json!([
+            68,
+            [
+                [15, ["marvel"]],
+                [
+                    69,
+                    [
+                        [2, [var]],
+                        [
+                            54,
+                            [[
+                                16,
+                                [[15, ["villains"]], [170, [[10, [var]], "villainDefeated"]]]
+                            ]]
+                        ]
+                    ]
+                ]
+            ]
+        ]);
+        assert_eq!(serialised, expected);
+    }
+}
@@ -1,10 +1,26 @@
 use crate::Command;
 use ql2::term::TermType;
+use std::convert::TryInto;
 
-#[derive(Debug)]
+/// An anonymous ReQL function, as taken by commands like `map` and
+/// `filter`
+///
+/// The [func](crate::func) macro is the usual way to write one. Each of
+/// its arguments gets a variable id no other function uses, so functions
+/// can be nested inside each other. A function is cloned to use it in
+/// more than one query.
+///
+/// ```
+/// # use reql::{func, r};
+/// let adult = func!(|user| user.get_field("age").ge(18));
+/// let adults = r.table("users").filter(adult.clone());
+/// let adult_admins = r.table("admins").filter(adult);
+/// ```
+#[derive(Debug, Clone)]
 pub struct Func(pub(crate) Command);
 
 impl Func {
+    #[doc(hidden)]
     pub fn new<T>(ids: Vec<u64>, body: T) -> Self
     where
         T: Into<Command>,
@@ -16,6 +32,31 @@ impl Func {
         )
     }
 
+    /// Build a function from a closure taking an array of its arguments
+    ///
+    /// This does what the [func](crate::func) macro does, for code that
+    /// would rather not use a macro, such as helpers building functions
+    /// of their own.
+    ///
+    /// ```
+    /// # use reql::{r, Func};
+    /// let total = Func::from_fn(|[acc, order]| acc + order.get_field("total"));
+    /// let revenue = r.table("orders").fold(r.args((0, total)));
+    /// ```
+    pub fn from_fn<F, T, const N: usize>(body: F) -> Self
+    where
+        F: FnOnce([Command; N]) -> T,
+        T: Into<Command>,
+    {
+        let ids: Vec<_> = (0..N).map(|_| crate::var_counter()).collect();
+        let args: Vec<_> = ids.iter().map(|id| Command::var(*id)).collect();
+        let args = match args.try_into() {
+            Ok(args) => args,
+            Err(_) => unreachable!("there is a variable for each argument"),
+        };
+        Self::new(ids, body(args))
+    }
+
     pub(crate) fn row<T>(body: T) -> Self
     where
         T: Into<Command>,
@@ -23,3 +64,66 @@ impl Func {
         Self::new(vec![0], body)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Func;
+    use crate::{self as reql, cmd, func, r};
+    use serde_json::{json, Value};
+
+    #[test]
+    fn nested_functions_have_their_own_variables() {
+        let query = r.table("teams").map(func!(|team| {
+            team.get_field("players")
+                .filter(func!(|player| player.get_field("active")))
+        }));
+        let serialised: Value = serde_json::from_str(&cmd::serialise(&query)).unwrap();
+        let outer = serialised[1][1][1][0][1][0].clone();
+        let inner_func = serialised[1][1][1][1][1][1].clone();
+        let inner = inner_func[1][0][1][0].clone();
+        assert_ne!(outer, inner);
+        let expected = json!([
+            38,
+            [
+                [15, ["teams"]],
+                [
+                    69,
+                    [
+                        [2, [outer]],
+                        [
+                            39,
+                            [
+                                [31, [[10, [outer]], "players"]],
+                                [69, [[2, [inner]], [31, [[10, [inner]], "active"]]]]
+                            ]
+                        ]
+                    ]
+                ]
+            ]
+        ]);
+        assert_eq!(serialised, expected);
+    }
+
+    #[test]
+    fn from_fn_matches_the_macro() {
+        let func = Func::from_fn(|[a, b]| a + b);
+        let serialised: Value = serde_json::from_str(&cmd::serialise(&func.0)).unwrap();
+        let (a, b) = (
+            serialised[1][0][1][0].clone(),
+            serialised[1][0][1][1].clone(),
+        );
+        assert_ne!(a, b);
+        let expected = json!([69, [[2, [a, b]], [24, [[10, [a]], [10, [b]]]]]]);
+        assert_eq!(serialised, expected);
+    }
+
+    #[test]
+    fn functions_can_be_reused() {
+        let active = func!(|player| player.get_field("active"));
+        let first = r.table("players").filter(active.clone());
+        let second = r.table("coaches").filter(active);
+        let first: Value = serde_json::from_str(&cmd::serialise(&first)).unwrap();
+        let second: Value = serde_json::from_str(&cmd::serialise(&second)).unwrap();
+        assert_eq!(first[1][1], second[1][1]);
+    }
+}
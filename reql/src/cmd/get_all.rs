@@ -25,21 +25,18 @@ where
 impl Arg for Args<(&str, Index)> {
     fn arg(self) -> cmd::Arg<()> {
         let Args((key, Index(index))) = self;
-        key.arg().with_arg(index)
+        key.arg().with_term_opt("index", index)
     }
 }
 
-#[allow(array_into_iter)]
-#[allow(clippy::into_iter_on_ref)]
 impl<T, const N: usize> Arg for Args<[T; N]>
 where
-    T: Into<String> + Clone,
+    T: Into<String>,
 {
     fn arg(self) -> cmd::Arg<()> {
         let Args(arr) = self;
         let mut query = Command::new(TermType::GetAll);
-        // TODO get rid of the clone in Rust v1.53
-        for arg in arr.into_iter().cloned() {
+        for arg in IntoIterator::into_iter(arr) {
             let arg = Command::from_json(arg.into());
             query = query.with_arg(arg);
         }
@@ -47,20 +44,38 @@ where
     }
 }
 
-#[allow(array_into_iter)]
-#[allow(clippy::into_iter_on_ref)]
 impl<T, const N: usize> Arg for Args<([T; N], Index)>
 where
-    T: Into<String> + Clone,
+    T: Into<String>,
 {
     fn arg(self) -> cmd::Arg<()> {
         let Args((arr, Index(index))) = self;
         let mut query = Command::new(TermType::GetAll);
-        // TODO get rid of the clone in Rust v1.53
-        for arg in arr.into_iter().cloned() {
+        for arg in IntoIterator::into_iter(arr) {
             let arg = Command::from_json(arg.into());
             query = query.with_arg(arg);
         }
-        query.with_arg(index).into_arg()
+        query.into_arg().with_term_opt("index", index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{cmd, r};
+
+    #[test]
+    fn r_table_get_all() {
+        let query = r.table("foo").get_all(r.args(["bar", "baz"]));
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[78,[[15,["foo"]],"bar","baz"]]"#;
+        assert_eq!(serialised, expected);
+    }
+
+    #[test]
+    fn r_table_get_all_index() {
+        let query = r.table("foo").get_all(r.args(("bar", r.index("baz"))));
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[78,[[15,["foo"]],"bar"],{"index":"baz"}]"#;
+        assert_eq!(serialised, expected);
     }
 }
@@ -28,3 +28,28 @@ impl Arg for String {
         Command::from_json(self).arg()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{cmd, r};
+
+    #[test]
+    fn r_table_get_field_nested() {
+        let query = r
+            .table("users")
+            .get(1)
+            .get_field("address")
+            .get_field("city");
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[31,[[31,[[16,[[15,["users"]],1]],"address"]],"city"]]"#;
+        assert_eq!(serialised, expected);
+    }
+
+    #[test]
+    fn r_table_ids() {
+        let query = r.table("users").ids();
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[31,[[15,["users"]],"id"]]"#;
+        assert_eq!(serialised, expected);
+    }
+}
@@ -1,5 +1,8 @@
+use super::args::Args;
+use super::index::Index;
 use crate::{cmd, Command};
 use ql2::term::TermType;
+use serde::Serialize;
 
 pub trait Arg {
     fn arg(self) -> cmd::Arg<()>;
@@ -12,3 +15,59 @@ impl Arg for Command {
             .into_arg()
     }
 }
+
+impl Arg for Args<(Command, Index)> {
+    fn arg(self) -> cmd::Arg<()> {
+        let Args((geometry, Index(index))) = self;
+        geometry.arg().with_term_opt("index", index)
+    }
+}
+
+/// Any of the geometry types, such as a `Polygon`
+impl<T> Arg for Args<(T, Index)>
+where
+    T: Serialize,
+{
+    fn arg(self) -> cmd::Arg<()> {
+        let Args((geometry, index)) = self;
+        Args((Command::from_json(geometry), index)).arg()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::geo::{Point, Polygon};
+    use crate::{cmd, r};
+    use serde_json::{json, Value};
+
+    #[test]
+    fn r_table_get_intersecting() {
+        let point = r.point(r.args((-117.220406, 32.719464)));
+        let query = r
+            .table("parks")
+            .get_intersecting(r.args((point, r.index("area"))));
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[166,[[15,["parks"]],[159,[-117.220406,32.719464]]],{"index":"area"}]"#;
+        assert_eq!(serialised, expected);
+    }
+
+    #[test]
+    fn r_table_get_intersecting_polygon() {
+        let ring = vec![
+            Point::new(0.0, 0.0).unwrap(),
+            Point::new(1.0, 0.0).unwrap(),
+            Point::new(1.0, 1.0).unwrap(),
+        ];
+        let query = r
+            .table("parks")
+            .get_intersecting(r.args((Polygon(vec![ring]), r.index("area"))));
+        let serialised: Value = serde_json::from_str(&cmd::serialise(&query)).unwrap();
+        let polygon = json!({
+            "$reql_type$": "GEOMETRY",
+            "type": "Polygon",
+            "coordinates": [2, [[2, [[2, [0.0, 0.0]], [2, [1.0, 0.0]], [2, [1.0, 1.0]]]]]]
+        });
+        let expected = json!([166, [[15, ["parks"]], polygon], {"index": "area"}]);
+        assert_eq!(serialised, expected);
+    }
+}
@@ -1,12 +1,106 @@
+use super::args::Args;
+use super::index::Index;
+use crate::cmd::{GeoSystem, Unit};
 use crate::{cmd, Command};
 use ql2::term::TermType;
+use reql_macros::CommandOptions;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, CommandOptions, Serialize, Default, PartialEq, PartialOrd)]
+#[non_exhaustive]
+pub struct Options {
+    /// The maximum number of documents to return, 100 by default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_results: Option<usize>,
+    /// How far from the point to look, in `unit`s; 100 km by default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_dist: Option<f64>,
+    /// The unit of `max_dist` and of the distances returned
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit: Option<Unit>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub geo_system: Option<GeoSystem>,
+}
 
 pub trait Arg {
-    fn arg(self) -> cmd::Arg<()>;
+    fn arg(self) -> cmd::Arg<Options>;
 }
 
 impl Arg for Command {
-    fn arg(self) -> cmd::Arg<()> {
+    fn arg(self) -> cmd::Arg<Options> {
         Self::new(TermType::GetNearest).with_arg(self).into_arg()
     }
 }
+
+impl Arg for Args<(Command, Index)> {
+    fn arg(self) -> cmd::Arg<Options> {
+        let Args((point, Index(index))) = self;
+        point.arg().with_term_opt("index", index)
+    }
+}
+
+/// A `Point`, or anything else that serialises to one
+impl<T> Arg for Args<(T, Index)>
+where
+    T: Serialize,
+{
+    fn arg(self) -> cmd::Arg<Options> {
+        let Args((point, index)) = self;
+        Args((Command::from_json(point), index)).arg()
+    }
+}
+
+impl<T> Arg for Args<(T, Index, Options)>
+where
+    Args<(T, Index)>: Arg,
+{
+    fn arg(self) -> cmd::Arg<Options> {
+        let Args((point, index, opts)) = self;
+        Args((point, index)).arg().with_opts(opts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Options;
+    use crate::cmd::Unit;
+    use crate::types::geo::Point;
+    use crate::{cmd, r};
+    use serde_json::{json, Value};
+
+    #[test]
+    fn r_table_get_nearest() {
+        let point = r.point(r.args((-122.423246, 37.779388)));
+        let query = r
+            .table("hideouts")
+            .get_nearest(r.args((point, r.index("location"))));
+        let serialised = cmd::serialise(&query);
+        let expected =
+            r#"[168,[[15,["hideouts"]],[159,[-122.423246,37.779388]]],{"index":"location"}]"#;
+        assert_eq!(serialised, expected);
+    }
+
+    #[test]
+    fn r_table_get_nearest_with_options() {
+        let point = Point::new(-122.423246, 37.779388).unwrap();
+        let opts = Options::new()
+            .max_dist(5.0)
+            .unit(Unit::Kilometer)
+            .max_results(3);
+        let query = r
+            .table("hideouts")
+            .get_nearest(r.args((point, r.index("location"), opts)));
+        let serialised: Value = serde_json::from_str(&cmd::serialise(&query)).unwrap();
+        let point = json!({
+            "$reql_type$": "GEOMETRY",
+            "type": "Point",
+            "coordinates": [2, [-122.423246, 37.779388]]
+        });
+        let expected = json!([
+            168,
+            [[15, ["hideouts"]], point],
+            {"max_results": 3, "max_dist": 5.0, "unit": "km", "index": "location"}
+        ]);
+        assert_eq!(serialised, expected);
+    }
+}
@@ -1,12 +1,125 @@
-use crate::{cmd, Command};
+use super::args::Args;
+use super::index::Index;
+use crate::{cmd, Command, Func};
 use ql2::term::TermType;
+use reql_macros::CommandOptions;
+use serde::Serialize;
+
+#[derive(
+    Debug, Clone, Copy, CommandOptions, Serialize, Default, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[non_exhaustive]
+pub struct Options {
+    /// Put a row in several groups when its key is an array, one for
+    /// each element of the array
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multi: Option<bool>,
+}
 
 pub trait Arg {
-    fn arg(self) -> cmd::Arg<()>;
+    fn arg(self) -> cmd::Arg<Options>;
 }
 
 impl Arg for Command {
-    fn arg(self) -> cmd::Arg<()> {
+    fn arg(self) -> cmd::Arg<Options> {
         Self::new(TermType::Group).with_arg(self).into_arg()
     }
 }
+
+impl<T> Arg for T
+where
+    T: Into<String>,
+{
+    fn arg(self) -> cmd::Arg<Options> {
+        Command::from_json(self.into()).arg()
+    }
+}
+
+impl Arg for Func {
+    fn arg(self) -> cmd::Arg<Options> {
+        let Func(func) = self;
+        func.arg()
+    }
+}
+
+/// Group by a secondary index instead of a field or function
+impl Arg for Index {
+    fn arg(self) -> cmd::Arg<Options> {
+        let Index(index) = self;
+        Command::new(TermType::Group)
+            .into_arg()
+            .with_term_opt("index", index)
+    }
+}
+
+/// Group by several fields, such as `r.args(["dept", "role"])`
+///
+/// The key of each group is then an array holding the value of each
+/// field, in order.
+impl<T, const N: usize> Arg for Args<[T; N]>
+where
+    T: Into<String>,
+{
+    fn arg(self) -> cmd::Arg<Options> {
+        let Args(fields) = self;
+        let mut query = Command::new(TermType::Group);
+        for field in IntoIterator::into_iter(fields) {
+            query = query.with_arg(Command::from_json(field.into()));
+        }
+        query.into_arg()
+    }
+}
+
+impl Arg for Args<(Command, Options)> {
+    fn arg(self) -> cmd::Arg<Options> {
+        let Args((key, opts)) = self;
+        key.arg().with_opts(opts)
+    }
+}
+
+impl<T> Arg for Args<(T, Options)>
+where
+    T: Into<String>,
+{
+    fn arg(self) -> cmd::Arg<Options> {
+        let Args((key, opts)) = self;
+        key.arg().with_opts(opts)
+    }
+}
+
+impl Arg for Args<(Func, Options)> {
+    fn arg(self) -> cmd::Arg<Options> {
+        let Args((func, opts)) = self;
+        func.arg().with_opts(opts)
+    }
+}
+
+impl Arg for Args<(Index, Options)> {
+    fn arg(self) -> cmd::Arg<Options> {
+        let Args((index, opts)) = self;
+        index.arg().with_opts(opts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Options;
+    use crate::{cmd, r};
+
+    #[test]
+    fn r_table_group_fields() {
+        let query = r.table("foo").group(r.args(["bar", "baz"])).count(());
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[43,[[144,[[15,["foo"]],"bar","baz"]]]]"#;
+        assert_eq!(serialised, expected);
+    }
+
+    #[test]
+    fn r_table_group_index_multi() {
+        let opts = Options::new().multi(true);
+        let query = r.table("foo").group(r.args((r.index("bar"), opts)));
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[144,[[15,["foo"]]],{"multi":true,"index":"bar"}]"#;
+        assert_eq!(serialised, expected);
+    }
+}
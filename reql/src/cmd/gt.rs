@@ -1,5 +1,7 @@
+use super::args::Args;
 use crate::{cmd, Command};
 use ql2::term::TermType;
+use serde::Serialize;
 
 pub trait Arg {
     fn arg(self) -> cmd::Arg<()>;
@@ -10,3 +12,23 @@ impl Arg for Command {
         Self::new(TermType::Gt).with_arg(self).into_arg()
     }
 }
+
+impl<T> Arg for T
+where
+    T: Serialize,
+{
+    fn arg(self) -> cmd::Arg<()> {
+        Command::from_json(self).arg()
+    }
+}
+
+impl<const N: usize> Arg for Args<[Command; N]> {
+    fn arg(self) -> cmd::Arg<()> {
+        let Args(arr) = self;
+        let mut query = Command::new(TermType::Gt);
+        for arg in IntoIterator::into_iter(arr) {
+            query = query.with_arg(arg);
+        }
+        query.into_arg()
+    }
+}
@@ -1,3 +1,4 @@
+use super::args::Args;
 use crate::{cmd, Command};
 use ql2::term::TermType;
 use serde::Serialize;
@@ -6,12 +7,57 @@ pub trait Arg {
     fn arg(self) -> cmd::Arg<()>;
 }
 
+impl Arg for Command {
+    fn arg(self) -> cmd::Arg<()> {
+        Self::new(TermType::HasFields).with_arg(self).into_arg()
+    }
+}
+
+/// A field name, or an object selecting nested fields such as
+/// `json!({"address": {"city": true}})`
 impl<T> Arg for T
 where
     T: Serialize,
 {
     fn arg(self) -> cmd::Arg<()> {
-        let arg = Command::from_json(self);
-        Command::new(TermType::HasFields).with_arg(arg).into_arg()
+        Command::from_json(self).arg()
+    }
+}
+
+impl<T, const N: usize> Arg for Args<[T; N]>
+where
+    T: Serialize,
+{
+    fn arg(self) -> cmd::Arg<()> {
+        let Args(fields) = self;
+        let mut query = Command::new(TermType::HasFields);
+        for field in IntoIterator::into_iter(fields) {
+            query = query.with_arg(Command::from_json(field));
+        }
+        query.into_arg()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{cmd, r};
+    use serde_json::json;
+
+    #[test]
+    fn r_table_has_fields() {
+        let query = r.table("users").has_fields(r.args(["email", "age"]));
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[32,[[15,["users"]],"email","age"]]"#;
+        assert_eq!(serialised, expected);
+    }
+
+    #[test]
+    fn r_table_has_fields_nested() {
+        let query = r
+            .table("users")
+            .has_fields(json!({"address": {"city": true}}));
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[32,[[15,["users"]],{"address":{"city":true}}]]"#;
+        assert_eq!(serialised, expected);
     }
 }
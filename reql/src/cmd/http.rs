@@ -1,12 +1,202 @@
+use super::args::Args;
 use crate::{cmd, Command};
 use ql2::term::TermType;
+use reql_macros::CommandOptions;
+use serde::Serialize;
+use serde_json::Value;
+use std::borrow::Cow;
+
+#[derive(Debug, Clone, CommandOptions, Serialize, Default, PartialEq)]
+#[non_exhaustive]
+pub struct Options {
+    /// Seconds to wait before timing out, 30 by default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<f64>,
+    /// How many times to retry on failure, 5 by default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attempts: Option<u64>,
+    /// How many redirects to follow, 1 by default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub redirects: Option<u64>,
+    /// Whether to verify the server's SSL certificate, `true` by default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verify: Option<bool>,
+    /// How to interpret the response body, `Auto` by default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result_format: Option<ResultFormat>,
+    /// The HTTP method to use, `Get` by default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub method: Option<Method>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auth: Option<Auth>,
+    /// An object of URL parameters to append to the URL
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub params: Option<Value>,
+    /// Extra headers, either as an object or an array of strings
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub header: Option<Value>,
+    /// The request body; objects are sent as form data for `Post` and
+    /// as JSON for the other methods, strings are sent as is
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+    /// The pagination strategy, only `"link-next"` is built in
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<Cow<'static, str>>,
+    /// How many pages to fetch when paginating, `-1` for no limit
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_limit: Option<i64>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum ResultFormat {
+    Text,
+    Json,
+    Jsonp,
+    Binary,
+    Auto,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[serde(rename_all = "UPPERCASE")]
+#[non_exhaustive]
+pub enum Method {
+    Get,
+    Post,
+    Put,
+    Patch,
+    Delete,
+    Head,
+}
+
+#[derive(Debug, Clone, Serialize, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[non_exhaustive]
+pub struct Auth {
+    #[serde(rename = "type")]
+    pub typ: AuthType,
+    pub user: Cow<'static, str>,
+    pub pass: Cow<'static, str>,
+}
+
+impl Auth {
+    pub fn basic<U, P>(user: U, pass: P) -> Self
+    where
+        U: cmd::StaticString,
+        P: cmd::StaticString,
+    {
+        Self {
+            typ: AuthType::Basic,
+            user: user.static_string(),
+            pass: pass.static_string(),
+        }
+    }
+
+    pub fn digest<U, P>(user: U, pass: P) -> Self
+    where
+        U: cmd::StaticString,
+        P: cmd::StaticString,
+    {
+        Self {
+            typ: AuthType::Digest,
+            ..Self::basic(user, pass)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum AuthType {
+    Basic,
+    Digest,
+}
 
 pub trait Arg {
-    fn arg(self) -> cmd::Arg<()>;
+    fn arg(self) -> cmd::Arg<Options>;
 }
 
 impl Arg for Command {
-    fn arg(self) -> cmd::Arg<()> {
+    fn arg(self) -> cmd::Arg<Options> {
         Self::new(TermType::Http).with_arg(self).into_arg()
     }
 }
+
+impl<T> Arg for T
+where
+    T: Into<String>,
+{
+    fn arg(self) -> cmd::Arg<Options> {
+        Command::from_json(self.into()).arg()
+    }
+}
+
+impl Arg for Args<(Command, Options)> {
+    fn arg(self) -> cmd::Arg<Options> {
+        let Args((url, opts)) = self;
+        url.arg().with_opts(opts)
+    }
+}
+
+impl<T> Arg for Args<(T, Options)>
+where
+    T: Into<String>,
+{
+    fn arg(self) -> cmd::Arg<Options> {
+        let Args((url, opts)) = self;
+        url.arg().with_opts(opts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Auth, Method, Options, ResultFormat};
+    use crate::{cmd, r};
+    use serde_json::{json, Value};
+
+    #[test]
+    fn r_http() {
+        let query = r.http("http://httpbin.org/get");
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[153,["http://httpbin.org/get"]]"#;
+        assert_eq!(serialised, expected);
+    }
+
+    #[test]
+    fn r_http_with_opts() {
+        let opts = Options::new()
+            .method(Method::Post)
+            .data(json!({"player": "Bob", "game": "tic tac toe"}))
+            .result_format(ResultFormat::Json)
+            .auth(Auth::basic("fred", "mxyzptlk"));
+        let query = r.http(r.args(("http://httpbin.org/post", opts)));
+        let serialised: Value = serde_json::from_str(&cmd::serialise(&query)).unwrap();
+        let expected = json!([
+            153,
+            ["http://httpbin.org/post"],
+            {
+                "result_format": "json",
+                "method": "POST",
+                "auth": {"type": "basic", "user": "fred", "pass": "mxyzptlk"},
+                "data": {"player": "Bob", "game": "tic tac toe"},
+            }
+        ]);
+        assert_eq!(serialised, expected);
+    }
+
+    #[test]
+    fn r_http_paginated() {
+        let opts = Options::new()
+            .page("link-next")
+            .page_limit(3)
+            .params(json!({"per_page": 100}));
+        let query = r.http(r.args(("https://api.github.com/users/rethinkdb/repos", opts)));
+        let serialised: Value = serde_json::from_str(&cmd::serialise(&query)).unwrap();
+        let expected = json!([
+            153,
+            ["https://api.github.com/users/rethinkdb/repos"],
+            {"params": {"per_page": 100}, "page": "link-next", "page_limit": 3}
+        ]);
+        assert_eq!(serialised, expected);
+    }
+}
@@ -10,3 +10,13 @@ impl Arg for Command {
         Self::new(TermType::InTimezone).with_arg(self).into_arg()
     }
 }
+
+/// A timezone offset such as `"-08:00"`
+impl<T> Arg for T
+where
+    T: Into<String>,
+{
+    fn arg(self) -> cmd::Arg<()> {
+        Command::from_json(self.into()).arg()
+    }
+}
@@ -1,17 +1,12 @@
 use super::asc::Asc;
 use super::desc::Desc;
 use crate::cmd;
-use crate::proto::{Command, Query};
-use serde::Serialize;
+use crate::proto::Command;
 
+/// The `index` option of commands that can use a secondary index
 #[derive(Debug, Clone)]
 pub struct Index(pub(crate) Command);
 
-#[derive(Serialize)]
-struct Inner<'a> {
-    index: Query<'a>,
-}
-
 pub trait Arg {
     fn arg(self) -> cmd::Arg<()>;
 }
@@ -21,27 +16,20 @@ where
     T: Into<String>,
 {
     fn arg(self) -> cmd::Arg<()> {
-        let cmd = Command::from_json(self.into());
-        Command::from_json(Inner { index: Query(&cmd) }).into_arg()
+        Command::from_json(self.into()).into_arg()
     }
 }
 
 impl Arg for Asc {
     fn arg(self) -> cmd::Arg<()> {
         let Asc(index) = self;
-        Command::from_json(Inner {
-            index: Query(&index),
-        })
-        .into_arg()
+        index.into_arg()
     }
 }
 
 impl Arg for Desc {
     fn arg(self) -> cmd::Arg<()> {
         let Desc(index) = self;
-        Command::from_json(Inner {
-            index: Query(&index),
-        })
-        .into_arg()
+        index.into_arg()
     }
 }
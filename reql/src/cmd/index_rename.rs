@@ -1,5 +1,75 @@
-use crate::cmd;
+use super::args::Args;
+use crate::{cmd, Command};
+use ql2::term::TermType;
+use reql_macros::CommandOptions;
+use serde::Serialize;
+
+#[derive(
+    Debug, Clone, Copy, CommandOptions, Serialize, Default, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[non_exhaustive]
+pub struct Options {
+    /// Drop an existing index with the new name instead of failing
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub overwrite: Option<bool>,
+}
 
 pub trait Arg {
-    fn arg(self) -> cmd::Arg<()>;
+    fn arg(self) -> cmd::Arg<Options>;
+}
+
+impl Arg for Args<(Command, Command)> {
+    fn arg(self) -> cmd::Arg<Options> {
+        let Args((old_name, new_name)) = self;
+        Command::new(TermType::IndexRename)
+            .with_arg(old_name)
+            .with_arg(new_name)
+            .into_arg()
+    }
+}
+
+impl<T, U> Arg for Args<(T, U)>
+where
+    T: Into<String>,
+    U: Into<String>,
+{
+    fn arg(self) -> cmd::Arg<Options> {
+        let Args((old_name, new_name)) = self;
+        let old_name = Command::from_json(old_name.into());
+        let new_name = Command::from_json(new_name.into());
+        Args((old_name, new_name)).arg()
+    }
+}
+
+impl Arg for Args<(Command, Command, Options)> {
+    fn arg(self) -> cmd::Arg<Options> {
+        let Args((old_name, new_name, opts)) = self;
+        Args((old_name, new_name)).arg().with_opts(opts)
+    }
+}
+
+impl<T, U> Arg for Args<(T, U, Options)>
+where
+    T: Into<String>,
+    U: Into<String>,
+{
+    fn arg(self) -> cmd::Arg<Options> {
+        let Args((old_name, new_name, opts)) = self;
+        Args((old_name, new_name)).arg().with_opts(opts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Options;
+    use crate::{cmd, r};
+
+    #[test]
+    fn r_table_index_rename_overwrite() {
+        let opts = Options::new().overwrite(true);
+        let query = r.table("foo").index_rename(r.args(("bar", "baz", opts)));
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[156,[[15,["foo"]],"bar","baz"],{"overwrite":true}]"#;
+        assert_eq!(serialised, expected);
+    }
 }
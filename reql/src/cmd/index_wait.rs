@@ -1,5 +1,52 @@
-use crate::cmd;
+use super::args::Args;
+use crate::{cmd, Command};
+use ql2::term::TermType;
 
 pub trait Arg {
     fn arg(self) -> cmd::Arg<()>;
 }
+
+impl Arg for Command {
+    fn arg(self) -> cmd::Arg<()> {
+        Self::new(TermType::IndexWait).with_arg(self).into_arg()
+    }
+}
+
+/// Wait for every index on the table
+impl Arg for () {
+    fn arg(self) -> cmd::Arg<()> {
+        Command::new(TermType::IndexWait).into_arg()
+    }
+}
+
+impl Arg for &str {
+    fn arg(self) -> cmd::Arg<()> {
+        Command::from_json(self).arg()
+    }
+}
+
+impl Arg for &String {
+    fn arg(self) -> cmd::Arg<()> {
+        Command::from_json(self.as_str()).arg()
+    }
+}
+
+impl Arg for String {
+    fn arg(self) -> cmd::Arg<()> {
+        Command::from_json(self).arg()
+    }
+}
+
+impl<T, const N: usize> Arg for Args<[T; N]>
+where
+    T: Into<String>,
+{
+    fn arg(self) -> cmd::Arg<()> {
+        let Args(indexes) = self;
+        let mut query = Command::new(TermType::IndexWait);
+        for index in IntoIterator::into_iter(indexes) {
+            query = query.with_arg(Command::from_json(index.into()));
+        }
+        query.into_arg()
+    }
+}
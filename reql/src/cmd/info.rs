@@ -10,3 +10,17 @@ impl Arg for Command {
         Self::new(TermType::Info).with_arg(self).into_arg()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{cmd, r};
+
+    #[test]
+    fn r_table_info() {
+        let query = r.table("users").info();
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[79,[[15,["users"]]]]"#;
+        assert_eq!(serialised, expected);
+        assert_eq!(cmd::serialise(&r.info(r.table("users"))), expected);
+    }
+}
@@ -1,5 +1,62 @@
-use crate::cmd;
+use super::args::Args;
+use crate::{cmd, Command, Func};
+use ql2::term::TermType;
 
 pub trait Arg {
     fn arg(self) -> cmd::Arg<()>;
 }
+
+impl Arg for Args<(Command, Command)> {
+    fn arg(self) -> cmd::Arg<()> {
+        let Args((other, predicate)) = self;
+        Command::new(TermType::InnerJoin)
+            .with_arg(other)
+            .with_arg(predicate)
+            .into_arg()
+    }
+}
+
+impl Arg for Args<(Command, Func)> {
+    fn arg(self) -> cmd::Arg<()> {
+        let Args((other, Func(predicate))) = self;
+        Args((other, predicate)).arg()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{self as reql, cmd, func, r};
+    use serde_json::{json, Value};
+
+    #[test]
+    fn r_table_inner_join() {
+        let query = r.table("marvel").inner_join(r.args((
+            r.table("dc"),
+            func!(|marvel, dc| marvel.bracket("strength").lt(dc.bracket("strength"))),
+        )));
+        let serialised: Value = serde_json::from_str(&cmd::serialise(&query)).unwrap();
+        let vars = serialised[1][2][1][0][1].clone();
+        let (marvel, dc) = (vars[0].clone(), vars[1].clone());
+        let expected = json!([
+            48,
+            [
+                [15, ["marvel"]],
+                [15, ["dc"]],
+                [
+                    69,
+                    [
+                        [2, [marvel, dc]],
+                        [
+                            19,
+                            [
+                                [170, [[10, [marvel]], "strength"]],
+                                [170, [[10, [dc]], "strength"]]
+                            ]
+                        ]
+                    ]
+                ]
+            ]
+        ]);
+        assert_eq!(serialised, expected);
+    }
+}
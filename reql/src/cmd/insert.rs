@@ -1,6 +1,6 @@
 use super::args::Args;
 use crate::cmd::{Durability, ReturnChanges};
-use crate::{cmd, Command};
+use crate::{cmd, r, Command, Func};
 use ql2::term::TermType;
 use reql_macros::CommandOptions;
 use serde::Serialize;
@@ -13,6 +13,21 @@ pub struct Options {
     pub durability: Option<Durability>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub return_changes: Option<ReturnChanges>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conflict: Option<Conflict>,
+}
+
+/// What to do when a document with the same primary key already exists
+#[derive(Debug, Clone, Copy, Serialize, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum Conflict {
+    /// Do not insert the new document and record the conflict as an error
+    Error,
+    /// Replace the old document in its entirety with the new one
+    Replace,
+    /// Update fields of the old document with fields from the new one
+    Update,
 }
 
 pub trait Arg {
@@ -58,10 +73,80 @@ where
     }
 }
 
+impl Arg for Args<(Command, Func)> {
+    fn arg(self) -> cmd::Arg<Options> {
+        let Args((val, Func(func))) = self;
+        val.arg().with_term_opt("conflict", func)
+    }
+}
+
+impl<T> Arg for Args<(T, Func)>
+where
+    T: Serialize,
+{
+    fn arg(self) -> cmd::Arg<Options> {
+        let Args((val, Func(func))) = self;
+        Command::from_json(val)
+            .arg()
+            .with_term_opt("conflict", func)
+    }
+}
+
+impl Arg for Args<(Command, Func, Options)> {
+    fn arg(self) -> cmd::Arg<Options> {
+        let Args((val, func, options)) = self;
+        Args((val, func)).arg().with_opts(options)
+    }
+}
+
+impl<T> Arg for Args<(T, Func, Options)>
+where
+    T: Serialize,
+{
+    fn arg(self) -> cmd::Arg<Options> {
+        let Args((val, func, options)) = self;
+        Args((val, func)).arg().with_opts(options)
+    }
+}
+
+// A conflict resolver replaces the `conflict` option entirely, so it is
+// only set when the caller didn't pass one.
+pub(crate) fn upsert(table: Command, mut arg: cmd::Arg<Options>) -> Command {
+    if !arg.arg.has_term_opt("conflict") {
+        let opts = arg.opts.unwrap_or_default();
+        arg.opts = Some(opts.conflict(Conflict::Update));
+    }
+    arg.into_cmd().with_parent(table)
+}
+
+// Inserts the document, then reads its primary key back from the change
+// the insert made. `always` includes the change even when an identical
+// document was already there, with `conflict` set to `update`.
+pub(crate) fn and_return(table: Command, mut arg: cmd::Arg<Options>) -> Command {
+    let opts = arg.opts.unwrap_or_default();
+    arg.opts = Some(opts.return_changes(ReturnChanges::Always));
+    let primary_key = table.clone().info().bracket("primary_key");
+    let insert = arg.into_cmd().with_parent(table);
+    let id = crate::var_counter();
+    let res = || Command::var(id);
+    let key = res()
+        .bracket("changes")
+        .nth(0)
+        .bracket("new_val")
+        .bracket(primary_key);
+    let failed = res().bracket("errors").gt(0);
+    let error = r.error(res().bracket("first_error"));
+    let body = r.branch(r.args((failed, error, key)));
+    insert.do_(Func::new(vec![id], body))
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{cmd, r};
+    use super::{Conflict, Options};
+    use crate::cmd::Durability;
+    use crate::{self as reql, cmd, func, r};
     use serde::Serialize;
+    use serde_json::{json, Value};
 
     #[derive(Serialize)]
     struct Document<'a> {
@@ -75,4 +160,99 @@ mod tests {
         let expected = r#"[56,[[15,["foo"]],{"item":"bar"}]]"#;
         assert_eq!(serialised, expected);
     }
+
+    #[test]
+    fn r_table_insert_with_options() {
+        let docs = [Document { item: "bar" }, Document { item: "baz" }];
+        let opts = Options::new()
+            .conflict(Conflict::Update)
+            .durability(Durability::Soft);
+        let query = r.table("foo").insert(r.args((docs, opts)));
+        // Options are an object, whose keys are not serialised in any
+        // particular order
+        let serialised: Value = serde_json::from_str(&cmd::serialise(&query)).unwrap();
+        let expected = json!([
+            56,
+            [[15, ["foo"]], [2, [{"item": "bar"}, {"item": "baz"}]]],
+            {"durability": "soft", "conflict": "update"}
+        ]);
+        assert_eq!(serialised, expected);
+    }
+
+    #[test]
+    fn r_table_insert_and_return() {
+        let query = r.table("foo").insert_and_return(json!({"item": "bar"}));
+        let serialised: Value = serde_json::from_str(&cmd::serialise(&query)).unwrap();
+        let var = serialised[1][0][1][0][1][0].clone();
+        let res = json!([10, [var]]);
+        let expected = json!([
+            64,
+            [
+                [
+                    69,
+                    [
+                        [2, [var]],
+                        [
+                            65,
+                            [
+                                [21, [[170, [res, "errors"]], 0]],
+                                [12, [[170, [res, "first_error"]]]],
+                                [
+                                    170,
+                                    [
+                                        [170, [[45, [[170, [res, "changes"]], 0]], "new_val"]],
+                                        [170, [[79, [[15, ["foo"]]]], "primary_key"]]
+                                    ]
+                                ]
+                            ]
+                        ]
+                    ]
+                ],
+                [56, [[15, ["foo"]], {"item": "bar"}], {"return_changes": "always"}]
+            ]
+        ]);
+        assert_eq!(serialised, expected);
+    }
+
+    #[test]
+    fn r_table_upsert() {
+        let query = r.table("foo").upsert(Document { item: "bar" });
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[56,[[15,["foo"]],{"item":"bar"}],{"conflict":"update"}]"#;
+        assert_eq!(serialised, expected);
+    }
+
+    #[test]
+    fn r_table_upsert_keeps_other_options() {
+        let opts = Options::new().durability(Durability::Soft);
+        let query = r
+            .table("foo")
+            .upsert(r.args((Document { item: "bar" }, opts)));
+        let serialised: Value = serde_json::from_str(&cmd::serialise(&query)).unwrap();
+        let expected = json!([
+            56,
+            [[15, ["foo"]], {"item": "bar"}],
+            {"durability": "soft", "conflict": "update"}
+        ]);
+        assert_eq!(serialised, expected);
+    }
+
+    #[test]
+    fn r_table_upsert_with_resolver() {
+        let resolver = func!(|_id, old, new| old.merge(new));
+        let query = r
+            .table("foo")
+            .upsert(r.args((Document { item: "bar" }, resolver)));
+        let serialised = cmd::serialise(&query);
+        assert!(serialised.starts_with(r#"[56,[[15,["foo"]],{"item":"bar"}],{"conflict":[69,"#));
+        assert!(!serialised.contains(r#""update""#));
+    }
+
+    #[test]
+    fn writes_are_recognised() {
+        assert!(r.table("foo").insert(json!({"item": "bar"})).is_write());
+        let nested = r.table("foo").get(1).do_(func!(|doc| doc.delete(())));
+        assert!(nested.is_write());
+        assert!(!r.table("foo").get(1).get_field("item").is_write());
+    }
 }
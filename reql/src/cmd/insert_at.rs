@@ -1,5 +1,7 @@
+use super::args::Args;
 use crate::{cmd, Command};
 use ql2::term::TermType;
+use serde::Serialize;
 
 pub trait Arg {
     fn arg(self) -> cmd::Arg<()>;
@@ -10,3 +12,34 @@ impl Arg for Command {
         Self::new(TermType::InsertAt).with_arg(self).into_arg()
     }
 }
+
+impl Arg for Args<(Command, Command)> {
+    fn arg(self) -> cmd::Arg<()> {
+        let Args((offset, value)) = self;
+        offset.arg().with_arg(value)
+    }
+}
+
+impl<T> Arg for Args<(i64, T)>
+where
+    T: Serialize,
+{
+    fn arg(self) -> cmd::Arg<()> {
+        let Args((offset, value)) = self;
+        let offset = Command::from_json(offset);
+        Args((offset, Command::from_json(value))).arg()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{cmd, r};
+
+    #[test]
+    fn r_expr_insert_at() {
+        let query = r.expr(["a", "c"]).insert_at(r.args((1, "b")));
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[82,[[2,["a","c"]],1,"b"]]"#;
+        assert_eq!(serialised, expected);
+    }
+}
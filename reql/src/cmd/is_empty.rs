@@ -10,3 +10,16 @@ impl Arg for Command {
         Self::new(TermType::IsEmpty).with_arg(self).into_arg()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{cmd, r};
+
+    #[test]
+    fn r_table_is_empty() {
+        let query = r.table("marvel").is_empty();
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[86,[[15,["marvel"]]]]"#;
+        assert_eq!(serialised, expected);
+    }
+}
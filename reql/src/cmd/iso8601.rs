@@ -1,12 +1,45 @@
+use super::args::Args;
 use crate::{cmd, Command};
 use ql2::term::TermType;
+use reql_macros::CommandOptions;
+use serde::Serialize;
+use std::borrow::Cow;
+
+#[derive(
+    Debug, Clone, CommandOptions, Serialize, Default, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[non_exhaustive]
+pub struct Options {
+    /// The timezone to use when the string doesn't specify one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_timezone: Option<Cow<'static, str>>,
+}
 
 pub trait Arg {
-    fn arg(self) -> cmd::Arg<()>;
+    fn arg(self) -> cmd::Arg<Options>;
 }
 
 impl Arg for Command {
-    fn arg(self) -> cmd::Arg<()> {
+    fn arg(self) -> cmd::Arg<Options> {
         Self::new(TermType::Iso8601).with_arg(self).into_arg()
     }
 }
+
+impl<T> Arg for T
+where
+    T: Into<String>,
+{
+    fn arg(self) -> cmd::Arg<Options> {
+        Command::from_json(self.into()).arg()
+    }
+}
+
+impl<T> Arg for Args<(T, Options)>
+where
+    T: Into<String>,
+{
+    fn arg(self) -> cmd::Arg<Options> {
+        let Args((date, opts)) = self;
+        date.arg().with_opts(opts)
+    }
+}
@@ -1,12 +1,71 @@
+use super::args::Args;
 use crate::{cmd, Command};
 use ql2::term::TermType;
+use reql_macros::CommandOptions;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, CommandOptions, Serialize, Default, PartialEq, PartialOrd)]
+#[non_exhaustive]
+pub struct Options {
+    /// How many seconds the code may run for, 5 by default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<f64>,
+}
 
 pub trait Arg {
-    fn arg(self) -> cmd::Arg<()>;
+    fn arg(self) -> cmd::Arg<Options>;
 }
 
 impl Arg for Command {
-    fn arg(self) -> cmd::Arg<()> {
+    fn arg(self) -> cmd::Arg<Options> {
         Self::new(TermType::Javascript).with_arg(self).into_arg()
     }
 }
+
+impl<T> Arg for T
+where
+    T: Into<String>,
+{
+    fn arg(self) -> cmd::Arg<Options> {
+        Command::from_json(self.into()).arg()
+    }
+}
+
+impl Arg for Args<(Command, Options)> {
+    fn arg(self) -> cmd::Arg<Options> {
+        let Args((source, opts)) = self;
+        source.arg().with_opts(opts)
+    }
+}
+
+impl<T> Arg for Args<(T, Options)>
+where
+    T: Into<String>,
+{
+    fn arg(self) -> cmd::Arg<Options> {
+        let Args((source, opts)) = self;
+        source.arg().with_opts(opts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Options;
+    use crate::{cmd, r};
+
+    #[test]
+    fn r_js() {
+        let query = r.js("'str1' + 'str2'");
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[11,["'str1' + 'str2'"]]"#;
+        assert_eq!(serialised, expected);
+    }
+
+    #[test]
+    fn r_js_with_timeout() {
+        let query = r.js(r.args(("while(true) {}", Options::new().timeout(1.3))));
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[11,["while(true) {}"],{"timeout":1.3}]"#;
+        assert_eq!(serialised, expected);
+    }
+}
@@ -1,4 +1,5 @@
-use crate::{cmd, Command};
+use crate::types::geo::Line;
+use crate::{cmd, r, Command};
 use ql2::term::TermType;
 
 pub trait Arg {
@@ -10,3 +11,13 @@ impl Arg for Command {
         Self::new(TermType::Line).with_arg(self).into_arg()
     }
 }
+
+impl Arg for Line {
+    fn arg(self) -> cmd::Arg<()> {
+        let mut query = Command::new(TermType::Line);
+        for point in self.0 {
+            query = query.with_arg(r.point(point));
+        }
+        query.into_arg()
+    }
+}
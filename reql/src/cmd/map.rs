@@ -33,8 +33,6 @@ impl Arg for Args<(Command, Func)> {
     }
 }
 
-#[allow(array_into_iter)]
-#[allow(clippy::into_iter_on_ref)]
 impl<const N: usize> Arg for Args<([Command; N], Func)> {
     fn arg(self) -> cmd::Arg<()> {
         let Args((sequence, Func(func))) = self;
@@ -42,7 +40,7 @@ impl<const N: usize> Arg for Args<([Command; N], Func)> {
         if N == 0 {
             func.arg()
         } else {
-            for (i, arg) in sequence.into_iter().cloned().enumerate() {
+            for (i, arg) in IntoIterator::into_iter(sequence).enumerate() {
                 if i == 0 {
                     cmd = arg.arg();
                 } else {
@@ -54,11 +52,9 @@ impl<const N: usize> Arg for Args<([Command; N], Func)> {
     }
 }
 
-#[allow(array_into_iter)]
-#[allow(clippy::into_iter_on_ref)]
 impl<T, const N: usize> Arg for Args<([T; N], Func)>
 where
-    T: Serialize + Clone,
+    T: Serialize,
 {
     fn arg(self) -> cmd::Arg<()> {
         let Args((sequence, Func(func))) = self;
@@ -66,7 +62,7 @@ where
         if N == 0 {
             func.arg()
         } else {
-            for (i, arg) in sequence.into_iter().cloned().enumerate() {
+            for (i, arg) in IntoIterator::into_iter(sequence).enumerate() {
                 let arg = Command::from_json(arg);
                 if i == 0 {
                     cmd = arg.arg();
@@ -10,3 +10,25 @@ impl Arg for Command {
         Self::new(TermType::Match).with_arg(self).into_arg()
     }
 }
+
+impl<T> Arg for T
+where
+    T: Into<String>,
+{
+    fn arg(self) -> cmd::Arg<()> {
+        Command::from_json(self.into()).arg()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{cmd, r};
+
+    #[test]
+    fn r_expr_match() {
+        let query = r.expr("name@example.com").match_("@example\\.com$");
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[97,["name@example.com","@example\\.com$"]]"#;
+        assert_eq!(serialised, expected);
+    }
+}
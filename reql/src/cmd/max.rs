@@ -1,4 +1,5 @@
-use crate::{cmd, Command};
+use super::index::Index;
+use crate::{cmd, Command, Func};
 use ql2::term::TermType;
 
 pub trait Arg {
@@ -10,3 +11,52 @@ impl Arg for Command {
         Self::new(TermType::Max).with_arg(self).into_arg()
     }
 }
+
+impl Arg for () {
+    fn arg(self) -> cmd::Arg<()> {
+        Command::new(TermType::Max).into_arg()
+    }
+}
+
+impl Arg for &str {
+    fn arg(self) -> cmd::Arg<()> {
+        Command::from_json(self).arg()
+    }
+}
+
+impl Arg for String {
+    fn arg(self) -> cmd::Arg<()> {
+        Command::from_json(self).arg()
+    }
+}
+
+impl Arg for Func {
+    fn arg(self) -> cmd::Arg<()> {
+        let Func(func) = self;
+        func.arg()
+    }
+}
+
+/// Get the row with the largest value of a secondary index of a table,
+/// which the server finds without reading the whole table
+impl Arg for Index {
+    fn arg(self) -> cmd::Arg<()> {
+        let Index(index) = self;
+        Command::new(TermType::Max)
+            .into_arg()
+            .with_term_opt("index", index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{cmd, r};
+
+    #[test]
+    fn r_table_max_index() {
+        let query = r.table("players").max(r.index("score"));
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[148,[[15,["players"]]],{"index":"score"}]"#;
+        assert_eq!(serialised, expected);
+    }
+}
@@ -1,5 +1,6 @@
-use crate::{cmd, Command};
+use crate::{cmd, Command, Func};
 use ql2::term::TermType;
+use serde::Serialize;
 
 pub trait Arg {
     fn arg(self) -> cmd::Arg<()>;
@@ -10,3 +11,35 @@ impl Arg for Command {
         Self::new(TermType::Merge).with_arg(self).into_arg()
     }
 }
+
+impl<T> Arg for T
+where
+    T: Serialize,
+{
+    fn arg(self) -> cmd::Arg<()> {
+        Command::from_json(self).arg()
+    }
+}
+
+/// Merge the object returned by the function, which is called with
+/// the document being merged into
+impl Arg for Func {
+    fn arg(self) -> cmd::Arg<()> {
+        let Func(func) = self;
+        func.arg()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{cmd, r};
+    use serde_json::json;
+
+    #[test]
+    fn r_table_get_merge() {
+        let query = r.table("foo").get(1).merge(json!({"bar": true}));
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[35,[[16,[[15,["foo"]],1]],{"bar":true}]]"#;
+        assert_eq!(serialised, expected);
+    }
+}
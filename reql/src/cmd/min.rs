@@ -1,4 +1,5 @@
-use crate::{cmd, Command};
+use super::index::Index;
+use crate::{cmd, Command, Func};
 use ql2::term::TermType;
 
 pub trait Arg {
@@ -10,3 +11,52 @@ impl Arg for Command {
         Self::new(TermType::Min).with_arg(self).into_arg()
     }
 }
+
+impl Arg for () {
+    fn arg(self) -> cmd::Arg<()> {
+        Command::new(TermType::Min).into_arg()
+    }
+}
+
+impl Arg for &str {
+    fn arg(self) -> cmd::Arg<()> {
+        Command::from_json(self).arg()
+    }
+}
+
+impl Arg for String {
+    fn arg(self) -> cmd::Arg<()> {
+        Command::from_json(self).arg()
+    }
+}
+
+impl Arg for Func {
+    fn arg(self) -> cmd::Arg<()> {
+        let Func(func) = self;
+        func.arg()
+    }
+}
+
+/// Get the row with the smallest value of a secondary index of a table,
+/// which the server finds without reading the whole table
+impl Arg for Index {
+    fn arg(self) -> cmd::Arg<()> {
+        let Index(index) = self;
+        Command::new(TermType::Min)
+            .into_arg()
+            .with_term_opt("index", index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{cmd, r};
+
+    #[test]
+    fn r_table_min_index() {
+        let query = r.table("players").min(r.index("score"));
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[147,[[15,["players"]]],{"index":"score"}]"#;
+        assert_eq!(serialised, expected);
+    }
+}
@@ -1,17 +1,33 @@
 use crate::{cmd, Command};
 use ql2::term::TermType;
+use serde::Serialize;
 use std::ops::Mul;
 
 pub trait Arg {
     fn arg(self) -> cmd::Arg<()>;
 }
 
+impl Arg for cmd::Arg<()> {
+    fn arg(self) -> cmd::Arg<()> {
+        self
+    }
+}
+
 impl Arg for Command {
     fn arg(self) -> cmd::Arg<()> {
         Self::new(TermType::Mul).with_arg(self).into_arg()
     }
 }
 
+impl<T> Arg for T
+where
+    T: Serialize,
+{
+    fn arg(self) -> cmd::Arg<()> {
+        Command::from_json(self).arg()
+    }
+}
+
 impl<T> Mul<T> for Command
 where
     T: Arg,
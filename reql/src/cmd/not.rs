@@ -10,3 +10,22 @@ impl Arg for Command {
         Self::new(TermType::Not).with_arg(self).into_arg()
     }
 }
+
+impl Arg for () {
+    fn arg(self) -> cmd::Arg<()> {
+        Command::new(TermType::Not).into_arg()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{cmd, r};
+
+    #[test]
+    fn r_expr_not() {
+        let query = r.expr(true).not(());
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[23,[true]]"#;
+        assert_eq!(serialised, expected);
+    }
+}
@@ -16,3 +16,16 @@ impl Arg for isize {
         Command::from_json(self).arg()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{cmd, r};
+
+    #[test]
+    fn r_expr_nth_negative() {
+        let query = r.expr([1, 2, 3]).nth(-1);
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[45,[[2,[1,2,3]],-1]]"#;
+        assert_eq!(serialised, expected);
+    }
+}
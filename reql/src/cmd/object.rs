@@ -1,3 +1,4 @@
+use super::args::Args;
 use crate::{cmd, Command};
 use ql2::term::TermType;
 
@@ -10,3 +11,16 @@ impl Arg for Command {
         Self::new(TermType::Object).with_arg(self).into_arg()
     }
 }
+
+/// Keys and values, one after the other, as in
+/// `r.args([r.expr("id"), r.expr(5), r.expr("data"), query])`
+impl<const N: usize> Arg for Args<[Command; N]> {
+    fn arg(self) -> cmd::Arg<()> {
+        let Args(arr) = self;
+        let mut query = Command::new(TermType::Object);
+        for arg in IntoIterator::into_iter(arr) {
+            query = query.with_arg(arg);
+        }
+        query.into_arg()
+    }
+}
@@ -1,5 +1,6 @@
-use crate::{cmd, Command};
+use crate::{cmd, Command, Func};
 use ql2::term::TermType;
+use serde::Serialize;
 
 pub trait Arg {
     fn arg(self) -> cmd::Arg<()>;
@@ -10,3 +11,56 @@ impl Arg for Command {
         Self::new(TermType::OffsetsOf).with_arg(self).into_arg()
     }
 }
+
+impl<T> Arg for T
+where
+    T: Serialize,
+{
+    fn arg(self) -> cmd::Arg<()> {
+        Command::from_json(self).arg()
+    }
+}
+
+/// A predicate picking the elements whose offsets are returned
+impl Arg for Func {
+    fn arg(self) -> cmd::Arg<()> {
+        let Func(func) = self;
+        func.arg()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{self as reql, cmd, func, r};
+    use serde_json::{json, Value};
+
+    #[test]
+    fn r_expr_offsets_of() {
+        let query = r.expr(json!(["a", "b", "c"])).offsets_of("c");
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[87,[[2,["a","b","c"]],"c"]]"#;
+        assert_eq!(serialised, expected);
+    }
+
+    #[test]
+    fn r_table_offsets_of_predicate() {
+        let query = r
+            .table("marvel")
+            .union(r.table("dc"))
+            .order_by("popularity")
+            .offsets_of(func!(|hero| hero
+                .bracket("superpowers")
+                .contains("invisibility")));
+        let serialised: Value = serde_json::from_str(&cmd::serialise(&query)).unwrap();
+        let var = serialised[1][1][1][0][1][0].clone();
+        let func = json!([
+            69,
+            [
+                [2, [var]],
+                [93, [[170, [[10, [var]], "superpowers"]], "invisibility"]]
+            ]
+        ]);
+        assert_eq!(serialised[0], 87);
+        assert_eq!(serialised[1][1], func);
+    }
+}
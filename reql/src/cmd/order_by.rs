@@ -39,13 +39,74 @@ where
     }
 }
 
-impl<T> Arg for Args<(T, Index)>
+impl Arg for Args<(&str, Index)> {
+    fn arg(self) -> cmd::Arg<()> {
+        let Args((key, index)) = self;
+        Args((Command::from_json(key), index)).arg()
+    }
+}
+
+impl Arg for Args<(String, Index)> {
+    fn arg(self) -> cmd::Arg<()> {
+        let Args((key, index)) = self;
+        Args((Command::from_json(key), index)).arg()
+    }
+}
+
+impl Arg for Args<(Command, Index)> {
+    fn arg(self) -> cmd::Arg<()> {
+        let Args((key, Index(index))) = self;
+        key.arg().with_term_opt("index", index)
+    }
+}
+
+impl Arg for Args<(Asc, Index)> {
+    fn arg(self) -> cmd::Arg<()> {
+        let Args((key, index)) = self;
+        Args((Command::from(key), index)).arg()
+    }
+}
+
+impl Arg for Args<(Desc, Index)> {
+    fn arg(self) -> cmd::Arg<()> {
+        let Args((key, index)) = self;
+        Args((Command::from(key), index)).arg()
+    }
+}
+
+/// Order by several keys, such as
+/// `r.args([Command::from(r.desc("age")), r.expr("name")])`
+///
+/// Later keys break ties between rows which compare equal on earlier ones.
+impl<const N: usize> Arg for Args<[Command; N]> {
+    fn arg(self) -> cmd::Arg<()> {
+        let Args(keys) = self;
+        let mut query = Command::new(TermType::OrderBy);
+        for key in IntoIterator::into_iter(keys) {
+            query = query.with_arg(key);
+        }
+        query.into_arg()
+    }
+}
+
+impl<T, const N: usize> Arg for Args<[T; N]>
 where
     T: Into<String>,
 {
     fn arg(self) -> cmd::Arg<()> {
-        let Args((key, Index(index))) = self;
-        Command::from_json(key.into()).arg().with_arg(index)
+        let Args(keys) = self;
+        let mut query = Command::new(TermType::OrderBy);
+        for key in IntoIterator::into_iter(keys) {
+            query = query.with_arg(Command::from_json(key.into()));
+        }
+        query.into_arg()
+    }
+}
+
+impl<const N: usize> Arg for Args<([Command; N], Index)> {
+    fn arg(self) -> cmd::Arg<()> {
+        let Args((keys, Index(index))) = self;
+        Args(keys).arg().with_term_opt("index", index)
     }
 }
 
@@ -59,13 +120,48 @@ impl Arg for Func {
 impl Arg for Args<(Func, Index)> {
     fn arg(self) -> cmd::Arg<()> {
         let Args((Func(func), Index(index))) = self;
-        func.arg().with_arg(index)
+        func.arg().with_term_opt("index", index)
     }
 }
 
 impl Arg for Index {
     fn arg(self) -> cmd::Arg<()> {
-        let Index(query) = self;
-        query.arg()
+        let Index(index) = self;
+        Command::new(TermType::OrderBy)
+            .into_arg()
+            .with_term_opt("index", index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{cmd, r, Command};
+
+    #[test]
+    fn r_table_order_by_index() {
+        let query = r.table("foo").order_by(r.index(r.desc("bar")));
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[41,[[15,["foo"]]],{"index":[74,["bar"]]}]"#;
+        assert_eq!(serialised, expected);
+    }
+
+    #[test]
+    fn r_table_order_by_keys() {
+        let query = r
+            .table("foo")
+            .order_by(r.args([Command::from(r.desc("bar")), r.asc("baz").into()]));
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[41,[[15,["foo"]],[74,["bar"]],[73,["baz"]]]]"#;
+        assert_eq!(serialised, expected);
+    }
+
+    #[test]
+    fn r_table_order_by_key_and_index() {
+        let query = r
+            .table("foo")
+            .order_by(r.args((r.desc("bar"), r.index("baz"))));
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[41,[[15,["foo"]],[74,["bar"]]],{"index":"baz"}]"#;
+        assert_eq!(serialised, expected);
     }
 }
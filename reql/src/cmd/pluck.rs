@@ -1,5 +1,7 @@
+use super::args::Args;
 use crate::{cmd, Command};
 use ql2::term::TermType;
+use serde::Serialize;
 
 pub trait Arg {
     fn arg(self) -> cmd::Arg<()>;
@@ -10,3 +12,52 @@ impl Arg for Command {
         Self::new(TermType::Pluck).with_arg(self).into_arg()
     }
 }
+
+/// A field name, or an object selecting nested fields such as
+/// `json!({"address": ["city", "zip"]})`
+impl<T> Arg for T
+where
+    T: Serialize,
+{
+    fn arg(self) -> cmd::Arg<()> {
+        Command::from_json(self).arg()
+    }
+}
+
+impl<T, const N: usize> Arg for Args<[T; N]>
+where
+    T: Serialize,
+{
+    fn arg(self) -> cmd::Arg<()> {
+        let Args(fields) = self;
+        let mut query = Command::new(TermType::Pluck);
+        for field in IntoIterator::into_iter(fields) {
+            query = query.with_arg(Command::from_json(field));
+        }
+        query.into_arg()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{cmd, r};
+    use serde_json::json;
+
+    #[test]
+    fn r_table_pluck_fields() {
+        let query = r.table("foo").pluck(r.args(["bar", "baz"]));
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[33,[[15,["foo"]],"bar","baz"]]"#;
+        assert_eq!(serialised, expected);
+    }
+
+    #[test]
+    fn r_table_pluck_nested() {
+        let query = r
+            .table("foo")
+            .pluck(r.args([json!("name"), json!({"address": ["city", "zip"]})]));
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[33,[[15,["foo"]],"name",{"address":[2,["city","zip"]]}]]"#;
+        assert_eq!(serialised, expected);
+    }
+}
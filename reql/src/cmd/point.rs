@@ -1,5 +1,8 @@
+use super::args::Args;
+use crate::types::geo::Point;
 use crate::{cmd, Command};
 use ql2::term::TermType;
+use serde_json::Value;
 
 pub trait Arg {
     fn arg(self) -> cmd::Arg<()>;
@@ -10,3 +13,51 @@ impl Arg for Command {
         Self::new(TermType::Point).with_arg(self).into_arg()
     }
 }
+
+impl Arg for Args<(f64, f64)> {
+    fn arg(self) -> cmd::Arg<()> {
+        let Args((longitude, latitude)) = self;
+        point(longitude, latitude).into_arg()
+    }
+}
+
+impl Arg for Point {
+    fn arg(self) -> cmd::Arg<()> {
+        point(self.longitude, self.latitude).into_arg()
+    }
+}
+
+// Out of range coordinates are caught before the query is sent
+fn point(longitude: f64, latitude: f64) -> Command {
+    let query = Command::new(TermType::Point);
+    match Point::new(longitude, latitude) {
+        Ok(point) => query
+            .with_arg(Command::from_json(point.longitude))
+            .with_arg(Command::from_json(point.latitude)),
+        Err(error) => query.with_arg(Command::from(Err::<Value, _>(crate::Error::from(error)))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::geo::Point;
+    use crate::{cmd, r};
+
+    #[test]
+    fn r_point() {
+        let query = r.point(r.args((-122.423246, 37.779388)));
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[159,[-122.423246,37.779388]]"#;
+        assert_eq!(serialised, expected);
+        let point = Point::new(-122.423246, 37.779388).unwrap();
+        assert_eq!(cmd::serialise(&r.point(point)), expected);
+    }
+
+    #[test]
+    fn r_point_out_of_range() {
+        for &(longitude, latitude) in &[(-181.0, 0.0), (0.0, 90.5)] {
+            let query = r.point(r.args((longitude, latitude)));
+            assert!(serde_json::to_string(&crate::Query(&query)).is_err());
+        }
+    }
+}
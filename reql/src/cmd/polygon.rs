@@ -1,4 +1,5 @@
-use crate::{cmd, Command};
+use crate::types::geo::{Point, Polygon};
+use crate::{cmd, r, Command};
 use ql2::term::TermType;
 
 pub trait Arg {
@@ -10,3 +11,47 @@ impl Arg for Command {
         Self::new(TermType::Polygon).with_arg(self).into_arg()
     }
 }
+
+// The first ring is the outline, the rest are holes which are
+// subtracted from it
+impl Arg for Polygon {
+    fn arg(self) -> cmd::Arg<()> {
+        let mut rings = self.0.into_iter();
+        let mut query = ring(rings.next().unwrap_or_default());
+        for hole in rings {
+            query = query.polygon_sub(ring(hole));
+        }
+        query.into_arg()
+    }
+}
+
+fn ring(points: Vec<Point>) -> Command {
+    let mut query = Command::new(TermType::Polygon);
+    for point in points {
+        query = query.with_arg(r.point(point));
+    }
+    query
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::geo::{Point, Polygon};
+    use crate::{cmd, r};
+
+    #[test]
+    fn r_polygon_with_hole() {
+        let point = |lon, lat| Point::new(lon, lat).unwrap();
+        let polygon = Polygon(vec![
+            vec![point(0.0, 0.0), point(0.0, 2.0), point(2.0, 2.0)],
+            vec![point(0.5, 0.5), point(0.5, 1.0), point(1.0, 1.0)],
+        ]);
+        let serialised = cmd::serialise(&r.polygon(polygon));
+        let expected = concat!(
+            "[171,[",
+            "[161,[[159,[0.0,0.0]],[159,[0.0,2.0]],[159,[2.0,2.0]]]],",
+            "[161,[[159,[0.5,0.5]],[159,[0.5,1.0]],[159,[1.0,1.0]]]]",
+            "]]"
+        );
+        assert_eq!(serialised, expected);
+    }
+}
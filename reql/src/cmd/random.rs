@@ -1,12 +1,118 @@
+use super::args::Args;
 use crate::{cmd, Command};
 use ql2::term::TermType;
+use reql_macros::CommandOptions;
+use serde::Serialize;
+
+#[derive(
+    Debug, Clone, Copy, CommandOptions, Serialize, Default, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[non_exhaustive]
+pub struct Options {
+    /// Return a floating-point number rather than an integer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub float: Option<bool>,
+}
 
 pub trait Arg {
-    fn arg(self) -> cmd::Arg<()>;
+    fn arg(self) -> cmd::Arg<Options>;
+}
+
+/// A float in `[0, 1)`
+impl Arg for () {
+    fn arg(self) -> cmd::Arg<Options> {
+        Command::new(TermType::Random).into_arg()
+    }
+}
+
+impl Arg for Options {
+    fn arg(self) -> cmd::Arg<Options> {
+        ().arg().with_opts(self)
+    }
 }
 
 impl Arg for Command {
-    fn arg(self) -> cmd::Arg<()> {
+    fn arg(self) -> cmd::Arg<Options> {
         Self::new(TermType::Random).with_arg(self).into_arg()
     }
 }
+
+/// An integer in `[0, upper)`
+impl Arg for i64 {
+    fn arg(self) -> cmd::Arg<Options> {
+        Command::from_json(self).arg()
+    }
+}
+
+/// A float in `[0, upper)`
+impl Arg for f64 {
+    fn arg(self) -> cmd::Arg<Options> {
+        Command::from_json(self).arg()
+    }
+}
+
+impl Arg for Args<(Command, Command)> {
+    fn arg(self) -> cmd::Arg<Options> {
+        let Args((lower, upper)) = self;
+        lower.arg().with_arg(upper)
+    }
+}
+
+/// An integer in `[lower, upper)`
+impl Arg for Args<(i64, i64)> {
+    fn arg(self) -> cmd::Arg<Options> {
+        let Args((lower, upper)) = self;
+        Args((Command::from_json(lower), Command::from_json(upper))).arg()
+    }
+}
+
+/// A float in `[lower, upper)`
+impl Arg for Args<(f64, f64)> {
+    fn arg(self) -> cmd::Arg<Options> {
+        let Args((lower, upper)) = self;
+        Args((Command::from_json(lower), Command::from_json(upper))).arg()
+    }
+}
+
+impl<T> Arg for Args<(T, Options)>
+where
+    T: Arg,
+{
+    fn arg(self) -> cmd::Arg<Options> {
+        let Args((upper, opts)) = self;
+        upper.arg().with_opts(opts)
+    }
+}
+
+impl<T> Arg for Args<(T, T, Options)>
+where
+    Args<(T, T)>: Arg,
+{
+    fn arg(self) -> cmd::Arg<Options> {
+        let Args((lower, upper, opts)) = self;
+        Args((lower, upper)).arg().with_opts(opts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Options;
+    use crate::{cmd, r};
+
+    #[test]
+    fn r_random() {
+        let serialised = cmd::serialise(&r.random(()));
+        assert_eq!(serialised, "[151,[]]");
+        let serialised = cmd::serialise(&r.random(100));
+        assert_eq!(serialised, "[151,[100]]");
+        let serialised = cmd::serialise(&r.random(r.args((-10, 10))));
+        assert_eq!(serialised, "[151,[-10,10]]");
+    }
+
+    #[test]
+    fn r_random_float() {
+        let query = r.random(r.args((1, 10, Options::new().float(true))));
+        let serialised = cmd::serialise(&query);
+        assert_eq!(serialised, r#"[151,[1,10],{"float":true}]"#);
+    }
+}
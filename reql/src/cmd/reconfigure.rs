@@ -1,12 +1,141 @@
+use super::table_create::Replicas;
 use crate::{cmd, Command};
 use ql2::term::TermType;
+use reql_macros::CommandOptions;
+use serde::{Serialize, Serializer};
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, CommandOptions, Default, PartialEq)]
+#[non_exhaustive]
+pub struct Options {
+    pub shards: Option<u8>,
+    pub replicas: Option<Replicas>,
+    /// Only report the changes that would be made, without making them
+    pub dry_run: Option<bool>,
+    /// Recover a table which lost a majority of its replicas
+    ///
+    /// `shards` and `replicas` are ignored when this is set.
+    pub emergency_repair: Option<EmergencyRepair>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum EmergencyRepair {
+    /// Make the remaining replicas authoritative, which may lose writes
+    UnsafeRollback,
+    /// Like `UnsafeRollback`, but also erase shards with no replicas left
+    UnsafeRollbackOrErase,
+}
+
+impl Serialize for Options {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        #[derive(Serialize)]
+        struct InnerOptions<'a> {
+            #[serde(skip_serializing_if = "Option::is_none")]
+            shards: Option<u8>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            replicas: Option<InnerReplicas<'a>>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            primary_replica_tag: Option<&'a Cow<'static, str>>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            dry_run: Option<bool>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            emergency_repair: Option<EmergencyRepair>,
+        }
+
+        #[derive(Serialize)]
+        #[serde(untagged)]
+        enum InnerReplicas<'a> {
+            Int(u8),
+            Map(&'a HashMap<Cow<'static, str>, u8>),
+        }
+
+        let (replicas, primary_replica_tag) = match &self.replicas {
+            Some(Replicas::Int(i)) => (Some(InnerReplicas::Int(*i)), None),
+            Some(Replicas::Map {
+                replicas,
+                primary_replica_tag,
+            }) => (
+                Some(InnerReplicas::Map(replicas)),
+                Some(primary_replica_tag),
+            ),
+            None => (None, None),
+        };
+
+        let opts = InnerOptions {
+            replicas,
+            primary_replica_tag,
+            shards: self.shards,
+            dry_run: self.dry_run,
+            emergency_repair: self.emergency_repair,
+        };
+
+        opts.serialize(serializer)
+    }
+}
 
 pub trait Arg {
-    fn arg(self) -> cmd::Arg<()>;
+    fn arg(self) -> cmd::Arg<Options>;
 }
 
 impl Arg for Command {
-    fn arg(self) -> cmd::Arg<()> {
+    fn arg(self) -> cmd::Arg<Options> {
         Self::new(TermType::Reconfigure).with_arg(self).into_arg()
     }
 }
+
+impl Arg for Options {
+    fn arg(self) -> cmd::Arg<Options> {
+        Command::new(TermType::Reconfigure)
+            .into_arg()
+            .with_opts(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Options;
+    use crate::cmd::table_create::Replicas;
+    use crate::{cmd, r};
+    use serde_json::{json, Value};
+    use std::collections::HashMap;
+
+    #[test]
+    fn r_table_reconfigure() {
+        let opts = Options::new()
+            .shards(2)
+            .replicas(Replicas::Int(3))
+            .dry_run(true);
+        let query = r.table("foo").reconfigure(opts);
+        let serialised: Value = serde_json::from_str(&cmd::serialise(&query)).unwrap();
+        let expected = json!([
+            176,
+            [[15, ["foo"]]],
+            {"shards": 2, "replicas": 3, "dry_run": true}
+        ]);
+        assert_eq!(serialised, expected);
+    }
+
+    #[test]
+    fn r_table_reconfigure_tagged_replicas() {
+        let mut replicas = HashMap::new();
+        replicas.insert("us_east".into(), 2);
+        let opts = Options::new().shards(1).replicas(Replicas::Map {
+            replicas,
+            primary_replica_tag: "us_east".into(),
+        });
+        let query = r.table("foo").reconfigure(opts);
+        let serialised: Value = serde_json::from_str(&cmd::serialise(&query)).unwrap();
+        let expected = json!([
+            176,
+            [[15, ["foo"]]],
+            {"shards": 1, "replicas": {"us_east": 2}, "primary_replica_tag": "us_east"}
+        ]);
+        assert_eq!(serialised, expected);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{cmd, Command};
+use crate::{cmd, Command, Func};
 use ql2::term::TermType;
 
 pub trait Arg {
@@ -10,3 +10,32 @@ impl Arg for Command {
         Self::new(TermType::Reduce).with_arg(self).into_arg()
     }
 }
+
+impl Arg for Func {
+    fn arg(self) -> cmd::Arg<()> {
+        let Func(func) = self;
+        func.arg()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{self as reql, cmd, func, r};
+    use serde_json::{json, Value};
+
+    #[test]
+    fn r_expr_reduce_sum() {
+        let query = r.expr([1, 2, 3]).reduce(func!(|acc, num| acc + num));
+        let serialised: Value = serde_json::from_str(&cmd::serialise(&query)).unwrap();
+        let vars = &serialised[1][1][1][0][1];
+        let (acc, num) = (vars[0].clone(), vars[1].clone());
+        let expected = json!([
+            37,
+            [
+                [2, [1, 2, 3]],
+                [69, [[2, [acc, num]], [24, [[10, [acc]], [10, [num]]]]]]
+            ]
+        ]);
+        assert_eq!(serialised, expected);
+    }
+}
@@ -13,6 +13,10 @@ pub struct Options {
     pub durability: Option<Durability>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub return_changes: Option<ReturnChanges>,
+    /// Allow the server to run functions that cannot be run atomically,
+    /// such as ones using `r.js` or reading other documents
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub non_atomic: Option<bool>,
 }
 
 pub trait Arg {
@@ -10,3 +10,29 @@ impl Arg for Command {
         Self::new(TermType::Round).with_arg(self).into_arg()
     }
 }
+
+impl Arg for f64 {
+    fn arg(self) -> cmd::Arg<()> {
+        Command::from_json(self).arg()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{cmd, r};
+
+    #[test]
+    fn r_round() {
+        let serialised = cmd::serialise(&r.round(12.345));
+        assert_eq!(serialised, "[185,[12.345]]");
+        let query = (r.expr(12.5) * 2).round();
+        let serialised = cmd::serialise(&query);
+        assert_eq!(serialised, "[185,[[26,[12.5,2]]]]");
+    }
+
+    #[test]
+    fn r_floor_and_ceil() {
+        assert_eq!(cmd::serialise(&r.floor(-12.5)), "[183,[-12.5]]");
+        assert_eq!(cmd::serialise(&r.expr(12.5).ceil()), "[184,[12.5]]");
+    }
+}
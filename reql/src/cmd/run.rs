@@ -1,5 +1,75 @@
+//! Run a query on a connection, returning a stream of results
+//!
+//! The stream yields one item per row. Results the server sends in
+//! several batches are fetched transparently, with the next batch
+//! requested only once the current one has been consumed, so the full
+//! set of stream adapters from `futures` can be used on it. A runtime
+//! error reported by the server surfaces as an `Err` item, after which
+//! the stream ends.
+//!
+//! Each row is deserialised into the type requested from `run`. A row
+//! that does not fit that type is reported as
+//! [Driver::Deserialize](crate::err::Driver::Deserialize), along with the
+//! offending value. To fetch a single document, such as the result of
+//! [get](super::get), take the first item of the stream.
+//!
+//! Dropping the stream before it has been exhausted tells the server to
+//! stop the query and free the results it was holding for it.
+//!
+//! # Examples
+//!
+//! Process each row as it arrives.
+//!
+//! ```
+//! use futures::TryStreamExt;
+//! use serde_json::Value;
+//!
+//! # async fn example() -> reql::Result<()> {
+//! # let session = reql::r.connect(()).await?;
+//! let mut query = reql::r.table("posts").run::<_, Value>(&session);
+//! while let Some(post) = query.try_next().await? {
+//!     println!("{}", post);
+//! }
+//! # Ok(()) }
+//! ```
+//!
+//! Fetch a single document into your own type.
+//!
+//! ```
+//! use futures::TryStreamExt;
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize)]
+//! struct Post {
+//!     title: String,
+//! }
+//!
+//! # async fn example() -> reql::Result<()> {
+//! # let session = reql::r.connect(()).await?;
+//! let post: Option<Post> = reql::r.table("posts").get(1).run(&session).try_next().await?;
+//! # Ok(()) }
+//! ```
+//!
+//! Only fetch the first ten rows; the rest of the query is stopped when
+//! the stream is dropped.
+//!
+//! ```
+//! use futures::{StreamExt, TryStreamExt};
+//! use serde_json::Value;
+//!
+//! # async fn example() -> reql::Result<()> {
+//! # let session = reql::r.connect(()).await?;
+//! let posts: Vec<Value> = reql::r
+//!     .table("posts")
+//!     .run(&session)
+//!     .take(10)
+//!     .try_collect()
+//!     .await?;
+//! # Ok(()) }
+//! ```
+
 use super::args::Args;
-use super::connect::DEFAULT_DB;
+use super::connect::{with_timeout, Transport, DEFAULT_DB};
 use crate::cmd::{Durability, ReadMode};
 use crate::proto::{Payload, Query};
 use crate::{err, r, Command, Connection, Result, Session};
@@ -9,19 +79,26 @@ use futures::stream::{Stream, StreamExt};
 use log::trace;
 use ql2::query::QueryType;
 use ql2::response::{ErrorType, ResponseType};
+use ql2::term::TermType;
 use reql_macros::CommandOptions;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{json, Value};
 use std::borrow::Cow;
 use std::str;
-use std::sync::atomic::Ordering;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
 
 const DATA_SIZE: usize = 4;
 const TOKEN_SIZE: usize = 8;
 const HEADER_SIZE: usize = DATA_SIZE + TOKEN_SIZE;
+// Response bodies are read into a buffer kept by the session, which is
+// only given back to the allocator after an unusually large response
+const READ_BUF_RETAINED: usize = 1024 * 1024;
 
 #[derive(Deserialize, Debug)]
+#[allow(dead_code)]
 pub(crate) struct Response {
     t: i32,
     e: Option<i32>,
@@ -49,10 +126,17 @@ impl Response {
 )]
 #[non_exhaustive]
 pub struct Options {
+    /// How up to date the data read by this query has to be
+    ///
+    /// Overrides the `read_mode` the session was connected with.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub read_mode: Option<ReadMode>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub time_format: Option<Format>,
+    /// Return a profile of the query's execution along with its result
+    ///
+    /// The result is then yielded as a single
+    /// [Profiled](crate::types::Profiled) row.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub profile: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -60,9 +144,32 @@ pub struct Options {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub group_format: Option<Format>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub binary_format: Option<Format>,
+    /// The largest array the server will build in memory, in elements
+    ///
+    /// The server's default is 100,000. A query that would build a bigger
+    /// array, for example by coercing a large table to an `"array"`,
+    /// fails with a [ResourceLimit](crate::err::Runtime::ResourceLimit)
+    /// error instead of sending it back.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub array_limit: Option<u64>,
+    /// Send the query without waiting for the server to reply
+    ///
+    /// The query is written to the connection and the stream ends
+    /// straight away, without yielding anything, so writes such as log
+    /// inserts cost no round trip. Use
+    /// [noreply_wait](crate::Session::noreply_wait) to wait until the
+    /// server has processed them.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub noreply: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub db: Option<Db>,
+    /// The maximum number of rows the server sends in a single batch
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_batch_rows: Option<u64>,
+    /// The maximum size of a single batch in bytes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_batch_bytes: Option<u64>,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Eq, PartialEq, Ord, PartialOrd, Hash)]
@@ -77,11 +184,19 @@ pub enum Format {
 pub struct Db(pub Cow<'static, str>);
 
 impl Options {
-    async fn default_db(self, session: &Session) -> Options {
+    // Fills in the options the session was connected with that the query
+    // doesn't set itself
+    async fn session_defaults(self, session: &Session) -> Options {
+        let opts = self.default_read_mode(session.inner.read_mode);
         let session_db = session.inner.db.lock().await;
-        if self.db.is_none() && *session_db != DEFAULT_DB {
-            return self.db(&*session_db);
+        if opts.db.is_none() && *session_db != DEFAULT_DB {
+            return opts.db(&*session_db);
         }
+        opts
+    }
+
+    fn default_read_mode(mut self, read_mode: Option<ReadMode>) -> Options {
+        self.read_mode = self.read_mode.or(read_mode);
         self
     }
 }
@@ -133,25 +248,106 @@ impl Arg for Args<(&mut Session, Options)> {
 }
 
 pub(crate) fn new<A, T>(query: Command, arg: A) -> impl Stream<Item = Result<T>>
+where
+    A: Arg,
+    T: Unpin + DeserializeOwned,
+{
+    start(query, arg, false)
+}
+
+// Writes all the queries to the server before reading any response, then
+// hands each query's stream its connection to read the response from
+pub(crate) async fn many<T>(
+    session: &Session,
+    queries: Vec<Command>,
+) -> Result<Vec<impl Stream<Item = Result<T>>>>
+where
+    T: Unpin + DeserializeOwned,
+{
+    let opts = Options::default().session_defaults(session).await;
+    let mut conns = Vec::with_capacity(queries.len());
+    let mut buf = Vec::new();
+    for query in &queries {
+        let conn = session.connection()?;
+        let payload = Payload(QueryType::Start, Some(Query(query)), opts.clone());
+        trace!(
+            "queueing query; token: {}, payload: {}",
+            conn.token,
+            payload
+        );
+        buf.extend_from_slice(&payload.encode(conn.token)?);
+        conns.push(conn);
+    }
+    if let Some(conn) = conns.first() {
+        let tokens: Vec<_> = conns.iter().map(|conn| conn.token).collect();
+        conn.submit_many(&buf, &tokens).await;
+    }
+    let streams = queries
+        .into_iter()
+        .zip(conns)
+        .map(|(query, conn)| Box::pin(start(query, conn, true)))
+        .collect();
+    Ok(streams)
+}
+
+// `started` is set when the query has already been sent, so its first
+// response only needs to be read
+fn start<A, T>(query: Command, arg: A, mut started: bool) -> impl Stream<Item = Result<T>>
 where
     A: Arg,
     T: Unpin + DeserializeOwned,
 {
     try_stream! {
         let (mut conn, mut opts) = arg.into_run_opts()?;
-        opts = opts.default_db(&conn.session).await;
+        check_server(&query, conn.session.server_version())?;
+        opts = opts.session_defaults(&conn.session).await;
         let change_feed = query.change_feed();
         if change_feed {
             conn.session.inner.mark_change_feed();
         }
         let noreply = opts.noreply.unwrap_or_default();
+        // Change feeds never end, so they can't be yielded with a profile
+        let profile = opts.profile.unwrap_or_default() && !change_feed;
+        // A profiled sequence sent in several batches is collected, to be
+        // yielded along with the profile once the last batch arrives
+        let mut profiled_rows = Vec::new();
+        let mut profile_data = None;
         let mut payload = Payload(QueryType::Start, Some(Query(&query)), opts);
+        let mut cursor = Cursor::new(&conn);
         loop {
-            let (response_type, resp) = conn.request(&payload, noreply).await?;
+            if cursor.partial && conn.closed() {
+                // cancelled between batches, the stop has already been sent
+                trace!("query cancelled; token: {}", conn.token);
+                conn.set_closed(false);
+                cursor.set_partial(false);
+                break;
+            }
+            let result = if started {
+                started = false;
+                conn.response().await
+            } else if change_feed && cursor.partial {
+                conn.wait_for_changes(&payload).await
+            } else {
+                conn.request(&payload, noreply).await
+            };
+            cursor.set_partial(false);
+            let (response_type, resp) = result?;
             trace!("yielding response; token: {}", conn.token);
             match response_type {
                 ResponseType::SuccessAtom | ResponseType::SuccessSequence | ResponseType::ServerInfo => {
-                    for val in serde_json::from_value::<Vec<T>>(resp.r)? {
+                    if profile {
+                        let p = resp.p.or_else(|| profile_data.take()).unwrap_or_default();
+                        let value = if profiled_rows.is_empty() {
+                            resp.r
+                        } else {
+                            profiled_rows.extend(rows::<Value>(resp.r)?);
+                            Value::Array(std::mem::take(&mut profiled_rows))
+                        };
+                        let row = profiled::<T>(response_type, value, p)?;
+                        yield row;
+                        break;
+                    }
+                    for val in rows::<T>(resp.r)? {
                         yield val;
                     }
                     break;
@@ -164,14 +360,25 @@ where
                         break;
                     }
                     payload = Payload(QueryType::Continue, None, Default::default());
-                    for val in serde_json::from_value::<Vec<T>>(resp.r)? {
+                    cursor.set_partial(true);
+                    if change_feed {
+                        overflowed(&resp.r)?;
+                    }
+                    if profile {
+                        if resp.p.is_some() {
+                            profile_data = resp.p;
+                        }
+                        profiled_rows.extend(rows::<Value>(resp.r)?);
+                        continue;
+                    }
+                    for val in rows::<T>(resp.r)? {
                         yield val;
                     }
                     continue;
                 }
                 ResponseType::WaitComplete => { break; }
                 typ => {
-                    let msg = error_message(resp.r)?;
+                    let msg = error_message(resp.r, resp.b)?;
                     match typ {
                         // This feed has been closed by conn.close().
                         ResponseType::ClientError if change_feed && msg.contains("not in stream cache") => { break; }
@@ -183,8 +390,165 @@ where
     }
 }
 
+// Write hooks were added in RethinkDB 2.4, older servers would reject
+// them with a less helpful error
+fn check_server(query: &Command, version: &str) -> Result<()> {
+    let hooks = query.uses(TermType::SetWriteHook) || query.uses(TermType::GetWriteHook);
+    if hooks && !at_least(version, (2, 4)) {
+        let msg = format!(
+            "write hooks need RethinkDB 2.4 or later, the server runs {}",
+            version
+        );
+        return Err(err::Driver::Other(msg).into());
+    }
+    Ok(())
+}
+
+fn at_least(version: &str, (major, minor): (u32, u32)) -> bool {
+    let mut parts = version
+        .split(|c: char| !c.is_ascii_digit())
+        .map(str::parse::<u32>);
+    match (parts.next(), parts.next()) {
+        (Some(Ok(x)), Some(Ok(y))) => (x, y) >= (major, minor),
+        // Leave it to the server if the version is not what we expect
+        _ => true,
+    }
+}
+
+// Deserialises each row on its own so that a failure can report the
+// value that caused it
+fn rows<T>(value: Value) -> Result<Vec<T>>
+where
+    T: DeserializeOwned,
+{
+    serde_json::from_value::<Vec<Value>>(value)?
+        .iter()
+        .map(|row| {
+            T::deserialize(row)
+                .map_err(|error| err::Driver::Deserialize(Arc::new(error), row.clone()).into())
+        })
+        .collect()
+}
+
+// A feed whose buffer on the server filled up gets an object with only
+// an `error` in place of the changes skipped
+fn overflowed(value: &Value) -> Result<()> {
+    let rows = match value {
+        Value::Array(rows) => rows,
+        _ => return Ok(()),
+    };
+    for row in rows {
+        let error = row
+            .as_object()
+            .filter(|row| row.len() == 1)
+            .and_then(|row| row.get("error"))
+            .and_then(Value::as_str);
+        if let Some(msg) = error {
+            if msg.starts_with("Changefeed cache over array size limit") {
+                return Err(err::Runtime::ChangefeedOverflow(msg.to_owned().into()).into());
+            }
+        }
+    }
+    Ok(())
+}
+
+// Queries run with the `profile` option get their profile back along
+// with the result, which are yielded together as a single row
+fn profiled<T>(response_type: ResponseType, value: Value, profile: Value) -> Result<T>
+where
+    T: DeserializeOwned,
+{
+    let value = match (response_type, value) {
+        (ResponseType::SuccessAtom, Value::Array(mut vec)) if vec.len() == 1 => vec.remove(0),
+        (_, value) => value,
+    };
+    let row = json!({ "profile": profile, "value": value });
+    T::deserialize(&row)
+        .map_err(|error| err::Driver::Deserialize(Arc::new(error), row.clone()).into())
+}
+
+// Tracks whether the server still holds more results for a query so
+// that dropping the stream early can stop it
+struct Cursor {
+    session: Session,
+    token: u64,
+    partial: bool,
+}
+
+impl Cursor {
+    fn new(conn: &Connection) -> Self {
+        Self {
+            session: conn.session.clone(),
+            token: conn.token,
+            partial: false,
+        }
+    }
+
+    fn set_partial(&mut self, partial: bool) {
+        self.partial = partial;
+    }
+}
+
+impl Drop for Cursor {
+    fn drop(&mut self) {
+        if self.partial {
+            trace!("stream dropped before completion; token: {}", self.token);
+            self.session.inner.stop_later(self.token);
+        }
+    }
+}
+
+/// Cancels a query from another task
+///
+/// Returned by [Connection::handle] for the query run on that
+/// connection.
+#[derive(Debug, Clone)]
+pub struct QueryHandle {
+    session: Session,
+    token: u64,
+    closed: Arc<AtomicBool>,
+}
+
+impl QueryHandle {
+    pub(crate) fn new(conn: &Connection) -> Self {
+        Self {
+            session: conn.session.clone(),
+            token: conn.token,
+            closed: conn.closed.clone(),
+        }
+    }
+
+    /// Sends `STOP` for the query, ending its stream
+    ///
+    /// Rows already received are still yielded, after which the stream
+    /// ends without an error. The session only has one request on the
+    /// wire at a time, so a query waiting for a response, such as a long
+    /// aggregation, is only stopped once that response arrives. Use the
+    /// session's read timeout to bound those instead.
+    pub async fn cancel(&self) -> Result<()> {
+        trace!("cancelling query; token: {}", self.token);
+        self.closed.store(true, Ordering::SeqCst);
+        self.session.inner.stop_later(self.token);
+        // A connection of its own to send the stop on, so the response is
+        // routed to the query's stream
+        let (_, rx) = futures::channel::mpsc::unbounded();
+        let conn = Connection::new(self.session.clone(), rx, self.session.inner.token());
+        let mut stream = self.session.inner.stream.lock().await;
+        let mut pending = Vec::new();
+        let result = async {
+            pending.extend(conn.send_stops(&mut stream).await?);
+            let read_timeout = conn.session.inner.read_timeout;
+            conn.read_responses(&mut stream, &mut pending, read_timeout)
+                .await
+        }
+        .await;
+        conn.fail_pending(result.clone(), pending);
+        result
+    }
+}
+
 impl Payload<'_> {
-    fn encode(&self, token: u64) -> Result<Vec<u8>> {
+    pub(crate) fn encode(&self, token: u64) -> Result<Vec<u8>> {
         let bytes = self.to_bytes()?;
         let data_len = bytes.len();
         let mut buf = Vec::with_capacity(HEADER_SIZE + data_len);
@@ -211,45 +575,181 @@ impl Connection {
         query: &'a Payload<'a>,
         noreply: bool,
     ) -> Result<(ResponseType, Response)> {
-        self.submit(query, noreply).await;
+        let read_timeout = self.session.inner.read_timeout;
+        self.submit(query, noreply, read_timeout).await;
+        self.response().await
+    }
+
+    // A change feed only answers a CONTINUE once there are changes to
+    // send, which may take arbitrarily long, so the read timeout doesn't
+    // apply while waiting for the response
+    async fn wait_for_changes<'a>(
+        &mut self,
+        query: &'a Payload<'a>,
+    ) -> Result<(ResponseType, Response)> {
+        self.submit(query, false, None).await;
+        self.response().await
+    }
+
+    async fn response(&self) -> Result<(ResponseType, Response)> {
         match self.rx.lock().await.next().await {
             Some(resp) => resp,
             None => Ok((ResponseType::SuccessAtom, Response::new())),
         }
     }
 
-    async fn submit<'a>(&self, query: &'a Payload<'a>, noreply: bool) {
-        let mut db_token = self.token;
-        let result = self.exec(query, noreply, &mut db_token).await;
-        self.send_response(db_token, result);
+    async fn submit<'a>(&self, query: &'a Payload<'a>, noreply: bool, wait: Option<Duration>) {
+        let mut pending = vec![self.token];
+        let result = self.exec(query, noreply, wait, &mut pending).await;
+        self.fail_pending(result, pending);
     }
 
     async fn exec<'a>(
         &self,
         query: &'a Payload<'a>,
         noreply: bool,
-        db_token: &mut u64,
-    ) -> Result<(ResponseType, Response)> {
+        wait: Option<Duration>,
+        pending: &mut Vec<u64>,
+    ) -> Result<()> {
         let buf = query.encode(self.token)?;
 
-        let guard = self.session.inner.stream.lock().await;
-        let mut stream = guard.clone();
+        let mut stream = self.session.inner.stream.lock().await;
+        let write_timeout = self.session.inner.write_timeout;
+
+        pending.extend(self.send_stops(&mut stream).await?);
 
         trace!("sending query; token: {}, payload: {}", self.token, query);
-        stream.write_all(&buf).await?;
+        let msg = || format!("sending query; token: {}", self.token);
+        with_timeout(stream.write_all(&buf), write_timeout, msg).await?;
         trace!("query sent; token: {}", self.token);
 
         if noreply {
-            return Ok((ResponseType::SuccessAtom, Response::new()));
+            pending.retain(|token| *token != self.token);
+            self.send_response(self.token, Ok((ResponseType::SuccessAtom, Response::new())));
         }
 
+        self.read_responses(&mut stream, pending, wait).await
+    }
+
+    async fn submit_many(&self, buf: &[u8], tokens: &[u64]) {
+        let mut pending = tokens.to_vec();
+        let result = self.exec_many(buf, &mut pending).await;
+        self.fail_pending(result, pending);
+    }
+
+    async fn exec_many(&self, buf: &[u8], pending: &mut Vec<u64>) -> Result<()> {
+        let mut stream = self.session.inner.stream.lock().await;
+        let write_timeout = self.session.inner.write_timeout;
+
+        let count = pending.len();
+        pending.extend(self.send_stops(&mut stream).await?);
+
+        trace!("sending {} queries; token: {}", count, self.token);
+        let msg = || format!("sending queries; token: {}", self.token);
+        with_timeout(stream.write_all(buf), write_timeout, msg).await?;
+        trace!("queries sent; token: {}", self.token);
+
+        let read_timeout = self.session.inner.read_timeout;
+        self.read_responses(&mut stream, pending, read_timeout)
+            .await
+    }
+
+    // Queries still waiting for a response when writing or reading fails
+    // get the error instead
+    fn fail_pending(&self, result: Result<()>, pending: Vec<u64>) {
+        if let Err(error) = result {
+            if let err::Error::Driver(err::Driver::Timeout(_)) = error {
+                // the stream may be left with a partial frame on it
+                self.session.inner.mark_broken();
+            }
+            for token in pending {
+                self.send_response(token, Err(error.clone()));
+            }
+        }
+    }
+
+    // Queries abandoned half way through are stopped here, while the
+    // caller holds the stream, so it can read their responses too
+    async fn send_stops(&self, stream: &mut Transport) -> Result<Vec<u64>> {
+        let write_timeout = self.session.inner.write_timeout;
+        let stops = self.session.inner.take_pending_stops();
+        for token in &stops {
+            trace!("stopping abandoned query; token: {}", token);
+            let stop = Payload(QueryType::Stop, None, Default::default()).encode(*token)?;
+            let msg = || format!("sending stop; token: {}", token);
+            with_timeout(stream.write_all(&stop), write_timeout, msg).await?;
+        }
+        Ok(stops)
+    }
+
+    // Reads one response for each query just written, in whatever order
+    // the server sends them. A frame for any other token means the stream
+    // is out of step with the queries on it, for example because of a
+    // response left unread earlier, so nothing read from it can be
+    // trusted anymore and the session is marked as broken. `wait` bounds
+    // how long to wait for each response to start arriving.
+    async fn read_responses(
+        &self,
+        stream: &mut Transport,
+        pending: &mut Vec<u64>,
+        wait: Option<Duration>,
+    ) -> Result<()> {
+        // Only ever locked while the stream is, so this never waits
+        let mut buf = self.session.inner.read_buf.lock().await;
+        let result = self.read_frames(stream, pending, &mut buf, wait).await;
+        if buf.capacity() > READ_BUF_RETAINED {
+            *buf = Vec::new();
+        }
+        result
+    }
+
+    async fn read_frames(
+        &self,
+        stream: &mut Transport,
+        pending: &mut Vec<u64>,
+        buf: &mut Vec<u8>,
+        wait: Option<Duration>,
+    ) -> Result<()> {
+        while !pending.is_empty() {
+            let token = self.read_frame(stream, buf, wait).await?;
+            match pending.iter().position(|t| *t == token) {
+                Some(pos) => {
+                    pending.swap_remove(pos);
+                }
+                None => {
+                    trace!(
+                        "unexpected response; token: {}, db_token: {}",
+                        self.token,
+                        token
+                    );
+                    self.session.inner.mark_broken();
+                    return Err(err::Driver::ConnectionBroken.into());
+                }
+            }
+            self.send_response(token, self.parse_response(buf));
+        }
+        Ok(())
+    }
+
+    // Reads the body into `body`, reusing its allocation, and returns the
+    // token of the response. Once the header has arrived the rest of the
+    // frame is always bounded by the read timeout.
+    async fn read_frame(
+        &self,
+        stream: &mut Transport,
+        body: &mut Vec<u8>,
+        wait: Option<Duration>,
+    ) -> Result<u64> {
+        let read_timeout = self.session.inner.read_timeout;
+
         trace!("reading header; token: {}", self.token);
         let mut header = [0u8; HEADER_SIZE];
-        stream.read_exact(&mut header).await?;
+        let msg = || format!("reading header; token: {}", self.token);
+        with_timeout(stream.read_exact(&mut header), wait, msg).await?;
 
         let mut buf = [0u8; TOKEN_SIZE];
         buf.copy_from_slice(&header[..TOKEN_SIZE]);
-        *db_token = {
+        let db_token = {
             let token = u64::from_le_bytes(buf);
             trace!("db_token: {}", token);
             if token > self.session.inner.token.load(Ordering::SeqCst) {
@@ -270,24 +770,30 @@ impl Connection {
         );
 
         trace!("reading body; token: {}", self.token);
-        let mut buf = vec![0u8; len];
-        stream.read_exact(&mut buf).await?;
+        body.clear();
+        body.resize(len, 0);
+        let msg = || format!("reading body; token: {}", self.token);
+        with_timeout(stream.read_exact(body), read_timeout, msg).await?;
 
         trace!(
             "body read; token: {}, db_token: {}, body: {}",
             self.token,
             db_token,
-            super::debug(&buf),
+            super::debug(body),
         );
 
-        let resp = serde_json::from_slice::<Response>(&buf)?;
+        Ok(db_token)
+    }
+
+    fn parse_response(&self, buf: &[u8]) -> Result<(ResponseType, Response)> {
+        let resp = serde_json::from_slice::<Response>(buf)?;
         trace!("response successfully parsed; token: {}", self.token,);
 
         let response_type = ResponseType::from_i32(resp.t)
             .ok_or_else(|| err::Driver::Other(format!("unknown response type `{}`", resp.t)))?;
 
         if let Some(error_type) = resp.e {
-            let msg = error_message(resp.r)?;
+            let msg = error_message(resp.r, resp.b)?;
             return Err(response_error(response_type, Some(error_type), msg));
         }
 
@@ -295,14 +801,25 @@ impl Connection {
     }
 }
 
-fn error_message(response: Value) -> Result<String> {
+fn error_message(response: Value, backtrace: Option<Value>) -> Result<err::Message> {
     let messages = serde_json::from_value::<Vec<String>>(response)?;
-    Ok(messages.join(" "))
+    let backtrace = match backtrace {
+        Some(backtrace) => serde_json::from_value(backtrace)?,
+        None => Default::default(),
+    };
+    Ok(err::Message {
+        text: messages.join(" "),
+        backtrace,
+    })
 }
 
-fn response_error(response_type: ResponseType, error_type: Option<i32>, msg: String) -> err::Error {
+fn response_error(
+    response_type: ResponseType,
+    error_type: Option<i32>,
+    msg: err::Message,
+) -> err::Error {
     match response_type {
-        ResponseType::ClientError => err::Driver::Other(msg).into(),
+        ResponseType::ClientError => err::Driver::Other(msg.to_string()).into(),
         ResponseType::CompileError => err::Error::Compile(msg),
         ResponseType::RuntimeError => match error_type
             .map(ErrorType::from_i32)
@@ -322,3 +839,573 @@ fn response_error(response_type: ResponseType, error_type: Option<i32>, msg: Str
         _ => err::Driver::Other(format!("unexpected response: {}", msg)).into(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Options, Transport, HEADER_SIZE, READ_BUF_RETAINED};
+    use crate::cmd::changes;
+    use crate::cmd::ReadMode;
+    use crate::proto::{Payload, Query};
+    use crate::types::{Change, Profiled, State};
+    use crate::{err, r, Driver, Error, InnerSession, Session};
+    use async_net::TcpStream;
+    use futures::channel::oneshot;
+    use futures::future::{self, Either};
+    use futures::lock::Mutex;
+    use futures::{StreamExt, TryStreamExt};
+    use futures_timer::Delay;
+    use ql2::query::QueryType;
+    use ql2::response::{ErrorType, ResponseType};
+    use serde_json::{json, Value};
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+
+    // A session on a stream that has already been through the handshake
+    async fn session(listener: &TcpListener) -> Session {
+        timed_session(listener, Duration::from_secs(5)).await
+    }
+
+    async fn timed_session(listener: &TcpListener, read_timeout: Duration) -> Session {
+        let stream = TcpStream::connect(listener.local_addr().unwrap())
+            .await
+            .unwrap();
+        let inner = InnerSession {
+            server_version: "2.4.1".to_owned(),
+            db: Mutex::new(super::DEFAULT_DB.into()),
+            socket: stream.clone(),
+            stream: Mutex::new(Transport::Tcp(stream)),
+            read_timeout: Some(read_timeout),
+            write_timeout: None,
+            read_mode: None,
+            channels: Default::default(),
+            pending_stops: Default::default(),
+            read_buf: Default::default(),
+            token: Default::default(),
+            broken: Default::default(),
+            change_feed: Default::default(),
+        };
+        Session {
+            inner: Arc::new(inner),
+        }
+    }
+
+    fn frame(token: u64, body: &str) -> Vec<u8> {
+        let mut buf = token.to_le_bytes().to_vec();
+        buf.extend_from_slice(&(body.len() as u32).to_le_bytes());
+        buf.extend_from_slice(body.as_bytes());
+        buf
+    }
+
+    // Answers each query read from the listener with the next response
+    fn serve(listener: TcpListener, responses: Vec<String>) -> thread::JoinHandle<()> {
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            for body in responses {
+                let mut header = [0u8; HEADER_SIZE];
+                stream.read_exact(&mut header).unwrap();
+                let mut token = [0u8; 8];
+                token.copy_from_slice(&header[..8]);
+                let len = u32::from_le_bytes([header[8], header[9], header[10], header[11]]);
+                stream.read_exact(&mut vec![0u8; len as usize]).unwrap();
+                stream
+                    .write_all(&frame(u64::from_le_bytes(token), &body))
+                    .unwrap();
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn atom_is_a_single_row() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let session = session(&listener).await;
+        let server = serve(
+            listener,
+            vec![
+                r#"{"t":1,"r":[3]}"#.to_owned(),
+                r#"{"t":1,"r":[[1,2,3]]}"#.to_owned(),
+            ],
+        );
+        let count: Vec<u32> = r
+            .expr([1, 2, 3])
+            .count(())
+            .run::<_, u32>(&session)
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(count, [3]);
+        let array: Vec<Vec<u32>> = r.expr([1, 2, 3]).run(&session).try_collect().await.unwrap();
+        assert_eq!(array, [[1, 2, 3]]);
+        server.join().unwrap();
+    }
+
+    #[tokio::test]
+    async fn sequence_is_streamed_row_by_row() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let session = session(&listener).await;
+        let server = serve(
+            listener,
+            vec![
+                r#"{"t":3,"r":[1,2]}"#.to_owned(),
+                r#"{"t":3,"r":[3]}"#.to_owned(),
+                r#"{"t":2,"r":[4]}"#.to_owned(),
+            ],
+        );
+        let rows: Vec<u32> = r
+            .table("numbers")
+            .run::<_, u32>(&session)
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(rows, [1, 2, 3, 4]);
+        server.join().unwrap();
+    }
+
+    #[tokio::test]
+    async fn profiled_sequence_is_collected_across_batches() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let session = session(&listener).await;
+        let server = serve(
+            listener,
+            vec![
+                r#"{"t":3,"r":[1,2],"p":[{"description":"Reading table."}]}"#.to_owned(),
+                r#"{"t":3,"r":[3]}"#.to_owned(),
+                r#"{"t":2,"r":[4]}"#.to_owned(),
+            ],
+        );
+        let opts = Options::new().profile(true);
+        let rows: Vec<Profiled<Vec<u32>>> = r
+            .table("numbers")
+            .run(r.args((&session, opts)))
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].value, [1, 2, 3, 4]);
+        assert_eq!(rows[0].profile[0]["description"], "Reading table.");
+        server.join().unwrap();
+    }
+
+    #[tokio::test]
+    async fn run_many_writes_every_query_before_reading() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let session = session(&listener).await;
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            // fails instead of hanging if the driver waits for a response
+            // before sending the next query
+            stream
+                .set_read_timeout(Some(Duration::from_secs(5)))
+                .unwrap();
+            let mut queries = Vec::new();
+            for _ in 0..3 {
+                let mut header = [0u8; HEADER_SIZE];
+                stream.read_exact(&mut header).unwrap();
+                let mut token = [0u8; 8];
+                token.copy_from_slice(&header[..8]);
+                let len = u32::from_le_bytes([header[8], header[9], header[10], header[11]]);
+                let mut body = vec![0u8; len as usize];
+                stream.read_exact(&mut body).unwrap();
+                let body: Value = serde_json::from_slice(&body).unwrap();
+                queries.push((u64::from_le_bytes(token), body[1].clone()));
+            }
+            // answered in reverse, each response echoing its query
+            for (token, value) in queries.into_iter().rev() {
+                let body = json!({"t": 1, "r": [value]}).to_string();
+                stream.write_all(&frame(token, &body)).unwrap();
+            }
+        });
+        let queries = vec![r.expr(10), r.expr(20), r.expr(30)];
+        let mut results = Vec::new();
+        for mut query in session.run_many::<u32, _>(queries).await.unwrap() {
+            results.push(query.try_next().await.unwrap());
+        }
+        assert_eq!(results, [Some(10), Some(20), Some(30)]);
+        server.join().unwrap();
+    }
+
+    #[tokio::test]
+    async fn cancel_stops_the_query_mid_stream() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let session = session(&listener).await;
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut queries = Vec::new();
+            for body in [r#"{"t":3,"r":[1,2]}"#, r#"{"t":2,"r":[]}"#] {
+                let mut header = [0u8; HEADER_SIZE];
+                stream.read_exact(&mut header).unwrap();
+                let mut token = [0u8; 8];
+                token.copy_from_slice(&header[..8]);
+                let len = u32::from_le_bytes([header[8], header[9], header[10], header[11]]);
+                let mut query = vec![0u8; len as usize];
+                stream.read_exact(&mut query).unwrap();
+                queries.push(String::from_utf8(query).unwrap());
+                stream
+                    .write_all(&frame(u64::from_le_bytes(token), body))
+                    .unwrap();
+            }
+            queries
+        });
+        let conn = session.connection().unwrap();
+        let handle = conn.handle();
+        let mut rows = Box::pin(r.table("numbers").run::<_, u32>(conn));
+        assert_eq!(rows.try_next().await.unwrap(), Some(1));
+        handle.cancel().await.unwrap();
+        let rest: Vec<u32> = rows.try_collect().await.unwrap();
+        assert_eq!(rest, [2]);
+        let queries = server.join().unwrap();
+        assert_eq!(queries[1], "[3]");
+    }
+
+    #[tokio::test]
+    async fn read_buffer_is_reused() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let session = session(&listener).await;
+        let large = format!(r#"{{"t":1,"r":["{}"]}}"#, "a".repeat(READ_BUF_RETAINED));
+        let responses = vec![r#"{"t":1,"r":[1]}"#.to_owned(), large];
+        let server = serve(listener, responses);
+        let one: Option<u32> = r.expr(1).run(&session).try_next().await.unwrap();
+        assert_eq!(one, Some(1));
+        assert!(session.inner.read_buf.lock().await.capacity() > 0);
+        let large: Option<String> = r.expr("a").run(&session).try_next().await.unwrap();
+        assert_eq!(large.map(|s| s.len()), Some(READ_BUF_RETAINED));
+        assert_eq!(session.inner.read_buf.lock().await.capacity(), 0);
+        server.join().unwrap();
+    }
+
+    #[tokio::test]
+    async fn changefeed_reconnects() {
+        let first = TcpListener::bind("127.0.0.1:0").unwrap();
+        let second = TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut sessions = vec![session(&second).await, session(&first).await];
+        // Each server closes the connection after its first response
+        let first = serve(first, vec![r#"{"t":3,"r":[{"new_val":1}]}"#.to_owned()]);
+        let second = serve(second, vec![r#"{"t":3,"r":[{"new_val":2}]}"#.to_owned()]);
+        let query = r.table("numbers").changes(());
+        let feed = changes::reconnecting::<u32, _, _, _>(query, move || {
+            let session = sessions
+                .pop()
+                .ok_or_else(|| Error::from(Driver::ConnectionBroken));
+            async move { session }
+        });
+        let changes: Vec<_> = feed.take(3).try_collect().await.unwrap();
+        assert_eq!(changes[0].new_val, Some(1));
+        assert_eq!(changes[1].state, Some(State::Reconnected));
+        assert_eq!(changes[2].new_val, Some(2));
+        first.join().unwrap();
+        second.join().unwrap();
+    }
+
+    #[tokio::test]
+    async fn changefeed_overflow_is_an_error() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let session = session(&listener).await;
+        let server = serve(
+            listener,
+            vec![
+                r#"{"t":3,"r":[{"new_val":1}]}"#.to_owned(),
+                r#"{"t":3,"r":[{"error":"Changefeed cache over array size limit, skipped 5 elements."},{"new_val":7}]}"#.to_owned(),
+            ],
+        );
+        let opts = changes::Options::new().changefeed_queue_size(1);
+        let mut feed = Box::pin(
+            r.table("numbers")
+                .changes(opts)
+                .run::<_, Change<u32>>(&session),
+        );
+        assert_eq!(feed.try_next().await.unwrap().unwrap().new_val, Some(1));
+        match feed.try_next().await {
+            Err(Error::Runtime(err::Runtime::ChangefeedOverflow(msg))) => {
+                assert!(msg.contains("skipped 5 elements"));
+            }
+            result => panic!("unexpected result: {:?}", result),
+        }
+        server.join().unwrap();
+    }
+
+    #[tokio::test]
+    async fn idle_changefeed_outlives_read_timeout() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let session = timed_session(&listener, Duration::from_millis(100)).await;
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let bodies = [r#"{"t":3,"r":[]}"#, r#"{"t":3,"r":[{"new_val":1}]}"#];
+            for (i, body) in bodies.iter().enumerate() {
+                let mut header = [0u8; HEADER_SIZE];
+                stream.read_exact(&mut header).unwrap();
+                let mut token = [0u8; 8];
+                token.copy_from_slice(&header[..8]);
+                let len = u32::from_le_bytes([header[8], header[9], header[10], header[11]]);
+                stream.read_exact(&mut vec![0u8; len as usize]).unwrap();
+                if i > 0 {
+                    // no changes for a while
+                    thread::sleep(Duration::from_millis(300));
+                }
+                stream
+                    .write_all(&frame(u64::from_le_bytes(token), body))
+                    .unwrap();
+            }
+        });
+        let mut feed = Box::pin(
+            r.table("numbers")
+                .changes(())
+                .run::<_, Change<u32>>(&session),
+        );
+        assert_eq!(feed.try_next().await.unwrap().unwrap().new_val, Some(1));
+        assert!(session.inner.broken().is_ok());
+        server.join().unwrap();
+    }
+
+    #[tokio::test]
+    async fn close_does_not_wait_for_idle_changefeed() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let session = session(&listener).await;
+        let (waiting, waited) = oneshot::channel();
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut header = [0u8; HEADER_SIZE];
+            stream.read_exact(&mut header).unwrap();
+            let mut token = [0u8; 8];
+            token.copy_from_slice(&header[..8]);
+            let len = u32::from_le_bytes([header[8], header[9], header[10], header[11]]);
+            stream.read_exact(&mut vec![0u8; len as usize]).unwrap();
+            stream
+                .write_all(&frame(u64::from_le_bytes(token), r#"{"t":3,"r":[]}"#))
+                .unwrap();
+            // the CONTINUE, which is never answered
+            stream.read_exact(&mut header).unwrap();
+            waiting.send(()).unwrap();
+            // the session shuts down the socket
+            let mut rest = Vec::new();
+            stream.read_to_end(&mut rest).unwrap();
+        });
+        let mut feed = Box::pin(
+            r.table("numbers")
+                .changes(())
+                .run::<_, Change<u32>>(&session),
+        );
+        let close = async {
+            waited.await.unwrap();
+            let close = Box::pin(session.close(()));
+            match future::select(close, Delay::new(Duration::from_secs(5))).await {
+                Either::Left((result, _)) => result.unwrap(),
+                Either::Right(_) => panic!("close waited for the change feed"),
+            }
+        };
+        let (next, ()) = futures::join!(feed.try_next(), close);
+        match next {
+            Err(error) => assert!(error.is_connection_error(), "{:?}", error),
+            result => panic!("unexpected result: {:?}", result),
+        }
+        assert!(session.is_broken());
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn server_versions() {
+        assert!(super::at_least("2.4.1~0bionic", (2, 4)));
+        assert!(super::at_least("3.0.0", (2, 4)));
+        assert!(!super::at_least("2.3.6", (2, 4)));
+        assert!(super::at_least("unknown", (2, 4)));
+    }
+
+    #[test]
+    fn write_hooks_need_a_recent_server() {
+        let query = r.table("comments").get_write_hook();
+        assert!(super::check_server(&query, "2.4.0").is_ok());
+        assert!(super::check_server(&r.table("comments"), "2.3.6").is_ok());
+        match super::check_server(&query, "2.3.6") {
+            Err(Error::Driver(Driver::Other(msg))) => assert!(msg.contains("2.4")),
+            res => panic!("unexpected result: {:?}", res),
+        }
+    }
+
+    #[tokio::test]
+    async fn closed_connection_is_noticed() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let session = session(&listener).await;
+        let (stream, _) = listener.accept().unwrap();
+        assert!(!session.is_broken());
+        drop(stream);
+        // Give the FIN a moment to arrive
+        let mut broken = false;
+        for _ in 0..50 {
+            if session.is_broken() {
+                broken = true;
+                break;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert!(broken);
+    }
+
+    #[tokio::test]
+    async fn leftover_response_breaks_the_session() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let session = session(&listener).await;
+        // An earlier query whose response was never read
+        let abandoned = session.connection().unwrap();
+        let conn = session.connection().unwrap();
+        let (abandoned, token) = (abandoned.token, conn.token);
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut header = [0u8; HEADER_SIZE];
+            stream.read_exact(&mut header).unwrap();
+            let len = u32::from_le_bytes([header[8], header[9], header[10], header[11]]);
+            stream.read_exact(&mut vec![0u8; len as usize]).unwrap();
+            stream
+                .write_all(&frame(abandoned, r#"{"t":1,"r":[100]}"#))
+                .unwrap();
+            stream
+                .write_all(&frame(token, r#"{"t":1,"r":[200]}"#))
+                .unwrap();
+        });
+        let res = r.expr(200).run::<_, u32>(conn).try_next().await;
+        server.join().unwrap();
+        match res {
+            Err(Error::Driver(Driver::ConnectionBroken)) => {}
+            res => panic!("unexpected result: {:?}", res),
+        }
+        assert!(session.is_broken());
+    }
+
+    #[tokio::test]
+    async fn frame_split_across_segments_is_read_whole() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let session = session(&listener).await;
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            stream.set_nodelay(true).unwrap();
+            let mut header = [0u8; HEADER_SIZE];
+            stream.read_exact(&mut header).unwrap();
+            let mut token = [0u8; 8];
+            token.copy_from_slice(&header[..8]);
+            let len = u32::from_le_bytes([header[8], header[9], header[10], header[11]]);
+            stream.read_exact(&mut vec![0u8; len as usize]).unwrap();
+            // Split inside the token, the length and the body
+            let response = frame(u64::from_le_bytes(token), r#"{"t":1,"r":[[1,2,3]]}"#);
+            for chunk in [
+                &response[..5],
+                &response[5..10],
+                &response[10..20],
+                &response[20..],
+            ] {
+                stream.write_all(chunk).unwrap();
+                thread::sleep(Duration::from_millis(20));
+            }
+            stream
+        });
+        let array: Option<Vec<u32>> = r.expr([1, 2, 3]).run(&session).try_next().await.unwrap();
+        assert_eq!(array, Some(vec![1, 2, 3]));
+        // Hold the server end open so a hang-up can't mark the session broken
+        let stream = server.join().unwrap();
+        assert!(!session.is_broken());
+        drop(stream);
+    }
+
+    #[test]
+    fn session_read_mode_is_sent_by_default() {
+        let query = r.table("users");
+        let opts = Options::default().default_read_mode(Some(ReadMode::Outdated));
+        let payload = Payload(QueryType::Start, Some(Query(&query)), opts);
+        let expected = r#"[1,[15,["users"]],{"read_mode":"outdated"}]"#;
+        assert_eq!(payload.to_string(), expected);
+    }
+
+    #[tokio::test]
+    async fn session_db_is_sent_by_default() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let session = session(&listener).await;
+        let query = r.table("users");
+        // Not sent for `test`, which the server defaults to anyway
+        let opts = Options::new().session_defaults(&session).await;
+        let payload = Payload(QueryType::Start, Some(Query(&query)), opts);
+        assert_eq!(payload.to_string(), r#"[1,[15,["users"]],{}]"#);
+        *session.inner.db.lock().await = "marvel".into();
+        let opts = Options::new().session_defaults(&session).await;
+        let payload = Payload(QueryType::Start, Some(Query(&query)), opts);
+        let expected = r#"[1,[15,["users"]],{"db":[14,["marvel"]]}]"#;
+        assert_eq!(payload.to_string(), expected);
+        // A db given to `run` wins over the session's
+        let opts = Options::new().db("dc").session_defaults(&session).await;
+        let payload = Payload(QueryType::Start, Some(Query(&query)), opts);
+        let expected = r#"[1,[15,["users"]],{"db":[14,["dc"]]}]"#;
+        assert_eq!(payload.to_string(), expected);
+    }
+
+    #[test]
+    fn array_limit_is_sent() {
+        let query = r.table("users").coerce_to("array");
+        let opts = Options::new().array_limit(10);
+        let payload = Payload(QueryType::Start, Some(Query(&query)), opts);
+        let expected = r#"[1,[51,[[15,["users"]],"array"]],{"array_limit":10}]"#;
+        assert_eq!(payload.to_string(), expected);
+    }
+
+    #[test]
+    fn query_read_mode_wins() {
+        let opts = Options::new()
+            .read_mode(ReadMode::Majority)
+            .default_read_mode(Some(ReadMode::Outdated));
+        assert_eq!(opts.read_mode, Some(ReadMode::Majority));
+        let opts = Options::default().default_read_mode(None);
+        assert_eq!(opts.read_mode, None);
+    }
+
+    #[test]
+    fn rows_are_deserialised() {
+        let rows = super::rows::<u8>(json!([1, 2, 3])).unwrap();
+        assert_eq!(rows, [1, 2, 3]);
+    }
+
+    #[test]
+    fn profile_is_yielded_with_the_value() {
+        let row = super::profiled::<Value>(
+            ResponseType::SuccessAtom,
+            json!([1]),
+            json!([{"description": "Evaluating datum."}]),
+        )
+        .unwrap();
+        assert_eq!(row["value"], 1);
+        assert_eq!(row["profile"][0]["description"], "Evaluating datum.");
+    }
+
+    #[test]
+    fn runtime_errors_are_classified() {
+        let msg = super::error_message(json!(["No attribute `foo` in object"]), Some(json!([1])))
+            .unwrap();
+        let error = super::response_error(
+            ResponseType::RuntimeError,
+            Some(ErrorType::NonExistence as i32),
+            msg,
+        );
+        match error {
+            Error::Runtime(err::Runtime::NonExistence(msg)) => {
+                assert_eq!(&*msg, "No attribute `foo` in object");
+                assert_eq!(msg.backtrace.0, [err::Frame::Pos(1)]);
+            }
+            error => panic!("unexpected error: {}", error),
+        }
+
+        let error = super::response_error(
+            ResponseType::RuntimeError,
+            Some(ErrorType::OpFailed as i32),
+            "Database `foo` already exists.".to_owned().into(),
+        );
+        assert!(matches!(
+            error,
+            Error::Runtime(err::Runtime::Availability(err::Availability::OpFailed(_)))
+        ));
+    }
+
+    #[test]
+    fn bad_row_is_reported() {
+        let error = super::rows::<u8>(json!([1, "two", 3])).unwrap_err();
+        match error {
+            Error::Driver(err::Driver::Deserialize(_, value)) => assert_eq!(value, json!("two")),
+            error => panic!("unexpected error: {}", error),
+        }
+    }
+}
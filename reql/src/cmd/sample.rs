@@ -10,3 +10,22 @@ impl Arg for Command {
         Self::new(TermType::Sample).with_arg(self).into_arg()
     }
 }
+
+impl Arg for usize {
+    fn arg(self) -> cmd::Arg<()> {
+        Command::from_json(self).arg()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{cmd, r};
+
+    #[test]
+    fn r_table_sample() {
+        let query = r.table("foo").sample(3);
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[81,[[15,["foo"]],3]]"#;
+        assert_eq!(serialised, expected);
+    }
+}
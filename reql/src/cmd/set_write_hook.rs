@@ -1,5 +1,67 @@
-use crate::cmd;
+use crate::{cmd, Command, Func};
+use ql2::term::TermType;
+use serde_json::Value;
 
 pub trait Arg {
     fn arg(self) -> cmd::Arg<()>;
 }
+
+impl Arg for Command {
+    fn arg(self) -> cmd::Arg<()> {
+        Self::new(TermType::SetWriteHook).with_arg(self).into_arg()
+    }
+}
+
+impl Arg for Func {
+    fn arg(self) -> cmd::Arg<()> {
+        let Func(func) = self;
+        func.arg()
+    }
+}
+
+/// `None` removes the write hook of the table
+impl Arg for Option<Func> {
+    fn arg(self) -> cmd::Arg<()> {
+        match self {
+            Some(func) => func.arg(),
+            None => Command::from(Value::Null).arg(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{self as reql, cmd, func, r, Func};
+    use serde_json::{json, Value};
+
+    #[test]
+    fn r_table_set_write_hook() {
+        let query = r
+            .table("comments")
+            .set_write_hook(func!(|_context, _old_val, new_val| new_val));
+        let serialised: Value = serde_json::from_str(&cmd::serialise(&query)).unwrap();
+        let vars = serialised[1][1][1][0][1].clone();
+        let new_val = vars[2].clone();
+        let expected = json!([
+            189,
+            [[15, ["comments"]], [69, [[2, vars], [10, [new_val]]]]]
+        ]);
+        assert_eq!(serialised, expected);
+    }
+
+    #[test]
+    fn r_table_clear_write_hook() {
+        let query = r.table("comments").set_write_hook(None::<Func>);
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[189,[[15,["comments"]],null]]"#;
+        assert_eq!(serialised, expected);
+    }
+
+    #[test]
+    fn r_table_get_write_hook() {
+        let query = r.table("comments").get_write_hook();
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[190,[[15,["comments"]]]]"#;
+        assert_eq!(serialised, expected);
+    }
+}
@@ -1,5 +1,31 @@
-use crate::cmd;
+use crate::{cmd, Command};
+use ql2::term::TermType;
 
 pub trait Arg {
     fn arg(self) -> cmd::Arg<()>;
 }
+
+impl Arg for Command {
+    fn arg(self) -> cmd::Arg<()> {
+        Self::new(TermType::Skip).with_arg(self).into_arg()
+    }
+}
+
+impl Arg for isize {
+    fn arg(self) -> cmd::Arg<()> {
+        Command::from_json(self).arg()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{cmd, r};
+
+    #[test]
+    fn r_table_skip_limit() {
+        let query = r.table("foo").skip(10).limit(5);
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[71,[[70,[[15,["foo"]],10]],5]]"#;
+        assert_eq!(serialised, expected);
+    }
+}
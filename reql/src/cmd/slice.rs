@@ -1,12 +1,102 @@
+use super::args::Args;
+use super::between::Status;
 use crate::{cmd, Command};
 use ql2::term::TermType;
+use reql_macros::CommandOptions;
+use serde::Serialize;
+
+/// Whether the bounds of a slice are included in the result
+///
+/// By default the start of a slice is closed and the end is open.
+#[derive(
+    Debug, Clone, Copy, CommandOptions, Serialize, Default, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[non_exhaustive]
+pub struct Options {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub left_bound: Option<Status>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub right_bound: Option<Status>,
+}
 
 pub trait Arg {
-    fn arg(self) -> cmd::Arg<()>;
+    fn arg(self) -> cmd::Arg<Options>;
 }
 
 impl Arg for Command {
-    fn arg(self) -> cmd::Arg<()> {
+    fn arg(self) -> cmd::Arg<Options> {
         Self::new(TermType::Slice).with_arg(self).into_arg()
     }
 }
+
+impl Arg for isize {
+    fn arg(self) -> cmd::Arg<Options> {
+        Command::from_json(self).arg()
+    }
+}
+
+impl Arg for Args<(Command, Command)> {
+    fn arg(self) -> cmd::Arg<Options> {
+        let Args((start, end)) = self;
+        start.arg().with_arg(end)
+    }
+}
+
+impl Arg for Args<(isize, isize)> {
+    fn arg(self) -> cmd::Arg<Options> {
+        let Args((start, end)) = self;
+        start.arg().with_arg(Command::from_json(end))
+    }
+}
+
+impl Arg for Args<(Command, Options)> {
+    fn arg(self) -> cmd::Arg<Options> {
+        let Args((start, opts)) = self;
+        start.arg().with_opts(opts)
+    }
+}
+
+impl Arg for Args<(isize, Options)> {
+    fn arg(self) -> cmd::Arg<Options> {
+        let Args((start, opts)) = self;
+        start.arg().with_opts(opts)
+    }
+}
+
+impl Arg for Args<(Command, Command, Options)> {
+    fn arg(self) -> cmd::Arg<Options> {
+        let Args((start, end, opts)) = self;
+        Args((start, end)).arg().with_opts(opts)
+    }
+}
+
+impl Arg for Args<(isize, isize, Options)> {
+    fn arg(self) -> cmd::Arg<Options> {
+        let Args((start, end, opts)) = self;
+        Args((start, end)).arg().with_opts(opts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Options;
+    use crate::cmd::between::Status;
+    use crate::{cmd, r};
+
+    #[test]
+    fn r_expr_slice() {
+        let query = r.expr([1, 2, 3, 4]).slice(r.args((1, -1)));
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[30,[[2,[1,2,3,4]],1,-1]]"#;
+        assert_eq!(serialised, expected);
+    }
+
+    #[test]
+    fn r_expr_slice_closed() {
+        let opts = Options::new().right_bound(Status::Closed);
+        let query = r.expr([1, 2, 3, 4]).slice(r.args((1, 2, opts)));
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[30,[[2,[1,2,3,4]],1,2],{"right_bound":"closed"}]"#;
+        assert_eq!(serialised, expected);
+    }
+}
@@ -1,5 +1,7 @@
+use super::args::Args;
 use crate::{cmd, Command};
 use ql2::term::TermType;
+use serde::Serialize;
 
 pub trait Arg {
     fn arg(self) -> cmd::Arg<()>;
@@ -10,3 +12,21 @@ impl Arg for Command {
         Self::new(TermType::SpliceAt).with_arg(self).into_arg()
     }
 }
+
+impl Arg for Args<(Command, Command)> {
+    fn arg(self) -> cmd::Arg<()> {
+        let Args((offset, value)) = self;
+        offset.arg().with_arg(value)
+    }
+}
+
+impl<T> Arg for Args<(i64, T)>
+where
+    T: Serialize,
+{
+    fn arg(self) -> cmd::Arg<()> {
+        let Args((offset, value)) = self;
+        let offset = Command::from_json(offset);
+        Args((offset, Command::from_json(value))).arg()
+    }
+}
@@ -1,5 +1,7 @@
+use super::args::Args;
 use crate::{cmd, Command};
 use ql2::term::TermType;
+use std::borrow::Cow;
 
 pub trait Arg {
     fn arg(self) -> cmd::Arg<()>;
@@ -10,3 +12,80 @@ impl Arg for Command {
         Self::new(TermType::Split).with_arg(self).into_arg()
     }
 }
+
+/// Split on whitespace
+impl Arg for () {
+    fn arg(self) -> cmd::Arg<()> {
+        Command::new(TermType::Split).into_arg()
+    }
+}
+
+impl Arg for &str {
+    fn arg(self) -> cmd::Arg<()> {
+        Command::from_json(self).arg()
+    }
+}
+
+impl Arg for String {
+    fn arg(self) -> cmd::Arg<()> {
+        Command::from_json(self).arg()
+    }
+}
+
+impl Arg for Cow<'static, str> {
+    fn arg(self) -> cmd::Arg<()> {
+        Command::from_json(self).arg()
+    }
+}
+
+/// Split on a separator at most `limit` times
+impl Arg for Args<(Command, Command)> {
+    fn arg(self) -> cmd::Arg<()> {
+        let Args((separator, limit)) = self;
+        separator.arg().with_arg(limit)
+    }
+}
+
+impl<T> Arg for Args<(T, usize)>
+where
+    T: Into<String>,
+{
+    fn arg(self) -> cmd::Arg<()> {
+        let Args((separator, limit)) = self;
+        let separator = Command::from_json(separator.into());
+        Args((separator, Command::from_json(limit))).arg()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{cmd, r};
+
+    #[test]
+    fn r_expr_split_whitespace() {
+        let query = r.expr("foo  bar bax").split(());
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[149,["foo  bar bax"]]"#;
+        assert_eq!(serialised, expected);
+    }
+
+    #[test]
+    fn r_expr_split_separator() {
+        let query = r.expr("12,37,,22,").split(",");
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[149,["12,37,,22,",","]]"#;
+        assert_eq!(serialised, expected);
+        assert_eq!(
+            cmd::serialise(&r.expr("12,37,,22,").split(String::from(","))),
+            expected
+        );
+    }
+
+    #[test]
+    fn r_expr_split_limit() {
+        let query = r.expr("12,37,,22,").split(r.args((",", 3)));
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[149,["12,37,,22,",",",3]]"#;
+        assert_eq!(serialised, expected);
+    }
+}
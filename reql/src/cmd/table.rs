@@ -60,6 +60,8 @@ where
 
 #[cfg(test)]
 mod tests {
+    use super::Options;
+    use crate::cmd::ReadMode;
     use crate::{cmd, r};
 
     #[test]
@@ -77,4 +79,13 @@ mod tests {
         let expected = r#"[15,[[14,["foo"]],"bar"]]"#;
         assert_eq!(serialised, expected);
     }
+
+    #[test]
+    fn r_db_table_with_options() {
+        let opts = Options::new().read_mode(ReadMode::Outdated);
+        let query = r.db("prod").table(r.args(("users", opts)));
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[15,[[14,["prod"]],"users"],{"read_mode":"outdated"}]"#;
+        assert_eq!(serialised, expected);
+    }
 }
@@ -111,3 +111,27 @@ where
         name.arg().with_opts(options)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Options;
+    use crate::cmd::Durability;
+    use crate::{cmd, r};
+    use serde_json::{json, Value};
+
+    #[test]
+    fn r_db_table_create_with_options() {
+        let opts = Options::new()
+            .primary_key("email")
+            .durability(Durability::Soft)
+            .shards(2);
+        let query = r.db("foo").table_create(r.args(("bar", opts)));
+        let serialised: Value = serde_json::from_str(&cmd::serialise(&query)).unwrap();
+        let expected = json!([
+            60,
+            [[14, ["foo"]], "bar"],
+            {"primary_key": "email", "durability": "soft", "shards": 2}
+        ]);
+        assert_eq!(serialised, expected);
+    }
+}
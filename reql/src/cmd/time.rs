@@ -1,3 +1,4 @@
+use super::args::Args;
 use crate::{cmd, Command};
 use ql2::term::TermType;
 
@@ -10,3 +11,74 @@ impl Arg for Command {
         Self::new(TermType::Time).with_arg(self).into_arg()
     }
 }
+
+/// Any combination of year, month, day, and optionally hour, minute and
+/// second, followed by a timezone
+impl<const N: usize> Arg for Args<[Command; N]> {
+    fn arg(self) -> cmd::Arg<()> {
+        let Args(arr) = self;
+        let mut query = Command::new(TermType::Time);
+        for arg in IntoIterator::into_iter(arr) {
+            query = query.with_arg(arg);
+        }
+        query.into_arg()
+    }
+}
+
+/// Year, month, day and timezone, such as `"Z"` or `"+02:00"`
+impl<T> Arg for Args<(i32, u8, u8, T)>
+where
+    T: Into<String>,
+{
+    fn arg(self) -> cmd::Arg<()> {
+        let Args((year, month, day, timezone)) = self;
+        Args([
+            Command::from_json(year),
+            Command::from_json(month),
+            Command::from_json(day),
+            Command::from_json(timezone.into()),
+        ])
+        .arg()
+    }
+}
+
+/// Year, month, day, hour, minute, second and timezone
+impl<T> Arg for Args<(i32, u8, u8, u8, u8, f64, T)>
+where
+    T: Into<String>,
+{
+    fn arg(self) -> cmd::Arg<()> {
+        let Args((year, month, day, hour, minute, second, timezone)) = self;
+        Args([
+            Command::from_json(year),
+            Command::from_json(month),
+            Command::from_json(day),
+            Command::from_json(hour),
+            Command::from_json(minute),
+            Command::from_json(second),
+            Command::from_json(timezone.into()),
+        ])
+        .arg()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{cmd, r};
+
+    #[test]
+    fn r_time_date() {
+        let query = r.time(r.args((1986, 11, 3, "Z")));
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[136,[1986,11,3,"Z"]]"#;
+        assert_eq!(serialised, expected);
+    }
+
+    #[test]
+    fn r_time_date_time() {
+        let query = r.time(r.args((1986, 11, 3, 12, 30, 15.5, "+02:00")));
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[136,[1986,11,3,12,30,15.5,"+02:00"]]"#;
+        assert_eq!(serialised, expected);
+    }
+}
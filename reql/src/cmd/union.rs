@@ -1,12 +1,78 @@
+use super::args::Args;
 use crate::{cmd, Command};
 use ql2::term::TermType;
+use reql_macros::CommandOptions;
+use serde::Serialize;
+
+#[derive(
+    Debug, Clone, Copy, CommandOptions, Serialize, Default, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[non_exhaustive]
+pub struct Options {
+    /// Mix the sequences as their elements come in, which is the
+    /// default, rather than returning them one after the other
+    ///
+    /// Changefeeds can only be unioned while interleaving.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interleave: Option<bool>,
+}
 
 pub trait Arg {
-    fn arg(self) -> cmd::Arg<()>;
+    fn arg(self) -> cmd::Arg<Options>;
 }
 
 impl Arg for Command {
-    fn arg(self) -> cmd::Arg<()> {
+    fn arg(self) -> cmd::Arg<Options> {
         Self::new(TermType::Union).with_arg(self).into_arg()
     }
 }
+
+impl<const N: usize> Arg for Args<[Command; N]> {
+    fn arg(self) -> cmd::Arg<Options> {
+        let Args(arr) = self;
+        let mut query = Command::new(TermType::Union);
+        for arg in IntoIterator::into_iter(arr) {
+            query = query.with_arg(arg);
+        }
+        query.into_arg()
+    }
+}
+
+impl Arg for Args<(Command, Options)> {
+    fn arg(self) -> cmd::Arg<Options> {
+        let Args((arg, opts)) = self;
+        arg.arg().with_opts(opts)
+    }
+}
+
+impl<const N: usize> Arg for Args<([Command; N], Options)> {
+    fn arg(self) -> cmd::Arg<Options> {
+        let Args((arr, opts)) = self;
+        Args(arr).arg().with_opts(opts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Options;
+    use crate::{cmd, r};
+
+    #[test]
+    fn r_table_union() {
+        let query = r
+            .table("marvel")
+            .union(r.args([r.table("dc"), r.table("image")]));
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[44,[[15,["marvel"]],[15,["dc"]],[15,["image"]]]]"#;
+        assert_eq!(serialised, expected);
+    }
+
+    #[test]
+    fn r_union_ordered() {
+        let opts = Options::new().interleave(false);
+        let query = r.union(r.args(([r.expr([1, 2]), r.expr([3])], opts)));
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[44,[[2,[1,2]],[2,[3]]],{"interleave":false}]"#;
+        assert_eq!(serialised, expected);
+    }
+}
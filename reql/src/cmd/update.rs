@@ -13,6 +13,10 @@ pub struct Options {
     pub durability: Option<Durability>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub return_changes: Option<ReturnChanges>,
+    /// Allow the server to run functions that cannot be run atomically,
+    /// such as ones using `r.js` or reading other documents
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub non_atomic: Option<bool>,
 }
 
 pub trait Arg {
@@ -71,3 +75,55 @@ impl Arg for Args<(Func, Options)> {
         func.arg().with_opts(opts)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Options;
+    use crate::cmd::ReturnChanges;
+    use crate::{cmd, r, Command};
+    use serde_json::{json, Value};
+
+    #[test]
+    fn r_table_get_update_with_options() {
+        let opts = Options::new()
+            .non_atomic(true)
+            .return_changes(ReturnChanges::Bool(true));
+        let query = r
+            .table("foo")
+            .get(1)
+            .update(r.args((json!({"bar": 2}), opts)));
+        let serialised: Value = serde_json::from_str(&cmd::serialise(&query)).unwrap();
+        let expected = json!([
+            53,
+            [[16, [[15, ["foo"]], 1]], {"bar": 2}],
+            {"non_atomic": true, "return_changes": true}
+        ]);
+        assert_eq!(serialised, expected);
+    }
+
+    #[test]
+    fn r_table_get_update_with_terms() {
+        let doc = Command::from([("title", r.expr("Hello")), ("updated", r.now())]);
+        let query = r.table("foo").get(1).update(doc);
+        let serialised: Value = serde_json::from_str(&cmd::serialise(&query)).unwrap();
+        let expected = json!([
+            53,
+            [[16, [[15, ["foo"]], 1]], [3, [], {"title": "Hello", "updated": [103, []]}]]
+        ]);
+        assert_eq!(serialised, expected);
+    }
+
+    #[test]
+    fn object_from_iterator() {
+        let fields = vec![
+            ("a".to_owned(), r.expr(1)),
+            ("b".to_owned(), r.expr([1, 2])),
+        ];
+        let query: Command = fields.into_iter().collect();
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[3,[],{"a":1,"b":[2,[1,2]]}]"#;
+        assert_eq!(serialised, expected);
+        let empty: Command = Vec::<(&str, Command)>::new().into_iter().collect();
+        assert_eq!(cmd::serialise(&empty), "[3,[]]");
+    }
+}
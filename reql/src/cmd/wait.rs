@@ -1,12 +1,69 @@
 use crate::{cmd, Command};
 use ql2::term::TermType;
+use reql_macros::CommandOptions;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, CommandOptions, Serialize, Default, PartialEq, PartialOrd)]
+#[non_exhaustive]
+pub struct Options {
+    /// The state to wait for, `all_replicas_ready` by default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wait_for: Option<WaitFor>,
+    /// How many seconds to wait before failing, with no limit by default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum WaitFor {
+    ReadyForOutdatedReads,
+    ReadyForReads,
+    ReadyForWrites,
+    AllReplicasReady,
+}
 
 pub trait Arg {
-    fn arg(self) -> cmd::Arg<()>;
+    fn arg(self) -> cmd::Arg<Options>;
 }
 
 impl Arg for Command {
-    fn arg(self) -> cmd::Arg<()> {
+    fn arg(self) -> cmd::Arg<Options> {
         Self::new(TermType::Wait).with_arg(self).into_arg()
     }
 }
+
+impl Arg for () {
+    fn arg(self) -> cmd::Arg<Options> {
+        Command::new(TermType::Wait).into_arg()
+    }
+}
+
+impl Arg for Options {
+    fn arg(self) -> cmd::Arg<Options> {
+        ().arg().with_opts(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Options, WaitFor};
+    use crate::{cmd, r};
+    use serde_json::{json, Value};
+
+    #[test]
+    fn r_table_wait() {
+        let opts = Options::new()
+            .wait_for(WaitFor::ReadyForWrites)
+            .timeout(5.0);
+        let query = r.table("foo").wait(opts);
+        let serialised: Value = serde_json::from_str(&cmd::serialise(&query)).unwrap();
+        let expected = json!([
+            177,
+            [[15, ["foo"]]],
+            {"wait_for": "ready_for_writes", "timeout": 5.0}
+        ]);
+        assert_eq!(serialised, expected);
+    }
+}
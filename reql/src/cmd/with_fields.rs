@@ -1,5 +1,39 @@
-use crate::cmd;
+use super::args::Args;
+use crate::{cmd, Command};
+use ql2::term::TermType;
+use serde::Serialize;
 
 pub trait Arg {
     fn arg(self) -> cmd::Arg<()>;
 }
+
+impl Arg for Command {
+    fn arg(self) -> cmd::Arg<()> {
+        Self::new(TermType::WithFields).with_arg(self).into_arg()
+    }
+}
+
+/// A field name, or an object selecting nested fields such as
+/// `json!({"address": ["city", "zip"]})`
+impl<T> Arg for T
+where
+    T: Serialize,
+{
+    fn arg(self) -> cmd::Arg<()> {
+        Command::from_json(self).arg()
+    }
+}
+
+impl<T, const N: usize> Arg for Args<[T; N]>
+where
+    T: Serialize,
+{
+    fn arg(self) -> cmd::Arg<()> {
+        let Args(fields) = self;
+        let mut query = Command::new(TermType::WithFields);
+        for field in IntoIterator::into_iter(fields) {
+            query = query.with_arg(Command::from_json(field));
+        }
+        query.into_arg()
+    }
+}
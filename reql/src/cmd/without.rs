@@ -1,5 +1,7 @@
+use super::args::Args;
 use crate::{cmd, Command};
 use ql2::term::TermType;
+use serde::Serialize;
 
 pub trait Arg {
     fn arg(self) -> cmd::Arg<()>;
@@ -10,3 +12,42 @@ impl Arg for Command {
         Self::new(TermType::Without).with_arg(self).into_arg()
     }
 }
+
+/// A field name, or an object selecting nested fields such as
+/// `json!({"address": ["city", "zip"]})`
+impl<T> Arg for T
+where
+    T: Serialize,
+{
+    fn arg(self) -> cmd::Arg<()> {
+        Command::from_json(self).arg()
+    }
+}
+
+impl<T, const N: usize> Arg for Args<[T; N]>
+where
+    T: Serialize,
+{
+    fn arg(self) -> cmd::Arg<()> {
+        let Args(fields) = self;
+        let mut query = Command::new(TermType::Without);
+        for field in IntoIterator::into_iter(fields) {
+            query = query.with_arg(Command::from_json(field));
+        }
+        query.into_arg()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{cmd, r};
+    use serde_json::json;
+
+    #[test]
+    fn r_table_without_nested() {
+        let query = r.table("foo").without(json!({"address": "zip"}));
+        let serialised = cmd::serialise(&query);
+        let expected = r#"[34,[[15,["foo"]],{"address":"zip"}]]"#;
+        assert_eq!(serialised, expected);
+    }
+}
@@ -1,16 +1,49 @@
+use serde::Deserialize;
+use std::ops::Deref;
 use std::sync::Arc;
 use std::{error, fmt, io};
 
 /// The most generic error message in ReQL
 #[derive(Debug, Clone)]
 pub enum Error {
-    Compile(String),
+    Compile(Message),
     Runtime(Runtime),
     Driver(Driver),
 }
 
 impl error::Error for Error {}
 
+impl Error {
+    /// Whether the connection to the server failed, rather than the query
+    ///
+    /// The query may or may not have reached the server before it did.
+    /// Timeouts don't count, as the server may just be slow to answer.
+    pub fn is_connection_error(&self) -> bool {
+        matches!(
+            self,
+            Self::Driver(Driver::ConnectionBroken | Driver::Io(..))
+        )
+    }
+
+    /// The message sent by the server, for compile and runtime errors
+    pub fn message(&self) -> Option<&Message> {
+        match self {
+            Self::Compile(msg) => Some(msg),
+            Self::Runtime(error) => Some(error.message()),
+            Self::Driver(_) => None,
+        }
+    }
+
+    /// The part of the query the server says failed, if it sent one
+    ///
+    /// This is also part of the error's `Display` output.
+    pub fn backtrace(&self) -> Option<&Backtrace> {
+        self.message()
+            .map(|msg| &msg.backtrace)
+            .filter(|backtrace| !backtrace.0.is_empty())
+    }
+}
+
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -28,13 +61,42 @@ impl fmt::Display for Error {
 #[derive(Debug, Clone)]
 pub enum Runtime {
     /// The query contains a logical impossibility, such as adding a number to a string.
-    QueryLogic(String),
-    NonExistence(String),
-    ResourceLimit(String),
-    User(String),
-    Internal(String),
+    QueryLogic(Message),
+    /// A field or document the query relies on does not exist, such as
+    /// reading a missing field with `bracket`
+    NonExistence(Message),
+    /// The query ran into a resource limit, such as the `array_limit`
+    ResourceLimit(Message),
+    /// An error raised by the query itself, using `r.error`
+    User(Message),
+    /// The query triggered an internal error on the server
+    Internal(Message),
+    /// A server in the cluster is unavailable
     Availability(Availability),
-    Permission(String),
+    /// The user the query runs as lacks the permissions it needs
+    Permission(Message),
+    /// A changefeed was read more slowly than changes came in, so the
+    /// server filled its `changefeed_queue_size` buffer and skipped some
+    ///
+    /// Restarting the feed with `squash` set, or with a bigger queue,
+    /// keeps up with more changes.
+    ChangefeedOverflow(Message),
+}
+
+impl Runtime {
+    /// The message sent by the server
+    pub fn message(&self) -> &Message {
+        match self {
+            Self::QueryLogic(msg)
+            | Self::NonExistence(msg)
+            | Self::ResourceLimit(msg)
+            | Self::User(msg)
+            | Self::Internal(msg)
+            | Self::Permission(msg)
+            | Self::ChangefeedOverflow(msg) => msg,
+            Self::Availability(error) => error.message(),
+        }
+    }
 }
 
 impl From<Runtime> for Error {
@@ -53,7 +115,74 @@ impl fmt::Display for Runtime {
             Self::Internal(msg) => write!(f, "internal error; {}", msg),
             Self::Availability(msg) => write!(f, "availability error; {}", msg),
             Self::Permission(msg) => write!(f, "permission error; {}", msg),
+            Self::ChangefeedOverflow(msg) => write!(f, "changefeed overflow; {}", msg),
+        }
+    }
+}
+
+/// The message of an error reported by the server
+///
+/// Dereferences to the message text. The backtrace points at the part of
+/// the query that failed, and is empty if the server did not send one.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Message {
+    pub text: String,
+    pub backtrace: Backtrace,
+}
+
+impl From<String> for Message {
+    fn from(text: String) -> Self {
+        Self {
+            text,
+            backtrace: Backtrace::default(),
+        }
+    }
+}
+
+impl Deref for Message {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.text
+    }
+}
+
+impl fmt::Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.text)?;
+        if !self.backtrace.0.is_empty() {
+            write!(f, "; in {}", self.backtrace)?;
+        }
+        Ok(())
+    }
+}
+
+/// The path from the root of a query to the term that failed
+///
+/// Each frame steps into one argument or option of the term before it.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize)]
+pub struct Backtrace(pub Vec<Frame>);
+
+/// A single step of a [Backtrace]
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum Frame {
+    /// The argument at this position
+    Pos(usize),
+    /// The option with this name
+    Opt(String),
+}
+
+impl fmt::Display for Backtrace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "query")?;
+        for frame in &self.0 {
+            match frame {
+                Frame::Pos(pos) => write!(f, " > argument {}", pos)?,
+                Frame::Opt(name) => write!(f, " > option `{}`", name)?,
+            }
         }
+        Ok(())
     }
 }
 
@@ -64,8 +193,17 @@ impl fmt::Display for Runtime {
 /// children.
 #[derive(Debug, Clone)]
 pub enum Availability {
-    OpFailed(String),
-    OpIndeterminate(String),
+    OpFailed(Message),
+    OpIndeterminate(Message),
+}
+
+impl Availability {
+    /// The message sent by the server
+    pub fn message(&self) -> &Message {
+        match self {
+            Self::OpFailed(msg) | Self::OpIndeterminate(msg) => msg,
+        }
+    }
 }
 
 impl From<Availability> for Error {
@@ -90,14 +228,80 @@ impl fmt::Display for Availability {
 #[derive(Debug, Clone)]
 #[non_exhaustive]
 pub enum Driver {
-    Auth(String),
+    Auth(Auth),
     ConnectionBroken,
     ConnectionLocked,
     Io(io::ErrorKind, String),
     Json(Arc<serde_json::Error>),
+    /// A row returned by the server could not be deserialised into the
+    /// requested type
+    Deserialize(Arc<serde_json::Error>, serde_json::Value),
+    Timeout(String),
+    /// The TLS connection to the server could not be established, for
+    /// example because its certificate failed verification
+    Tls(String),
     Other(String),
 }
 
+/// The handshake with the server failed
+///
+/// Each variant keeps the message the error was raised with.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Auth {
+    /// The password is wrong
+    Unauthorized(String),
+    /// There is no user with this name
+    UnknownUser(String),
+    /// The server does not speak a protocol version this driver supports
+    UnsupportedProtocol(String),
+    /// The server's SCRAM messages were malformed or could not be
+    /// verified, so it may not be the server it claims to be
+    Scram(String),
+    /// Any other authentication error, with the code the server sent
+    Other(usize, String),
+}
+
+impl Auth {
+    // Error codes 10 to 20 are authentication errors
+    pub(crate) fn from_code(code: usize, msg: String) -> Self {
+        match code {
+            12 => Self::Unauthorized(msg),
+            17 => Self::UnknownUser(msg),
+            _ => Self::Other(code, msg),
+        }
+    }
+
+    /// The message the error was raised with
+    pub fn message(&self) -> &str {
+        match self {
+            Self::Unauthorized(msg)
+            | Self::UnknownUser(msg)
+            | Self::UnsupportedProtocol(msg)
+            | Self::Scram(msg)
+            | Self::Other(_, msg) => msg,
+        }
+    }
+}
+
+impl From<Auth> for Error {
+    fn from(err: Auth) -> Error {
+        Driver::Auth(err).into()
+    }
+}
+
+impl fmt::Display for Auth {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Unauthorized(msg) => write!(f, "unauthorized; {}", msg),
+            Self::UnknownUser(msg) => write!(f, "unknown user; {}", msg),
+            Self::UnsupportedProtocol(msg) => write!(f, "unsupported protocol; {}", msg),
+            Self::Scram(msg) => write!(f, "scram; {}", msg),
+            Self::Other(code, msg) => write!(f, "error code {}; {}", code, msg),
+        }
+    }
+}
+
 impl From<Driver> for Error {
     fn from(err: Driver) -> Error {
         Error::Driver(err)
@@ -115,6 +319,11 @@ impl fmt::Display for Driver {
             ),
             Self::Io(_, error) => write!(f, "{}", error),
             Self::Json(error) => write!(f, "{}", error),
+            Self::Deserialize(error, value) => {
+                write!(f, "failed to deserialise `{}`; {}", value, error)
+            }
+            Self::Timeout(msg) => write!(f, "{}", msg),
+            Self::Tls(msg) => write!(f, "tls error; {}", msg),
             Self::Other(msg) => write!(f, "{}", msg),
         }
     }
@@ -126,8 +335,63 @@ impl From<io::Error> for Error {
     }
 }
 
+impl From<reql_types::geo::Error> for Error {
+    fn from(err: reql_types::geo::Error) -> Error {
+        Driver::Other(err.to_string()).into()
+    }
+}
+
 impl From<serde_json::Error> for Error {
     fn from(err: serde_json::Error) -> Error {
         Driver::Json(Arc::new(err)).into()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Availability, Backtrace, Driver, Error, Frame, Message, Runtime};
+    use std::io;
+
+    #[test]
+    fn connection_errors() {
+        assert!(Error::from(Driver::ConnectionBroken).is_connection_error());
+        assert!(Error::from(io::Error::from(io::ErrorKind::ConnectionReset)).is_connection_error());
+        let runtime = Runtime::QueryLogic(Message::from("Expected type NUMBER".to_owned()));
+        assert!(!Error::from(runtime).is_connection_error());
+        assert!(!Error::from(Driver::Other("oops".to_owned())).is_connection_error());
+        assert!(!Error::from(Driver::Timeout("timed out".to_owned())).is_connection_error());
+    }
+
+    #[test]
+    fn message_shows_backtrace() {
+        let msg = Message {
+            text: "No attribute `foo` in object".to_owned(),
+            backtrace: Backtrace(vec![Frame::Pos(0), Frame::Opt("index".to_owned())]),
+        };
+        assert_eq!(
+            msg.to_string(),
+            "No attribute `foo` in object; in query > argument 0 > option `index`"
+        );
+    }
+
+    #[test]
+    fn server_backtrace() {
+        let msg = Message {
+            text: "Index `foo` was not found".to_owned(),
+            backtrace: Backtrace(vec![Frame::Pos(0), Frame::Opt("index".to_owned())]),
+        };
+        let error = Error::from(Availability::OpFailed(msg.clone()));
+        assert_eq!(error.message(), Some(&msg));
+        assert_eq!(error.backtrace(), Some(&msg.backtrace));
+        let error = Error::Compile(Message::from("Expected 2 arguments".to_owned()));
+        assert_eq!(error.backtrace(), None);
+        assert_eq!(Error::from(Driver::ConnectionBroken).message(), None);
+    }
+
+    #[test]
+    fn backtrace_is_deserialised() {
+        let backtrace: Backtrace = serde_json::from_str(r#"[1, "index", 0]"#).unwrap();
+        let expected = [Frame::Pos(1), Frame::Opt("index".to_owned()), Frame::Pos(0)];
+        assert_eq!(backtrace.0, expected);
+    }
+}
@@ -50,6 +50,14 @@
 //!
 //! The variable `connection` is now initialized and we can run queries.
 //!
+//! `r.connect` opens exactly one connection and doesn't start a pool,
+//! which keeps short-lived tools and tests cheap to start. The session
+//! can be cloned and shared between tasks, and queries on it are
+//! multiplexed over that single connection. Applications that need a
+//! pool can use the [`mobc-reql`](https://docs.rs/mobc-reql) crate,
+//! whose pooled connections dereference to this same `Session`, so
+//! queries are run in exactly the same way with or without a pool.
+//!
 //! # Send a query to the database #
 //!
 //! ```
@@ -59,33 +67,55 @@
 //! ```
 //!
 //! [See the `r` struct for more available commands](r)
+//!
+//! # Logging #
+//!
+//! The driver logs through the [`log`](https://docs.rs/log) facade rather
+//! than owning a logger of its own, so its records go to whichever logger
+//! your application installs. If you already use `slog`, forward them to
+//! your `slog::Logger` with the `slog-stdlog` crate.
+//!
+//! All records are emitted under targets starting with `reql`, so the
+//! driver's output can be filtered on its own, or turned off completely
+//! by setting the level for the `reql` target to `off` (for example
+//! `RUST_LOG=reql=off` with `env_logger`).
 
 #![allow(clippy::wrong_self_convention)]
 
 pub mod cmd;
 mod err;
+pub mod migrate;
 mod proto;
 
 use async_net::TcpStream;
+use cmd::connect::Transport;
 use cmd::run::Response;
 use cmd::StaticString;
 use dashmap::DashMap;
 use futures::channel::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use futures::io::AsyncWriteExt;
 use futures::lock::Mutex;
+use futures::stream::{Stream, TryStreamExt};
 use log::trace;
 use proto::{Payload, Query};
 use ql2::query::QueryType;
 use ql2::response::ResponseType;
 use ql2::term::TermType;
+use serde::de::DeserializeOwned;
 use serde_json::json;
+use socket2::SockRef;
 use std::borrow::Cow;
+use std::io;
+use std::mem::MaybeUninit;
+use std::net::Shutdown;
 use std::ops::Drop;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 use types::ServerInfo;
 
-#[doc(hidden)]
 pub use cmd::func::Func;
+pub use cmd::run::QueryHandle;
 pub use err::*;
 pub use proto::Command;
 pub use reql_macros::func;
@@ -96,12 +126,33 @@ pub use reql_types as types;
 pub static VAR_COUNTER: AtomicU64 = AtomicU64::new(1);
 
 #[doc(hidden)]
+#[cfg(not(test))]
 pub fn var_counter() -> u64 {
     VAR_COUNTER.fetch_add(1, Ordering::SeqCst)
 }
 
+// Unit tests run in parallel, so each test thread counts its own variables
+// unless it asks for the shared counter through `current_counter`
+#[cfg(test)]
+thread_local! {
+    static LOCAL_COUNTER: std::cell::Cell<Option<u64>> = const { std::cell::Cell::new(Some(1)) };
+}
+
+#[doc(hidden)]
+#[cfg(test)]
+pub fn var_counter() -> u64 {
+    LOCAL_COUNTER.with(|local| match local.get() {
+        Some(id) => {
+            local.set(Some(id + 1));
+            id
+        }
+        None => VAR_COUNTER.fetch_add(1, Ordering::SeqCst),
+    })
+}
+
 #[cfg(test)]
 fn current_counter() -> u64 {
+    LOCAL_COUNTER.with(|local| local.set(None));
     VAR_COUNTER.load(Ordering::SeqCst)
 }
 
@@ -113,30 +164,88 @@ type Receiver = UnboundedReceiver<Result<(ResponseType, Response)>>;
 
 #[derive(Debug)]
 struct InnerSession {
+    server_version: String,
     db: Mutex<Cow<'static, str>>,
-    stream: Mutex<TcpStream>,
+    stream: Mutex<Transport>,
+    // The socket under `stream`, to shut it down while a query holds it
+    socket: TcpStream,
+    read_timeout: Option<Duration>,
+    write_timeout: Option<Duration>,
+    read_mode: Option<cmd::ReadMode>,
     channels: DashMap<u64, Sender>,
+    pending_stops: std::sync::Mutex<Vec<u64>>,
+    read_buf: Mutex<Vec<u8>>,
     token: AtomicU64,
     broken: AtomicBool,
     change_feed: AtomicBool,
 }
 
 impl InnerSession {
+    // Every query gets a token of its own, which the server echoes back
+    // in each of its responses so they can be routed to that query
     fn token(&self) -> u64 {
-        let token = self
-            .token
-            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |x| Some(x + 1))
-            .unwrap();
+        let token = self.token.fetch_add(1, Ordering::SeqCst);
         if token == u64::MAX {
             self.mark_broken();
         }
         token
     }
 
+    // Queues a STOP for a query whose stream was dropped before the server
+    // sent all of its results. It is sent along with the next query.
+    fn stop_later(&self, token: u64) {
+        if let Ok(mut stops) = self.pending_stops.lock() {
+            stops.push(token);
+        }
+    }
+
+    fn take_pending_stops(&self) -> Vec<u64> {
+        match self.pending_stops.lock() {
+            Ok(mut stops) => std::mem::take(&mut *stops),
+            Err(_) => Vec::new(),
+        }
+    }
+
     fn mark_broken(&self) {
         self.broken.store(true, Ordering::SeqCst);
     }
 
+    // A server that went away without the session noticing, such as after
+    // a network blip, leaves the socket readable with nothing to read.
+    // This is only checked while no query holds the stream, when the
+    // server has no reason to send anything, so any bytes waiting mean the
+    // stream is out of step with its queries too. The socket is
+    // non-blocking, so peeking never waits.
+    fn probe(&self) {
+        let stream = match self.stream.try_lock() {
+            Some(stream) => stream,
+            None => return,
+        };
+        let socket = SockRef::from(stream.tcp());
+        if let Ok(Some(error)) = socket.take_error() {
+            trace!("session broken; error: {}", error);
+            self.mark_broken();
+            return;
+        }
+        let mut buf = [MaybeUninit::uninit(); 1];
+        match socket.peek(&mut buf) {
+            Err(error) if error.kind() == io::ErrorKind::WouldBlock => {}
+            Ok(0) => {
+                trace!("session broken; the server closed the connection");
+                self.mark_broken();
+            }
+            Ok(_) if !stream.is_tls() => {
+                trace!("session broken; unexpected bytes on the connection");
+                self.mark_broken();
+            }
+            Ok(_) => {}
+            Err(error) => {
+                trace!("session broken; error: {}", error);
+                self.mark_broken();
+            }
+        }
+    }
+
     fn broken(&self) -> Result<()> {
         if self.broken.load(Ordering::SeqCst) {
             return Err(err::Driver::ConnectionBroken.into());
@@ -171,6 +280,13 @@ pub struct Session {
 }
 
 impl Session {
+    /// Get a connection on which to run a single query
+    ///
+    /// Each connection has a token of its own, so any number of queries
+    /// may run on the same session at once, with responses routed to the
+    /// query they belong to. A connection, however, carries one query
+    /// at a time. Running a query on `&Session` gets a fresh connection
+    /// for it automatically.
     pub fn connection(&self) -> Result<Connection> {
         self.inner.broken()?;
         self.inner.change_feed()?;
@@ -238,6 +354,151 @@ impl Session {
         Ok(())
     }
 
+    /// Run a query on this session with the given run options
+    ///
+    /// This is the same as `query.run(r.args((&session, opts)))`, for when
+    /// a query is built in one place and run in another. Each row of the
+    /// result is yielded by the stream, so a single value, such as the
+    /// result of a write or of `count`, is its only item.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use futures::TryStreamExt;
+    /// use reql::cmd::{run, ReadMode};
+    /// use serde_json::Value;
+    ///
+    /// # async fn example() -> reql::Result<()> {
+    /// # let session = reql::r.connect(()).await?;
+    /// let query = reql::r.table("heroes");
+    /// let opts = run::Options::new().read_mode(ReadMode::Outdated);
+    /// let heroes: Vec<Value> = session.run::<Value>(query, opts).try_collect().await?;
+    /// # Ok(()) }
+    /// ```
+    pub fn run<'a, T>(
+        &'a self,
+        query: Command,
+        opts: cmd::run::Options,
+    ) -> impl Stream<Item = Result<T>> + 'a
+    where
+        T: 'a + Unpin + DeserializeOwned,
+    {
+        query.run(r.args((self, opts)))
+    }
+
+    /// Run a term already in the JSON form sent to the server
+    ///
+    /// This is an escape hatch for commands the builder doesn't have yet,
+    /// or for terms generated by another tool. `term` has to be a term
+    /// array, such as `[15,["users"]]` for `r.table("users")`, and is
+    /// checked to be made of known term types before it is sent. It is
+    /// then run just like a query built with `r`.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use futures::TryStreamExt;
+    /// use reql::cmd::run;
+    /// use serde_json::{json, Value};
+    ///
+    /// # async fn example() -> reql::Result<()> {
+    /// # let session = reql::r.connect(()).await?;
+    /// let term = json!([15, ["heroes"]]);
+    /// let heroes: Vec<Value> = session
+    ///     .run_raw::<Value>(term, run::Options::new())
+    ///     .try_collect()
+    ///     .await?;
+    /// # Ok(()) }
+    /// ```
+    pub fn run_raw<'a, T>(
+        &'a self,
+        term: serde_json::Value,
+        opts: cmd::run::Options,
+    ) -> impl Stream<Item = Result<T>> + 'a
+    where
+        T: 'a + Unpin + DeserializeOwned,
+    {
+        let query = match term {
+            serde_json::Value::Array(_) => Command::from_raw(term),
+            _ => Err(Driver::Other("a raw query has to be a term array".to_owned()).into()),
+        };
+        Box::pin(async_stream::try_stream! {
+            let mut rows = self.run::<T>(query?, opts);
+            while let Some(row) = rows.try_next().await? {
+                yield row;
+            }
+        })
+    }
+
+    /// Run a query that returns a single value, such as `count` or `get`,
+    /// and return that value
+    ///
+    /// `None` is returned if the query turns out to return an empty
+    /// sequence. Any rows after the first one are ignored.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # async fn example() -> reql::Result<()> {
+    /// # let session = reql::r.connect(()).await?;
+    /// let heroes: Option<u64> = session
+    ///     .run_value(reql::r.table("heroes").count(()), Default::default())
+    ///     .await?;
+    /// # Ok(()) }
+    /// ```
+    pub async fn run_value<T>(&self, query: Command, opts: cmd::run::Options) -> Result<Option<T>>
+    where
+        T: Unpin + DeserializeOwned,
+    {
+        let mut rows = self.run(query, opts);
+        rows.try_next().await
+    }
+
+    /// Run several independent queries at once, pipelining them over the
+    /// session
+    ///
+    /// All the queries are written to the server before any of their
+    /// responses is read, saving a round trip per query compared to
+    /// running them one after the other. A stream of results is returned
+    /// for each query, in the order the queries were given.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use futures::TryStreamExt;
+    /// use serde_json::Value;
+    ///
+    /// # async fn example() -> reql::Result<()> {
+    /// # let session = reql::r.connect(()).await?;
+    /// let queries = vec![reql::r.table("heroes").get(1), reql::r.table("villains").get(1)];
+    /// for mut query in session.run_many::<Value, _>(queries).await? {
+    ///     let doc = query.try_next().await?;
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub async fn run_many<T, I>(&self, queries: I) -> Result<Vec<impl Stream<Item = Result<T>>>>
+    where
+        I: IntoIterator<Item = Command>,
+        T: Unpin + DeserializeOwned,
+    {
+        cmd::run::many(self, queries.into_iter().collect()).await
+    }
+
+    /// Get information about the server this session is connected to
+    ///
+    /// The result holds the server's `id`, its `name` and whether it is
+    /// a `proxy` server. This is handy when debugging which node of a
+    /// cluster a session ended up on.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # async fn example() -> reql::Result<()> {
+    /// # let session = reql::r.connect(()).await?;
+    /// let info = session.server().await?;
+    /// println!("connected to {:?} ({})", info.name, info.id);
+    /// # Ok(()) }
+    /// ```
     pub async fn server(&self) -> Result<ServerInfo> {
         let mut conn = self.connection()?;
         let payload = Payload(QueryType::ServerInfo, None, Default::default());
@@ -248,19 +509,108 @@ impl Session {
             conn.token,
             typ,
         );
-        let mut vec = serde_json::from_value::<Vec<ServerInfo>>(resp.r)?;
-        let info = vec
-            .pop()
-            .ok_or_else(|| Driver::Other("server info is empty".into()))?;
+        let unexpected = |value: &serde_json::Value| {
+            Driver::Other(format!("unexpected server info response: {}", value))
+        };
+        let mut vec = serde_json::from_value::<Vec<ServerInfo>>(resp.r.clone())
+            .map_err(|_| unexpected(&resp.r))?;
+        let info = vec.pop().ok_or_else(|| unexpected(&resp.r))?;
         Ok(info)
     }
 
+    /// The version of RethinkDB the session is connected to, as reported
+    /// by the server when connecting, such as `2.4.1~0bionic`
+    pub fn server_version(&self) -> &str {
+        &self.inner.server_version
+    }
+
+    /// Close the session, shutting down the underlying TCP connection
+    ///
+    /// Unless [SkipNoreplyWait](cmd::close::SkipNoreplyWait) is passed, this
+    /// first waits for outstanding `noreply` writes to finish. Queries still
+    /// running on the session are then stopped so the server can release
+    /// their resources. Any query currently waiting for a response will
+    /// be allowed to receive it first.
+    ///
+    /// A change feed waits for its next change while holding the session,
+    /// which may take forever, so a session running one is shut down right
+    /// away instead. The server then stops the feed itself, and the feed's
+    /// stream ends with a connection error.
+    ///
+    /// Closing a session more than once is a no-op. A closed session is
+    /// marked as broken, so connection pools will not hand it out again.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # async fn example() -> reql::Result<()> {
+    /// let session = reql::r.connect(()).await?;
+    /// session.close(()).await
+    /// # }
+    /// ```
+    ///
+    /// [Read more about this command →](cmd::close)
+    pub async fn close<T>(&self, arg: T) -> Result<()>
+    where
+        T: cmd::close::Arg,
+    {
+        if !self.is_broken() {
+            let change_feed = self.inner.is_change_feed();
+            if arg.noreply_wait() && !change_feed {
+                self.noreply_wait().await?;
+            }
+            self.inner.mark_broken();
+            let stream = if change_feed {
+                self.inner.stream.try_lock()
+            } else {
+                Some(self.inner.stream.lock().await)
+            };
+            match stream {
+                Some(mut stream) => {
+                    let payload = Payload(QueryType::Stop, None, Default::default());
+                    let tokens: Vec<_> = self.inner.channels.iter().map(|x| *x.key()).collect();
+                    for token in tokens {
+                        trace!(
+                            "stopping query before closing the session; token: {}",
+                            token
+                        );
+                        stream.write_all(&payload.encode(token)?).await?;
+                    }
+                    stream.flush().await?;
+                }
+                None => trace!("closing the session while a change feed holds it"),
+            }
+        }
+        match self.inner.socket.shutdown(Shutdown::Both) {
+            Err(error) if error.kind() != io::ErrorKind::NotConnected => Err(error.into()),
+            _ => {
+                trace!("session closed");
+                Ok(())
+            }
+        }
+    }
+
+    /// Whether the session can no longer be used
+    ///
+    /// Besides errors seen by earlier queries, this notices a server that
+    /// closed the connection, without sending anything over the network,
+    /// so it is cheap enough for connection pools to call on every
+    /// checkout.
     #[doc(hidden)]
     pub fn is_broken(&self) -> bool {
+        if !self.inner.broken.load(Ordering::SeqCst) {
+            self.inner.probe();
+        }
         self.inner.broken.load(Ordering::SeqCst)
     }
 }
 
+impl AsRef<Session> for Session {
+    fn as_ref(&self) -> &Session {
+        self
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Connection {
     session: Session,
@@ -323,6 +673,30 @@ impl Connection {
         Ok(())
     }
 
+    /// A handle to cancel the query run on this connection
+    ///
+    /// Unlike dropping its stream, this works from another task, for
+    /// example to give up on a slow request once a deadline has passed.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use futures::TryStreamExt;
+    /// use serde_json::Value;
+    ///
+    /// # async fn example() -> reql::Result<()> {
+    /// # let session = reql::r.connect(()).await?;
+    /// let conn = session.connection()?;
+    /// let handle = conn.handle();
+    /// let mut rows = reql::r.table("logs").run::<_, Value>(conn);
+    /// let first = rows.try_next().await?;
+    /// handle.cancel().await?;
+    /// # Ok(()) }
+    /// ```
+    pub fn handle(&self) -> QueryHandle {
+        QueryHandle::new(self)
+    }
+
     fn closed(&self) -> bool {
         self.closed.load(Ordering::SeqCst)
     }
@@ -351,6 +725,7 @@ impl Drop for Connection {
 /// use reql::r;
 /// ```
 #[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy)]
 pub struct r;
 
 impl r {
@@ -376,6 +751,19 @@ impl r {
         cmd::connect::new(options.into_connect_opts()).await
     }
 
+    /// Create a database
+    ///
+    /// The result deserialises into [DbWriteStatus](types::DbWriteStatus).
+    /// Creating a database that already exists fails with
+    /// [OpFailed](Availability::OpFailed).
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # reql::example(|r, conn| async_stream::stream! {
+    /// r.db_create("superheroes").run(conn)
+    /// # });
+    /// ```
     pub fn db_create<T>(self, arg: T) -> Command
     where
         T: cmd::db_create::Arg,
@@ -383,6 +771,17 @@ impl r {
         arg.arg().into_cmd()
     }
 
+    /// Drop a database, along with all of its tables
+    ///
+    /// The result deserialises into [DbWriteStatus](types::DbWriteStatus).
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # reql::example(|r, conn| async_stream::stream! {
+    /// r.db_drop("superheroes").run(conn)
+    /// # });
+    /// ```
     pub fn db_drop<T>(self, arg: T) -> Command
     where
         T: cmd::db_drop::Arg,
@@ -390,6 +789,8 @@ impl r {
         arg.arg().into_cmd()
     }
 
+    /// List the names of all databases, which deserialise into a
+    /// `Vec<String>`
     pub fn db_list(self) -> Command {
         Command::new(TermType::DbList)
     }
@@ -522,6 +923,19 @@ impl r {
         arg.arg().into_cmd()
     }
 
+    /// Generate a random number
+    ///
+    /// Without arguments this is a float in `[0, 1)`. A single bound gives
+    /// a number in `[0, upper)` and two bounds one in `[lower, upper)`,
+    /// which is an integer if the bounds are, unless the `float` option is
+    /// set.
+    ///
+    /// ```
+    /// # use reql::cmd::random::Options;
+    /// # use reql::r;
+    /// let dice = r.random(r.args((1, 7)));
+    /// let ratio = r.random(r.args((1, 7, Options::new().float(true))));
+    /// ```
     pub fn random<T>(self, arg: T) -> Command
     where
         T: cmd::random::Arg,
@@ -554,6 +968,11 @@ impl r {
         Command::new(TermType::Now)
     }
 
+    /// Create a time from its parts
+    ///
+    /// Takes `r.args((year, month, day, timezone))` or
+    /// `r.args((year, month, day, hour, minute, second, timezone))`,
+    /// where the timezone is `"Z"` or an offset such as `"+02:00"`.
     pub fn time<T>(self, arg: T) -> Command
     where
         T: cmd::time::Arg,
@@ -575,6 +994,13 @@ impl r {
         arg.arg().into_cmd()
     }
 
+    pub fn binary<T>(self, arg: T) -> Command
+    where
+        T: cmd::binary::Arg,
+    {
+        arg.arg().into_cmd()
+    }
+
     pub fn do_<T>(self, arg: T) -> Command
     where
         T: cmd::do_::Arg,
@@ -582,6 +1008,12 @@ impl r {
         arg.arg(None).into_cmd()
     }
 
+    /// Pick between values depending on a test
+    ///
+    /// Takes `r.args((test, true_action, false_action))`, or
+    /// `r.args(([(test, action), ...], false_action))` to try several
+    /// tests in order, like a chain of `else if`s. Only `false` and
+    /// `null` count as false.
     pub fn branch<T>(self, arg: T) -> Command
     where
         T: cmd::branch::Arg,
@@ -603,6 +1035,20 @@ impl r {
         arg.arg().into_cmd()
     }
 
+    /// Turn a Rust value into a query
+    ///
+    /// Anything that implements `Serialize`, from numbers and strings to
+    /// vectors, maps and your own structs, is serialised with
+    /// `serde_json` and sent as a literal value, so it can be combined
+    /// with other commands.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # reql::example(|r, conn| async_stream::stream! {
+    /// r.expr(vec![1, 2, 3]).count(()).run(conn)
+    /// # });
+    /// ```
     pub fn expr<T>(self, arg: T) -> Command
     where
         T: cmd::expr::Arg,
@@ -610,6 +1056,22 @@ impl r {
         arg.arg().into_cmd()
     }
 
+    /// Evaluate JavaScript code on the server
+    ///
+    /// The code is run by the server's embedded V8 engine, and can also
+    /// return a function to pass to commands like `filter` or `map`.
+    /// It is much slower than the equivalent ReQL, as every value has to
+    /// be converted to and from JavaScript, and queries using it can't be
+    /// spread across the cluster, so reach for it only when ReQL has no
+    /// way of doing something. The code is stopped once the `timeout`
+    /// option, 5 seconds by default, has passed.
+    ///
+    /// ```
+    /// # use reql::cmd::js::Options;
+    /// # use reql::r;
+    /// let two = r.js("1 + 1");
+    /// let short = r.js(r.args(("while(true) {}", Options::new().timeout(1.3))));
+    /// ```
     pub fn js<T>(self, arg: T) -> Command
     where
         T: cmd::js::Arg,
@@ -631,6 +1093,28 @@ impl r {
         arg.arg().into_cmd()
     }
 
+    /// Fetch a URL from the server
+    ///
+    /// The request is made by the RethinkDB server rather than the client,
+    /// so the result can be used inside the query, for example to enrich
+    /// documents as they are inserted. JSON responses are parsed into
+    /// objects, anything else comes back as a string or binary value
+    /// depending on the `result_format` option. Failed requests are
+    /// retried according to the `attempts` option and then reported as a
+    /// runtime error from the server.
+    ///
+    /// ```
+    /// # use reql::cmd::http::{Method, Options};
+    /// # use reql::r;
+    /// # use serde_json::json;
+    /// let user = r.http("https://api.github.com/users/rethinkdb");
+    /// let posted = r.http(r.args((
+    ///     "http://httpbin.org/post",
+    ///     Options::new()
+    ///         .method(Method::Post)
+    ///         .data(json!({"player": "Bob", "game": "tic tac toe"})),
+    /// )));
+    /// ```
     pub fn http<T>(self, arg: T) -> Command
     where
         T: cmd::http::Arg,
@@ -729,6 +1213,22 @@ impl r {
         cmd::index::Index(arg.arg().into_cmd())
     }
 
+    /// The smallest possible value, for open-ended ranges
+    ///
+    /// Use it as the lower bound of [between](Command::between) to match
+    /// everything below the upper bound.
+    pub fn minval(self) -> Command {
+        Command::new(TermType::Minval)
+    }
+
+    /// The largest possible value, for open-ended ranges
+    ///
+    /// Use it as the upper bound of [between](Command::between) to match
+    /// everything above the lower bound.
+    pub fn maxval(self) -> Command {
+        Command::new(TermType::Maxval)
+    }
+
     pub fn args<T>(self, arg: T) -> cmd::args::Args<T> {
         cmd::args::Args(arg)
     }
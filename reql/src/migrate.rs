@@ -0,0 +1,211 @@
+//! Versioned schema changes
+//!
+//! A [Migration] lists the queries that move the schema up to its
+//! version, such as creating tables and indexes, and the ones that undo
+//! them. A [Migrator] keeps track of the versions applied to a database
+//! in a table of their own, `_migrations` by default, so running it on
+//! every start up only applies the migrations that are new.
+//!
+//! RethinkDB has no transactions, so a migration whose queries fail part
+//! way through is not recorded and has to be fixed up by hand. Keeping
+//! each migration down to a single change makes that easy.
+//!
+//! ```
+//! use reql::migrate::{Migration, Migrator};
+//! use reql::r;
+//!
+//! # async fn example() -> reql::Result<()> {
+//! let session = r.connect(()).await?;
+//! let migrator = Migrator::new(vec![
+//!     Migration::new(1)
+//!         .up(r.table_create("users"))
+//!         .down(r.db("test").table_drop("users")),
+//!     Migration::new(2)
+//!         .up(r.table("users").index_create("email"))
+//!         .down(r.table("users").index_drop("email")),
+//! ]);
+//! let applied = migrator.up(&session).await?;
+//! # Ok(()) }
+//! ```
+
+use crate::{err, r, Command, Error, Result, Runtime, Session};
+use futures::TryStreamExt;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::borrow::Cow;
+
+const DEFAULT_TABLE: &str = "_migrations";
+
+/// The queries that make up a version of the schema
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct Migration {
+    pub version: u64,
+    /// Run in order when the migration is applied
+    pub up: Vec<Command>,
+    /// Run in order when the migration is rolled back
+    pub down: Vec<Command>,
+}
+
+impl Migration {
+    pub fn new(version: u64) -> Self {
+        Self {
+            version,
+            up: Vec::new(),
+            down: Vec::new(),
+        }
+    }
+
+    /// Adds a query to run when applying the migration
+    pub fn up(mut self, query: Command) -> Self {
+        self.up.push(query);
+        self
+    }
+
+    /// Adds a query to run when rolling the migration back
+    pub fn down(mut self, query: Command) -> Self {
+        self.down.push(query);
+        self
+    }
+}
+
+// A row of the migrations table
+#[derive(Debug, Serialize, Deserialize)]
+struct Applied {
+    id: u64,
+}
+
+/// Applies and rolls back migrations, keeping track of those applied
+#[derive(Debug, Clone)]
+pub struct Migrator {
+    table: Cow<'static, str>,
+    migrations: Vec<Migration>,
+}
+
+impl Migrator {
+    /// Creates a migrator for these migrations, in any order
+    pub fn new(mut migrations: Vec<Migration>) -> Self {
+        migrations.sort_by_key(|migration| migration.version);
+        Self {
+            table: Cow::Borrowed(DEFAULT_TABLE),
+            migrations,
+        }
+    }
+
+    /// Sets the table the applied versions are recorded in
+    pub fn table<T>(mut self, table: T) -> Self
+    where
+        T: crate::cmd::StaticString,
+    {
+        self.table = table.static_string();
+        self
+    }
+
+    /// The versions applied so far, oldest first
+    pub async fn applied(&self, session: &Session) -> Result<Vec<u64>> {
+        self.create_table(session).await?;
+        let mut versions: Vec<u64> = r
+            .table(self.table.clone())
+            .get_field("id")
+            .run::<_, u64>(session)
+            .try_collect()
+            .await?;
+        versions.sort_unstable();
+        Ok(versions)
+    }
+
+    /// Applies the migrations that haven't been yet, in order of their
+    /// versions, and returns the versions applied
+    ///
+    /// Stops at the first query that fails, leaving the migrations
+    /// applied before it recorded.
+    pub async fn up(&self, session: &Session) -> Result<Vec<u64>> {
+        let applied = self.applied(session).await?;
+        let mut versions = Vec::new();
+        for migration in pending(&self.migrations, &applied) {
+            run_all(session, &migration.up).await?;
+            let row = Applied {
+                id: migration.version,
+            };
+            run_all(session, &[r.table(self.table.clone()).insert(row)]).await?;
+            versions.push(migration.version);
+        }
+        Ok(versions)
+    }
+
+    /// Rolls back the latest migration applied, if any, and returns its
+    /// version
+    pub async fn down(&self, session: &Session) -> Result<Option<u64>> {
+        let applied = self.applied(session).await?;
+        let version = match applied.last() {
+            Some(version) => *version,
+            None => return Ok(None),
+        };
+        let migration = self
+            .migrations
+            .iter()
+            .find(|migration| migration.version == version)
+            .ok_or_else(|| err::Driver::Other(format!("migration {} is not known", version)))?;
+        run_all(session, &migration.down).await?;
+        let delete = r.table(self.table.clone()).get(version).delete(());
+        run_all(session, &[delete]).await?;
+        Ok(Some(version))
+    }
+
+    async fn create_table(&self, session: &Session) -> Result<()> {
+        let create = r.table_create(self.table.clone());
+        match run_all(session, &[create]).await {
+            Ok(()) => {}
+            Err(Error::Runtime(Runtime::Availability(error)))
+                if error.message().contains("already exists") => {}
+            Err(error) => return Err(error),
+        }
+        run_all(session, &[r.table(self.table.clone()).wait(())]).await
+    }
+}
+
+// The migrations that still need to be applied, in order
+fn pending<'a>(migrations: &'a [Migration], applied: &[u64]) -> Vec<&'a Migration> {
+    migrations
+        .iter()
+        .filter(|migration| !applied.contains(&migration.version))
+        .collect()
+}
+
+async fn run_all(session: &Session, queries: &[Command]) -> Result<()> {
+    for query in queries {
+        query
+            .clone()
+            .run::<_, Value>(session)
+            .try_for_each(|_| async { Ok(()) })
+            .await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{pending, Migration, Migrator};
+    use crate::r;
+
+    #[test]
+    fn migrations_are_sorted() {
+        let migrator = Migrator::new(vec![
+            Migration::new(2),
+            Migration::new(1).up(r.table_create("users")),
+        ]);
+        let versions: Vec<_> = migrator.migrations.iter().map(|m| m.version).collect();
+        assert_eq!(versions, [1, 2]);
+        assert_eq!(migrator.migrations[0].up.len(), 1);
+    }
+
+    #[test]
+    fn only_pending_migrations_are_applied() {
+        let migrations = [Migration::new(1), Migration::new(2), Migration::new(3)];
+        let versions: Vec<_> = pending(&migrations, &[1, 3])
+            .iter()
+            .map(|m| m.version)
+            .collect();
+        assert_eq!(versions, [2]);
+    }
+}
@@ -2,9 +2,11 @@ use crate::cmd::run::{Db, Options};
 use crate::{err, r};
 use ql2::query::QueryType;
 use ql2::term::TermType;
-use serde::ser::{self, Serialize, Serializer};
+use serde::ser::{self, Serialize, SerializeMap, Serializer};
 use serde_json::value::{Number, Value};
+use std::borrow::Cow;
 use std::collections::{HashMap, VecDeque};
+use std::iter::FromIterator;
 use std::{fmt, str};
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -39,21 +41,47 @@ impl Serialize for Datum {
     }
 }
 
-#[allow(array_into_iter)]
-#[allow(clippy::into_iter_on_ref)]
 impl<const N: usize> From<[Command; N]> for Command {
     fn from(arr: [Command; N]) -> Self {
         let mut query = Self::new(TermType::MakeArray);
-        // TODO remove this clone on Rust v1.53 once
-        // https://twitter.com/m_ou_se/status/1385966446254166020
-        // is available on stable
-        for arg in arr.into_iter().cloned() {
+        for arg in IntoIterator::into_iter(arr) {
             query = query.with_arg(arg);
         }
         query
     }
 }
 
+/// Builds an object whose values are queries, as in
+/// `Command::from([("title", r.expr("Hello")), ("updated", r.now())])`
+///
+/// The values are sent to the server as terms with `MAKE_OBJ`, so unlike
+/// `r.expr` with a map they are evaluated before being stored.
+impl<K, const N: usize> From<[(K, Command); N]> for Command
+where
+    K: Into<String>,
+{
+    fn from(arr: [(K, Command); N]) -> Self {
+        IntoIterator::into_iter(arr).collect()
+    }
+}
+
+impl<K> FromIterator<(K, Command)> for Command
+where
+    K: Into<String>,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (K, Command)>,
+    {
+        let mut query = Self::new(TermType::MakeObj);
+        query.term_opts = iter
+            .into_iter()
+            .map(|(key, value)| (Cow::Owned(key.into()), value))
+            .collect();
+        query
+    }
+}
+
 impl From<Value> for Datum {
     fn from(value: Value) -> Self {
         match value {
@@ -79,6 +107,10 @@ pub struct Command {
     #[doc(hidden)]
     pub args: VecDeque<super::Result<Command>>,
     opts: Option<super::Result<Datum>>,
+    // Options whose values are terms rather than plain data, like the
+    // `index` option which may be `r.desc("id")`, or the fields of an
+    // object built with `MAKE_OBJ`
+    term_opts: Vec<(Cow<'static, str>, Command)>,
     change_feed: bool,
 }
 
@@ -90,6 +122,7 @@ impl Command {
             datum: None,
             args: VecDeque::new(),
             opts: None,
+            term_opts: Vec::new(),
             change_feed: false,
         }
     }
@@ -127,6 +160,15 @@ impl Command {
         self
     }
 
+    pub(crate) fn with_term_opt(mut self, key: &'static str, value: Command) -> Self {
+        self.term_opts.push((Cow::Borrowed(key), value));
+        self
+    }
+
+    pub(crate) fn has_term_opt(&self, key: &str) -> bool {
+        self.term_opts.iter().any(|(k, _)| k == key)
+    }
+
     #[doc(hidden)]
     pub fn from_json<T>(arg: T) -> Self
     where
@@ -135,6 +177,57 @@ impl Command {
         serde_json::to_value(arg).map_err(super::Error::from).into()
     }
 
+    // Builds the query from a term already in the JSON form sent to the
+    // server, such as `[15,["users"]]`, checking each term's type along
+    // the way so a typo is caught before the query is sent
+    pub(crate) fn from_raw(value: Value) -> super::Result<Self> {
+        let term = match value {
+            Value::Array(term) => term,
+            Value::Object(map) => {
+                return map
+                    .into_iter()
+                    .map(|(key, value)| Self::from_raw(value).map(|value| (key, value)))
+                    .collect();
+            }
+            value => return Ok(value.into()),
+        };
+        let invalid = |msg: &str| err::Driver::Other(format!("invalid raw term; {}", msg));
+        let mut term = term.into_iter();
+        let typ = term
+            .next()
+            .and_then(|typ| typ.as_i64())
+            .and_then(|typ| TermType::from_i32(typ as i32))
+            .ok_or_else(|| invalid("expected a term type as the first element"))?;
+        let mut query = Self::new(typ);
+        query.change_feed = typ == TermType::Changes;
+        match term.next() {
+            Some(Value::Array(args)) => {
+                for arg in args {
+                    let arg = Self::from_raw(arg)?;
+                    query.change_feed = query.change_feed || arg.change_feed;
+                    query = query.with_arg(arg);
+                }
+            }
+            None => {}
+            Some(_) => return Err(invalid("expected the arguments as an array").into()),
+        }
+        match term.next() {
+            Some(Value::Object(opts)) => {
+                for (key, value) in opts {
+                    query
+                        .term_opts
+                        .push((Cow::Owned(key), Self::from_raw(value)?));
+                }
+            }
+            None => {}
+            Some(_) => return Err(invalid("expected the options as an object").into()),
+        }
+        if term.next().is_some() {
+            return Err(invalid("expected at most 3 elements").into());
+        }
+        Ok(query)
+    }
+
     pub(crate) fn mark_change_feed(mut self) -> Self {
         self.change_feed = true;
         self
@@ -144,6 +237,44 @@ impl Command {
         self.change_feed
     }
 
+    /// Whether running the query may change data or the cluster's
+    /// configuration
+    ///
+    /// Functions passed to `for_each`, as well as anything nested inside
+    /// other commands, are looked at too.
+    pub fn is_write(&self) -> bool {
+        let writes = matches!(
+            self.typ,
+            TermType::Insert
+                | TermType::Update
+                | TermType::Replace
+                | TermType::Delete
+                | TermType::Sync
+                | TermType::ForEach
+                | TermType::DbCreate
+                | TermType::DbDrop
+                | TermType::TableCreate
+                | TermType::TableDrop
+                | TermType::IndexCreate
+                | TermType::IndexDrop
+                | TermType::IndexRename
+                | TermType::Reconfigure
+                | TermType::Rebalance
+                | TermType::Grant
+                | TermType::SetWriteHook
+        );
+        writes
+            || self.args.iter().flatten().any(Command::is_write)
+            || self.term_opts.iter().any(|(_, opt)| opt.is_write())
+    }
+
+    // Whether the query uses a term of this type anywhere
+    pub(crate) fn uses(&self, typ: TermType) -> bool {
+        self.typ == typ
+            || self.args.iter().flatten().any(|arg| arg.uses(typ))
+            || self.term_opts.iter().any(|(_, opt)| opt.uses(typ))
+    }
+
     pub(crate) fn into_arg<T>(self) -> Arg<T> {
         Arg {
             arg: self,
@@ -200,19 +331,53 @@ impl Serialize for Query<'_> {
             },
             _ => {
                 let typ = cmd.typ as i32;
+                let args = to_query_result(&cmd.args).map_err(ser::Error::custom)?;
                 match &cmd.opts {
-                    Some(Ok(map)) => (
-                        typ,
-                        to_query_result(&cmd.args).map_err(ser::Error::custom)?,
-                        map,
-                    )
-                        .serialize(serializer),
-                    None => (typ, to_query_result(&cmd.args).map_err(ser::Error::custom)?)
-                        .serialize(serializer),
                     Some(Err(error)) => Err(ser::Error::custom(error)),
+                    None if cmd.term_opts.is_empty() => (typ, args).serialize(serializer),
+                    opts => {
+                        let opts = Opts {
+                            datum: opts.as_ref().and_then(|opts| opts.as_ref().ok()),
+                            terms: &cmd.term_opts,
+                        };
+                        (typ, args, opts).serialize(serializer)
+                    }
+                }
+            }
+        }
+    }
+}
+
+struct Opts<'a> {
+    datum: Option<&'a Datum>,
+    terms: &'a [(Cow<'static, str>, Command)],
+}
+
+impl Serialize for Opts<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if self.terms.is_empty() {
+            return self.datum.serialize(serializer);
+        }
+        let mut map = serializer.serialize_map(None)?;
+        match self.datum {
+            Some(Datum::Object(datum)) => {
+                for (key, value) in datum {
+                    map.serialize_entry(key, value)?;
                 }
             }
+            Some(datum) => {
+                let msg = format!("expected the options as an object, got `{:?}`", datum);
+                return Err(ser::Error::custom(msg));
+            }
+            None => {}
+        }
+        for (key, value) in self.terms {
+            map.serialize_entry(key, &Query(value))?;
         }
+        map.end()
     }
 }
 
@@ -262,6 +427,11 @@ where
         self
     }
 
+    pub(crate) fn with_term_opt(mut self, key: &'static str, value: Command) -> Self {
+        self.arg = self.arg.with_term_opt(key, value);
+        self
+    }
+
     pub(crate) fn into_cmd(self) -> Command {
         match self.opts {
             Some(opts) => self.arg.with_opts(opts),
@@ -321,3 +491,52 @@ impl Serialize for Db {
         Query(&cmd).serialize(serializer)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Query;
+    use crate::{r, Command};
+    use ql2::term::TermType;
+    use serde_json::json;
+
+    fn serialise(query: &Command) -> String {
+        serde_json::to_string(&Query(query)).unwrap()
+    }
+
+    #[test]
+    fn raw_term_is_sent_as_is() {
+        let raw = json!([41, [[15, ["users"]]], {"index": [74, ["age"]]}]);
+        let query = Command::from_raw(raw).unwrap();
+        let serialised = serialise(&query);
+        let expected = r#"[41,[[15,["users"]]],{"index":[74,["age"]]}]"#;
+        assert_eq!(serialised, expected);
+        assert_eq!(
+            serialised,
+            serialise(&r.table("users").order_by(r.index(r.desc("age"))))
+        );
+    }
+
+    #[test]
+    fn non_object_opts_are_an_error() {
+        let query = Command::new(TermType::OrderBy)
+            .with_opts(json!(["age"]))
+            .with_term_opt("index", r.expr("age"));
+        assert!(serde_json::to_string(&Query(&query)).is_err());
+    }
+
+    #[test]
+    fn raw_changefeed_is_noticed() {
+        let query = Command::from_raw(json!([152, [[15, ["users"]]]])).unwrap();
+        assert!(query.change_feed());
+        let query = Command::from_raw(json!([15, ["users"]])).unwrap();
+        assert!(!query.change_feed());
+    }
+
+    #[test]
+    fn raw_term_must_be_a_term() {
+        assert!(Command::from_raw(json!([99999, []])).is_err());
+        assert!(Command::from_raw(json!(["users"])).is_err());
+        assert!(Command::from_raw(json!([15, "users"])).is_err());
+        assert!(Command::from_raw(json!([15, ["users"], {}, 1])).is_err());
+    }
+}
@@ -0,0 +1,33 @@
+use futures::TryStreamExt;
+use reql::{func, r};
+use serde_json::json;
+
+#[tokio::test]
+async fn arithmetic() -> reql::Result<()> {
+    let conn = r.connect(()).await?;
+
+    let orders = json!([
+        {"price": 2.5, "qty": 4},
+        {"price": 10, "qty": 3},
+    ]);
+    let totals: Option<Vec<f64>> = r
+        .expr(orders)
+        .map(func!(
+            |order| order.clone().bracket("price") * order.bracket("qty")
+        ))
+        .run(&conn)
+        .try_next()
+        .await?;
+    assert_eq!(totals, Some(vec![10.0, 30.0]));
+
+    // `+` concatenates strings and arrays too
+    let greeting: Option<String> = (r.expr("hello ") + "world").run(&conn).try_next().await?;
+    assert_eq!(greeting.as_deref(), Some("hello world"));
+    let list: Option<Vec<u32>> = (r.expr([1, 2]) + [3]).run(&conn).try_next().await?;
+    assert_eq!(list, Some(vec![1, 2, 3]));
+
+    let rem: Option<i64> = (r.expr(17) % 5).run(&conn).try_next().await?;
+    assert_eq!(rem, Some(2));
+
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use futures::TryStreamExt;
+use reql::cmd::run::Options;
+use reql::{r, Error, Runtime};
+use serde_json::Value;
+
+#[tokio::test]
+async fn array_limit() -> reql::Result<()> {
+    env_logger::init();
+    let conn = r.connect(()).await?;
+
+    let query = || r.range(r.expr(10)).coerce_to("array");
+    let res = query()
+        .run::<_, Value>(r.args((&conn, Options::new().array_limit(5))))
+        .try_next()
+        .await;
+    match res {
+        Err(Error::Runtime(Runtime::ResourceLimit(msg))) => {
+            assert!(msg.contains("Array over size limit `5`"), "{}", msg);
+        }
+        res => panic!("unexpected result: {:?}", res),
+    }
+
+    let array: Option<Vec<u32>> = query()
+        .run(r.args((&conn, Options::new().array_limit(10))))
+        .try_next()
+        .await?;
+    assert_eq!(array.map(|array| array.len()), Some(10));
+
+    Ok(())
+}
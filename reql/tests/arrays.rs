@@ -0,0 +1,61 @@
+use futures::TryStreamExt;
+use reql::{func, r};
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn arrays() -> reql::Result<()> {
+    let conn = r.connect(()).await?;
+
+    let _ = r
+        .table_create("arrays")
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await;
+    let _ = r
+        .table("arrays")
+        .insert(json!({"id": 1, "tags": ["rust"]}))
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await;
+
+    // Push to the list atomically, on the server
+    let _ = r
+        .table("arrays")
+        .get(1)
+        .update(func!(|doc| r.object(r.args([
+            r.expr("tags"),
+            doc.bracket("tags").append("rethinkdb"),
+        ]))))
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await?;
+    let tags: Option<Vec<String>> = r
+        .table("arrays")
+        .get(1)
+        .bracket("tags")
+        .run(&conn)
+        .try_next()
+        .await?;
+    assert_eq!(tags, Some(vec!["rust".to_owned(), "rethinkdb".to_owned()]));
+
+    let list: Option<Vec<u32>> = r
+        .expr([2, 4])
+        .prepend(1)
+        .insert_at(r.args((2, 3)))
+        .change_at(r.args((3, 5)))
+        .delete_at(0)
+        .set_insert(2)
+        .set_union([6])
+        .run(&conn)
+        .try_next()
+        .await?;
+    assert_eq!(list, Some(vec![2, 3, 5, 6]));
+
+    let doc = json!({"a": 1, "b": 2});
+    let keys: Option<Vec<String>> = r.expr(doc.clone()).keys().run(&conn).try_next().await?;
+    assert_eq!(keys, Some(vec!["a".to_owned(), "b".to_owned()]));
+    let values: Option<Vec<u32>> = r.expr(doc).values().run(&conn).try_next().await?;
+    assert_eq!(values, Some(vec![1, 2]));
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use futures::TryStreamExt;
+use reql::cmd::between::{Options, Status};
+use reql::r;
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn between() -> reql::Result<()> {
+    let conn = r.connect(()).await?;
+
+    let _ = r
+        .table_create("between")
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await;
+    let _ = r
+        .table("between")
+        .index_create("age")
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await;
+    let _ = r
+        .table("between")
+        .index_wait(())
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await?;
+    let _ = r
+        .table("between")
+        .insert(json!([
+            {"id": 1, "age": 10},
+            {"id": 2, "age": 20},
+            {"id": 3, "age": 30},
+            {"id": 4, "age": 40},
+        ]))
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await?;
+
+    // Closed on the left, open on the right, so 20 is in and 40 is out
+    let opts = Options::new()
+        .index("age")
+        .left_bound(Status::Closed)
+        .right_bound(Status::Open);
+    let mut ids: Vec<u32> = r
+        .table("between")
+        .between(r.args((20, 40, opts)))
+        .get_field("id")
+        .run::<_, u32>(&conn)
+        .try_collect()
+        .await?;
+    ids.sort_unstable();
+    assert_eq!(ids, [2, 3]);
+
+    let opts = Options::new().index("age");
+    let mut ids: Vec<u32> = r
+        .table("between")
+        .between(r.args((r.minval(), r.expr(30), opts)))
+        .get_field("id")
+        .run::<_, u32>(&conn)
+        .try_collect()
+        .await?;
+    ids.sort_unstable();
+    assert_eq!(ids, [1, 2]);
+
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use futures::TryStreamExt;
+use reql::{func, r};
+
+#[tokio::test]
+async fn branch() -> reql::Result<()> {
+    let conn = r.connect(()).await?;
+
+    let size: Option<String> = r
+        .branch(r.args((r.expr(15).gt(10), "big", "small")))
+        .run(&conn)
+        .try_next()
+        .await?;
+    assert_eq!(size.as_deref(), Some("big"));
+
+    // `do_` binds a value so each test can look at it
+    let size: Option<String> = r
+        .do_(r.args((
+            r.expr(5),
+            func!(|n| r.branch(r.args((
+                [
+                    (n.clone().gt(100), r.expr("huge")),
+                    (n.gt(10), r.expr("big"))
+                ],
+                r.expr("small"),
+            )))),
+        )))
+        .run(&conn)
+        .try_next()
+        .await?;
+    assert_eq!(size.as_deref(), Some("small"));
+
+    Ok(())
+}
@@ -0,0 +1,22 @@
+use futures::TryStreamExt;
+use reql::r;
+
+#[tokio::test]
+async fn cancel() -> reql::Result<()> {
+    env_logger::init();
+    let session = r.connect(()).await?;
+    let conn = session.connection()?;
+    let handle = conn.handle();
+
+    // Far more rows than fit in the first batch
+    let mut rows = Box::pin(r.range(r.expr(1_000_000)).run::<_, u64>(conn));
+    assert_eq!(rows.try_next().await?, Some(0));
+    handle.cancel().await?;
+    while rows.try_next().await?.is_some() {}
+
+    // The session is still usable afterwards
+    let val: Option<u32> = r.expr(1).run(&session).try_next().await?;
+    assert_eq!(val, Some(1));
+
+    Ok(())
+}
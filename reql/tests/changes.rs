@@ -0,0 +1,140 @@
+use futures::TryStreamExt;
+use reql::cmd::changes::Options;
+use reql::r;
+use reql::types::{Change, ChangeType, State};
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn changes() -> reql::Result<()> {
+    env_logger::init();
+    let feed_conn = r.connect(()).await?;
+    let conn = r.connect(()).await?;
+
+    let _ = r
+        .table_create("changes")
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await;
+
+    let opts = Options::new().include_states(true);
+    let mut feed = r
+        .table("changes")
+        .changes(opts)
+        .run::<_, Change<Value>>(&feed_conn);
+
+    // Wait for the feed to be ready before making any changes
+    while let Some(change) = feed.try_next().await? {
+        if change.is_ready() {
+            break;
+        }
+    }
+
+    let _ = r
+        .table("changes")
+        .insert(json!({"id": 1}))
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await?;
+
+    let change = feed.try_next().await?.unwrap();
+    assert_eq!(change.old_val, None);
+    assert_eq!(change.new_val, Some(json!({"id": 1})));
+
+    // Dropping the feed stops it, so its session can run queries again
+    drop(feed);
+    let val: Option<u32> = r.expr(1).run(&feed_conn).try_next().await?;
+    assert_eq!(val, Some(1));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn point_changes() -> reql::Result<()> {
+    let feed_conn = r.connect(()).await?;
+    let conn = r.connect(()).await?;
+
+    let _ = r
+        .table_create("point_changes")
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await;
+    let _ = r
+        .table("point_changes")
+        .insert(json!({"id": 1, "n": 1}))
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await?;
+
+    let opts = Options::new()
+        .include_initial(true)
+        .include_states(true)
+        .include_types(true);
+    let mut feed = r
+        .table("point_changes")
+        .get(1)
+        .changes(opts)
+        .run::<_, Change<Value>>(&feed_conn);
+
+    let change = feed.try_next().await?.unwrap();
+    assert_eq!(change.state, Some(State::Initializing));
+    let change = feed.try_next().await?.unwrap();
+    assert_eq!(change.result_type, Some(ChangeType::Initial));
+    assert_eq!(change.new_val, Some(json!({"id": 1, "n": 1})));
+    let change = feed.try_next().await?.unwrap();
+    assert!(change.is_ready());
+
+    let _ = r
+        .table("point_changes")
+        .get(1)
+        .update(json!({"n": 2}))
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await?;
+
+    let change = feed.try_next().await?.unwrap();
+    assert_eq!(change.result_type, Some(ChangeType::Change));
+    assert_eq!(change.old_val, Some(json!({"id": 1, "n": 1})));
+    assert_eq!(change.new_val, Some(json!({"id": 1, "n": 2})));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn filtered_changes() -> reql::Result<()> {
+    let feed_conn = r.connect(()).await?;
+    let conn = r.connect(()).await?;
+
+    let _ = r
+        .table_create("filtered_changes")
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await;
+
+    let mut feed = r
+        .table("filtered_changes")
+        .filter(json!({"active": true}))
+        .changes(Options::new().include_states(true))
+        .run::<_, Change<Value>>(&feed_conn);
+
+    while let Some(change) = feed.try_next().await? {
+        if change.is_ready() {
+            break;
+        }
+    }
+
+    // Only the second insert matches the filter
+    let _ = r
+        .table("filtered_changes")
+        .insert(json!([
+            {"id": 1, "active": false},
+            {"id": 2, "active": true},
+        ]))
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await?;
+
+    let change = feed.try_next().await?.unwrap();
+    assert_eq!(change.new_val, Some(json!({"id": 2, "active": true})));
+
+    Ok(())
+}
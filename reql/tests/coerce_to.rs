@@ -0,0 +1,45 @@
+use futures::TryStreamExt;
+use reql::r;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+#[tokio::test]
+async fn coerce_to() -> reql::Result<()> {
+    let conn = r.connect(()).await?;
+
+    let _ = r
+        .table_create("coerce_to")
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await;
+    let _ = r
+        .table("coerce_to")
+        .insert(json!([{"id": "a"}, {"id": "b"}, {"id": "c"}]))
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await?;
+
+    let query = r.table("coerce_to").get_all(r.args(["a", "c"]));
+    let typ: Option<String> = query.clone().type_of().run(&conn).try_next().await?;
+    assert_eq!(typ.as_deref(), Some("SELECTION<STREAM>"));
+
+    // Once coerced, the whole selection comes back as a single array
+    let mut docs: Vec<Vec<Value>> = query.coerce_to("array").run(&conn).try_collect().await?;
+    assert_eq!(docs.len(), 1);
+    let mut docs = docs.remove(0);
+    docs.sort_unstable_by_key(|doc| doc["id"].to_string());
+    assert_eq!(docs, [json!({"id": "a"}), json!({"id": "c"})]);
+
+    let object: Option<HashMap<String, u32>> = r
+        .expr(json!([["a", 1], ["b", 2]]))
+        .coerce_to("object")
+        .run(&conn)
+        .try_next()
+        .await?;
+    let expected: HashMap<_, _> = vec![("a".to_owned(), 1), ("b".to_owned(), 2)]
+        .into_iter()
+        .collect();
+    assert_eq!(object, Some(expected));
+
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use futures::TryStreamExt;
+use reql::{func, r};
+use serde_json::json;
+
+#[tokio::test]
+async fn concat_map() -> reql::Result<()> {
+    let conn = r.connect(()).await?;
+
+    let heroes = json!([
+        {"name": "thor", "defeated": ["loki", "hela"]},
+        {"name": "hulk", "defeated": []},
+        {"name": "iron man", "defeated": ["ultron"]},
+    ]);
+    let defeated: Option<Vec<String>> = r
+        .expr(heroes)
+        .concat_map(func!(|hero| hero.bracket("defeated")))
+        .run(&conn)
+        .try_next()
+        .await?;
+    assert_eq!(
+        defeated,
+        Some(vec![
+            "loki".to_owned(),
+            "hela".to_owned(),
+            "ultron".to_owned()
+        ])
+    );
+
+    Ok(())
+}
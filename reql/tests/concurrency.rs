@@ -9,12 +9,19 @@ async fn concurrency() -> reql::Result<()> {
 
     let num = 10_000;
     for i in 0..num {
-        streams.push(r.expr(format!("message {}", i)).run::<_, String>(&conn));
+        let query = r.expr(format!("message {}", i)).run::<_, String>(&conn);
+        // Each response must reach the query that asked for it
+        streams.push(query.map_ok(move |msg| (i, msg)));
     }
 
     let mut list = select_all(streams);
 
-    while list.try_next().await?.is_some() {}
+    let mut received = 0;
+    while let Some((i, msg)) = list.try_next().await? {
+        assert_eq!(msg, format!("message {}", i));
+        received += 1;
+    }
+    assert_eq!(received, num);
 
     Ok(())
 }
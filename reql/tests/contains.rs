@@ -0,0 +1,36 @@
+use futures::TryStreamExt;
+use reql::{func, r};
+use serde_json::json;
+
+#[tokio::test]
+async fn contains() -> reql::Result<()> {
+    env_logger::init();
+    let conn = r.connect(()).await?;
+    let tags = || r.expr(json!(["urgent", "bug", "ui"]));
+
+    let found: Option<bool> = tags().contains("urgent").run(&conn).try_next().await?;
+    assert_eq!(found, Some(true));
+
+    let found: Option<bool> = tags()
+        .contains(r.args(["bug", "backend"]))
+        .run(&conn)
+        .try_next()
+        .await?;
+    assert_eq!(found, Some(false));
+
+    let found: Option<bool> = r
+        .expr(json!([3, 8, 12]))
+        .contains(func!(|n| n.gt(10)))
+        .run(&conn)
+        .try_next()
+        .await?;
+    assert_eq!(found, Some(true));
+
+    let offsets: Option<Vec<usize>> = tags().offsets_of("ui").run(&conn).try_next().await?;
+    assert_eq!(offsets, Some(vec![2]));
+
+    let empty: Option<bool> = r.expr(json!([])).is_empty().run(&conn).try_next().await?;
+    assert_eq!(empty, Some(true));
+
+    Ok(())
+}
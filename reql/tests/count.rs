@@ -0,0 +1,47 @@
+use futures::TryStreamExt;
+use reql::{func, r, Error};
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn count() -> reql::Result<()> {
+    env_logger::init();
+    let conn = r.connect(()).await?;
+
+    let _ = r
+        .table_create("count")
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await;
+
+    let _ = r
+        .table("count")
+        .insert(json!([
+            {"id": 1, "age": 12},
+            {"id": 2, "age": 30},
+            {"id": 3, "age": 45},
+        ]))
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await?;
+
+    let all: Option<u32> = r.table("count").count(()).run(&conn).try_next().await?;
+    assert_eq!(all, Some(3));
+
+    let adults: Option<u32> = r
+        .table("count")
+        .count(func!(|doc| doc.bracket("age").ge(r.expr(18))))
+        .run(&conn)
+        .try_next()
+        .await?;
+    assert_eq!(adults, Some(2));
+
+    let oldest: Option<Value> = r.table("count").max("age").run(&conn).try_next().await?;
+    assert_eq!(oldest, Some(json!({"id": 3, "age": 45})));
+
+    // The average of nothing is an error on the server
+    let empty: [u32; 0] = [];
+    let avg = r.expr(empty).avg(()).run::<_, f64>(&conn).try_next().await;
+    assert!(matches!(avg, Err(Error::Runtime(_))));
+
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use futures::TryStreamExt;
+use reql::cmd::run::Options;
+use reql::r;
+
+#[tokio::test]
+async fn cursor() -> reql::Result<()> {
+    env_logger::init();
+    let conn = r.connect(()).await?;
+
+    // Force the server to send the results in several batches
+    let opts = Options::new().max_batch_rows(10);
+    let nums: Vec<_> = r
+        .range(r.expr(100))
+        .run::<_, u32>(r.args((&conn, opts.clone())))
+        .try_collect()
+        .await?;
+    assert_eq!(nums, (0..100).collect::<Vec<_>>());
+
+    // Abandon a stream after the first batch
+    let mut query = r.range(r.expr(100)).run::<_, u32>(r.args((&conn, opts)));
+    assert_eq!(query.try_next().await?, Some(0));
+    drop(query);
+
+    // The session should still be usable afterwards
+    let val: Option<u32> = r.expr(200).run(&conn).try_next().await?;
+    assert_eq!(val, Some(200));
+
+    Ok(())
+}
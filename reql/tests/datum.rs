@@ -0,0 +1,29 @@
+use futures::TryStreamExt;
+use reql::r;
+use reql::types::{DateTime, Datum};
+use serde_json::json;
+use std::convert::TryFrom;
+
+#[tokio::test]
+async fn datum() -> reql::Result<()> {
+    env_logger::init();
+    let conn = r.connect(()).await?;
+
+    let now: Option<Datum> = r.now().run(&conn).try_next().await?;
+    assert!(DateTime::try_from(now.unwrap()).is_ok());
+
+    let doc: Option<Datum> = r
+        .expr(json!({"name": "reql", "tags": ["a", "b"]}))
+        .run(&conn)
+        .try_next()
+        .await?;
+    match doc {
+        Some(Datum::Object(map)) => {
+            assert_eq!(map["name"], Datum::String("reql".to_owned()));
+            assert!(matches!(map["tags"], Datum::Array(_)));
+        }
+        doc => panic!("unexpected result: {:?}", doc),
+    }
+
+    Ok(())
+}
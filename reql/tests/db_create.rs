@@ -0,0 +1,39 @@
+use futures::TryStreamExt;
+use reql::types::DbWriteStatus;
+use reql::{r, Availability, Error, Runtime};
+
+#[tokio::test]
+async fn db_create_and_drop() -> reql::Result<()> {
+    env_logger::init();
+    let conn = r.connect(()).await?;
+
+    let _ = r
+        .db_drop("db_create")
+        .run::<_, DbWriteStatus>(&conn)
+        .try_next()
+        .await;
+
+    let status: Option<DbWriteStatus> = r.db_create("db_create").run(&conn).try_next().await?;
+    assert_eq!(status.unwrap().dbs_created, 1);
+
+    let dbs: Option<Vec<String>> = r.db_list().run(&conn).try_next().await?;
+    assert!(dbs.unwrap().contains(&"db_create".to_owned()));
+
+    // The database exists already
+    let result = r
+        .db_create("db_create")
+        .run::<_, DbWriteStatus>(&conn)
+        .try_next()
+        .await;
+    assert!(matches!(
+        result,
+        Err(Error::Runtime(Runtime::Availability(
+            Availability::OpFailed(_)
+        )))
+    ));
+
+    let status: Option<DbWriteStatus> = r.db_drop("db_create").run(&conn).try_next().await?;
+    assert_eq!(status.unwrap().dbs_dropped, 1);
+
+    Ok(())
+}
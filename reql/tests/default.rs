@@ -0,0 +1,42 @@
+use futures::TryStreamExt;
+use reql::{func, r};
+use serde_json::json;
+
+#[tokio::test]
+async fn default() -> reql::Result<()> {
+    let conn = r.connect(()).await?;
+
+    let users = json!([
+        {"name": "ada", "nickname": "countess"},
+        {"name": "alan", "nickname": null},
+        {"name": "grace"},
+    ]);
+    let nicknames: Option<Vec<String>> = r
+        .expr(users.clone())
+        .map(func!(|user| user
+            .get_field("nickname")
+            .default("anonymous")))
+        .run(&conn)
+        .try_next()
+        .await?;
+    assert_eq!(
+        nicknames,
+        Some(vec![
+            "countess".to_owned(),
+            "anonymous".to_owned(),
+            "anonymous".to_owned(),
+        ])
+    );
+
+    // An explicit `null` counts as missing too
+    let names: Option<Vec<String>> = r
+        .expr(users)
+        .filter(func!(|user| user.get_field("nickname").default("").ne("")))
+        .get_field("name")
+        .run(&conn)
+        .try_next()
+        .await?;
+    assert_eq!(names, Some(vec!["ada".to_owned()]));
+
+    Ok(())
+}
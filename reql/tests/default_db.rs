@@ -0,0 +1,54 @@
+use futures::TryStreamExt;
+use reql::cmd::connect::Options;
+use reql::r;
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn default_db() -> reql::Result<()> {
+    env_logger::init();
+    let conn = r.connect(Options::new().db("default_db")).await?;
+
+    let _ = r
+        .db_create("default_db")
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await;
+    for db in ["default_db", "test"] {
+        let _ = r
+            .db(db)
+            .table_create("where")
+            .run::<_, Value>(&conn)
+            .try_next()
+            .await;
+        let _ = r
+            .db(db)
+            .table("where")
+            .insert(json!({"id": 1, "db": db}))
+            .run::<_, Value>(&conn)
+            .try_next()
+            .await?;
+    }
+
+    // A bare table is looked up in the database the session was opened with
+    let db: Option<String> = r
+        .table("where")
+        .get(1)
+        .get_field("db")
+        .run(&conn)
+        .try_next()
+        .await?;
+    assert_eq!(db.as_deref(), Some("default_db"));
+
+    // while an explicit db wins
+    let db: Option<String> = r
+        .db("test")
+        .table("where")
+        .get(1)
+        .get_field("db")
+        .run(&conn)
+        .try_next()
+        .await?;
+    assert_eq!(db.as_deref(), Some("test"));
+
+    Ok(())
+}
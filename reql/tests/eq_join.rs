@@ -0,0 +1,87 @@
+use futures::TryStreamExt;
+use reql::r;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Player {
+    id: u32,
+    player: String,
+    game: String,
+    genre: String,
+}
+
+#[tokio::test]
+async fn eq_join() -> reql::Result<()> {
+    let conn = r.connect(()).await?;
+
+    for table in &["eq_join_players", "eq_join_games"] {
+        let _ = r
+            .table_create(*table)
+            .run::<_, Value>(&conn)
+            .try_next()
+            .await;
+    }
+    let _ = r
+        .table("eq_join_games")
+        .index_create("game")
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await;
+    let _ = r
+        .table("eq_join_games")
+        .index_wait(())
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await?;
+
+    let _ = r
+        .table("eq_join_players")
+        .insert(json!([
+            {"id": 1, "player": "ada", "game": "chess"},
+            {"id": 2, "player": "alan", "game": "go"},
+        ]))
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await?;
+    let _ = r
+        .table("eq_join_games")
+        .insert(json!([
+            {"id": 10, "game": "chess", "genre": "strategy"},
+            {"id": 20, "game": "go", "genre": "abstract"},
+        ]))
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await?;
+
+    // Both sides have an `id`, so drop the right one before merging them
+    let mut players: Vec<Player> = r
+        .table("eq_join_players")
+        .eq_join(r.args(("game", r.table("eq_join_games"), r.index("game"))))
+        .without(json!({"right": {"id": true}}))
+        .zip()
+        .run(&conn)
+        .try_collect()
+        .await?;
+    players.sort_unstable_by_key(|player| player.id);
+
+    assert_eq!(
+        players,
+        [
+            Player {
+                id: 1,
+                player: "ada".to_owned(),
+                game: "chess".to_owned(),
+                genre: "strategy".to_owned(),
+            },
+            Player {
+                id: 2,
+                player: "alan".to_owned(),
+                game: "go".to_owned(),
+                genre: "abstract".to_owned(),
+            },
+        ]
+    );
+
+    Ok(())
+}
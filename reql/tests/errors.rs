@@ -0,0 +1,22 @@
+use futures::TryStreamExt;
+use reql::{r, Error, Frame, Runtime};
+
+#[tokio::test]
+async fn errors() -> reql::Result<()> {
+    env_logger::init();
+    let conn = r.connect(()).await?;
+
+    let error = r
+        .branch(r.args((r.expr(true), r.expr(1) + "a", r.expr(0))))
+        .run::<_, u32>(&conn)
+        .try_next()
+        .await
+        .unwrap_err();
+    assert!(matches!(error, Error::Runtime(Runtime::QueryLogic(_))));
+    // The server points at the second argument of `branch`
+    let backtrace = error.backtrace().unwrap();
+    assert_eq!(backtrace.0.first(), Some(&Frame::Pos(1)));
+    assert!(error.to_string().contains("in query > argument 1"));
+
+    Ok(())
+}
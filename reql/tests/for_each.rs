@@ -0,0 +1,49 @@
+use futures::TryStreamExt;
+use reql::types::WriteStatus;
+use reql::{func, r};
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn for_each() -> reql::Result<()> {
+    env_logger::init();
+    let conn = r.connect(()).await?;
+
+    for table in &["for_each_source", "for_each_dest"] {
+        let _ = r
+            .table_create(*table)
+            .run::<_, Value>(&conn)
+            .try_next()
+            .await;
+        let _ = r
+            .table(*table)
+            .delete(())
+            .run::<_, WriteStatus>(&conn)
+            .try_next()
+            .await?;
+    }
+
+    let _ = r
+        .table("for_each_source")
+        .insert(json!([{"id": 1}, {"id": 2}, {"id": 3}]))
+        .run::<_, WriteStatus>(&conn)
+        .try_next()
+        .await?;
+
+    let status: Option<WriteStatus> = r
+        .table("for_each_source")
+        .for_each(func!(|row| r.table("for_each_dest").insert(row)))
+        .run(&conn)
+        .try_next()
+        .await?;
+    assert_eq!(status.unwrap().inserted, 3);
+
+    let count: Option<u64> = r
+        .table("for_each_dest")
+        .count(())
+        .run(&conn)
+        .try_next()
+        .await?;
+    assert_eq!(count, Some(3));
+
+    Ok(())
+}
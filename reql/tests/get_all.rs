@@ -0,0 +1,68 @@
+use futures::TryStreamExt;
+use reql::{func, r};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct User {
+    id: u32,
+    email: String,
+}
+
+#[tokio::test]
+async fn get_and_get_all() -> reql::Result<()> {
+    env_logger::init();
+    let conn = r.connect(()).await?;
+
+    let _ = r
+        .table_create("get_all")
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await;
+
+    let _ = r
+        .table("get_all")
+        .index_create(r.args(("email", func!(|doc| doc.bracket("email")))))
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await;
+
+    let _ = r
+        .table("get_all")
+        .index_wait(())
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await?;
+
+    let users = json!([
+        {"id": 1, "email": "ada@example.com"},
+        {"id": 2, "email": "alan@example.com"},
+    ]);
+    let _ = r
+        .table("get_all")
+        .insert(users)
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await?;
+
+    let user: Option<User> = r.table("get_all").get(1).run(&conn).try_next().await?;
+    assert_eq!(
+        user.map(|user| user.email).as_deref(),
+        Some("ada@example.com")
+    );
+
+    // A missing document is `null`, which deserialises as `None`
+    let missing: Option<Option<User>> = r.table("get_all").get(3).run(&conn).try_next().await?;
+    assert_eq!(missing, Some(None));
+
+    let users: Vec<User> = r
+        .table("get_all")
+        .get_all(r.args(("alan@example.com", r.index("email"))))
+        .run(&conn)
+        .try_collect()
+        .await?;
+    assert_eq!(users.len(), 1);
+    assert_eq!(users[0].id, 2);
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use futures::TryStreamExt;
+use reql::{r, Error, Runtime};
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn get_field() -> reql::Result<()> {
+    let conn = r.connect(()).await?;
+    let doc = json!({"name": "ada", "address": {"city": "London"}});
+
+    let city: Option<String> = r
+        .expr(doc.clone())
+        .get_field("address")
+        .bracket("city")
+        .run(&conn)
+        .try_next()
+        .await?;
+    assert_eq!(city.as_deref(), Some("London"));
+
+    let has: Option<bool> = r
+        .expr(doc.clone())
+        .has_fields(json!({"address": {"zip": true}}))
+        .run(&conn)
+        .try_next()
+        .await?;
+    assert_eq!(has, Some(false));
+
+    // A missing field is an error on a single object
+    let missing = r
+        .expr(doc.clone())
+        .get_field("age")
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await;
+    assert!(matches!(
+        missing,
+        Err(Error::Runtime(Runtime::NonExistence(_)))
+    ));
+
+    // but sequences just skip the objects without it
+    let names: Option<Vec<String>> = r
+        .expr(json!([doc, {"age": 36}]))
+        .get_field("name")
+        .run(&conn)
+        .try_next()
+        .await?;
+    assert_eq!(names, Some(vec!["ada".to_owned()]));
+
+    Ok(())
+}
@@ -0,0 +1,90 @@
+use futures::TryStreamExt;
+use reql::cmd::insert::{self, Conflict};
+use reql::cmd::{get_nearest, index_create, Unit};
+use reql::r;
+use reql::types::geo::{Nearest, Point};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Place {
+    id: String,
+    location: Point,
+}
+
+#[tokio::test]
+async fn get_nearest() -> reql::Result<()> {
+    env_logger::init();
+    let conn = r.connect(()).await?;
+
+    let _ = r
+        .table_create("get_nearest")
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await;
+    let _ = r
+        .table("get_nearest")
+        .index_create(r.args(("location", index_create::Options::new().geo(true))))
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await;
+    let _ = r
+        .table("get_nearest")
+        .index_wait(())
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await?;
+
+    let places = [
+        ("ferry_building", -122.3937, 37.7955),
+        ("coit_tower", -122.4058, 37.8024),
+        ("golden_gate", -122.4783, 37.8199),
+        ("los_angeles", -118.2437, 34.0522),
+    ];
+    let places: Vec<_> = places
+        .iter()
+        .map(|&(id, lon, lat)| Place {
+            id: id.to_owned(),
+            location: Point::new(lon, lat).unwrap(),
+        })
+        .collect();
+    let _ = r
+        .table("get_nearest")
+        .insert(r.args((places, insert::Options::new().conflict(Conflict::Replace))))
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await?;
+
+    // Los Angeles is well over 10 km away
+    let union_square = Point::new(-122.4075, 37.7880)?;
+    let opts = get_nearest::Options::new()
+        .max_dist(10.0)
+        .unit(Unit::Kilometer);
+    let nearest: Option<Vec<Nearest<Place>>> = r
+        .table("get_nearest")
+        .get_nearest(r.args((union_square, r.index("location"), opts)))
+        .run(&conn)
+        .try_next()
+        .await?;
+    let nearest = nearest.unwrap();
+    let ids: Vec<_> = nearest.iter().map(|near| near.doc.id.as_str()).collect();
+    assert_eq!(ids, ["ferry_building", "coit_tower", "golden_gate"]);
+    assert!(nearest.iter().all(|near| near.dist < 10.0));
+
+    // The same distance comes back in miles when asked to
+    let in_km = nearest[0].dist;
+    let opts = get_nearest::Options::new()
+        .max_results(1)
+        .unit(Unit::InternationalMile);
+    let nearest: Option<Vec<Nearest<Place>>> = r
+        .table("get_nearest")
+        .get_nearest(r.args((union_square, r.index("location"), opts)))
+        .run(&conn)
+        .try_next()
+        .await?;
+    let in_miles = &nearest.unwrap()[0];
+    assert_eq!(in_miles.doc.id, "ferry_building");
+    assert!((in_miles.dist * 1.609344 - in_km).abs() < 0.001);
+
+    Ok(())
+}
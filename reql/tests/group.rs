@@ -0,0 +1,56 @@
+use futures::TryStreamExt;
+use reql::r;
+use reql::types::GroupedData;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+
+#[tokio::test]
+async fn group_count() -> reql::Result<()> {
+    env_logger::init();
+    let conn = r.connect(()).await?;
+
+    let _ = r
+        .table_create("group_count")
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await;
+
+    let docs = json!([
+        {"id": 1, "kind": "cat", "colour": "black"},
+        {"id": 2, "kind": "dog", "colour": "black"},
+        {"id": 3, "kind": "cat", "colour": "white"},
+        {"id": 4, "kind": "cat", "colour": "black"},
+    ]);
+    let _ = r
+        .table("group_count")
+        .insert(docs)
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await?;
+
+    let grouped: Option<GroupedData<String, u32>> = r
+        .table("group_count")
+        .group(r.expr("kind"))
+        .count(())
+        .run(&conn)
+        .try_next()
+        .await?;
+    let counts = BTreeMap::from(grouped.unwrap());
+    assert_eq!(counts["cat"], 3);
+    assert_eq!(counts["dog"], 1);
+
+    // Grouping by several fields gives each group an array as its key
+    let grouped: Option<GroupedData<(String, String), u32>> = r
+        .table("group_count")
+        .group(r.args(["kind", "colour"]))
+        .count(())
+        .run(&conn)
+        .try_next()
+        .await?;
+    let counts = BTreeMap::from(grouped.unwrap());
+    assert_eq!(counts[&("cat".to_owned(), "black".to_owned())], 2);
+    assert_eq!(counts[&("cat".to_owned(), "white".to_owned())], 1);
+    assert_eq!(counts[&("dog".to_owned(), "black".to_owned())], 1);
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use futures::TryStreamExt;
+use reql::cmd::http::{Options, ResultFormat};
+use reql::{r, Error};
+use serde_json::{json, Value};
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::thread;
+
+// Serves a single JSON response so the test doesn't depend on the internet
+fn mock_server() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    thread::spawn(move || {
+        if let Ok((mut stream, _)) = listener.accept() {
+            let mut buf = [0; 1024];
+            let _ = stream.read(&mut buf);
+            let body = r#"{"name":"reql","stars":42}"#;
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            let _ = stream.write_all(response.as_bytes());
+        }
+    });
+    format!("http://{}/repo", addr)
+}
+
+#[tokio::test]
+async fn http() -> reql::Result<()> {
+    env_logger::init();
+    let conn = r.connect(()).await?;
+
+    let url = mock_server();
+    let opts = Options::new().result_format(ResultFormat::Json).attempts(1);
+    let repo: Option<Value> = r.http(r.args((url, opts))).run(&conn).try_next().await?;
+    assert_eq!(repo, Some(json!({"name": "reql", "stars": 42})));
+
+    // Nothing is listening on port 1
+    let opts = Options::new().attempts(1).timeout(1.0);
+    let res = r
+        .http(r.args(("http://127.0.0.1:1/", opts)))
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await;
+    match res {
+        Err(Error::Runtime(_)) => {}
+        res => panic!("unexpected result: {:?}", res),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use futures::TryStreamExt;
+use reql::r;
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn ids() -> reql::Result<()> {
+    env_logger::init();
+    let conn = r.connect(()).await?;
+
+    let _ = r
+        .table_create("ids")
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await;
+    let _ = r
+        .table("ids")
+        .insert(json!([
+            {"id": 1, "body": "a long document"},
+            {"id": 2, "body": "another long document"},
+        ]))
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await?;
+
+    let mut ids: Vec<u32> = r
+        .table("ids")
+        .ids()
+        .run::<_, u32>(&conn)
+        .try_collect()
+        .await?;
+    ids.sort_unstable();
+    assert_eq!(ids, [1, 2]);
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
 use futures::TryStreamExt;
+use reql::types::IndexStatus;
 use reql::{func, r};
-use serde_json::Value;
+use serde_json::{json, Value};
 
 #[tokio::test]
 async fn index_create() -> reql::Result<()> {
@@ -31,6 +32,38 @@ async fn index_create() -> reql::Result<()> {
         .try_next()
         .await?;
 
+    let statuses: Vec<IndexStatus> = r
+        .table("comments")
+        .index_wait("author_name")
+        .run(&conn)
+        .try_collect()
+        .await?;
+    assert!(statuses.iter().all(|status| status.ready));
+
+    let _ = r
+        .table("comments")
+        .insert(json!({"id": 1, "author": {"name": "ada"}}))
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await?;
+
+    let ids: Vec<u32> = r
+        .table("comments")
+        .get_all(r.args(("ada", r.index("author_name"))))
+        .get_field("id")
+        .run::<_, u32>(&conn)
+        .try_collect()
+        .await?;
+    assert_eq!(ids, [1]);
+
+    let indexes: Option<Vec<String>> = r
+        .table("comments")
+        .index_list()
+        .run(&conn)
+        .try_next()
+        .await?;
+    assert!(indexes.unwrap().contains(&"author_name".to_owned()));
+
     let _ = r
         .table("comments")
         .index_drop("post_and_date")
@@ -0,0 +1,45 @@
+use futures::TryStreamExt;
+use reql::cmd::table_create;
+use reql::r;
+use reql::types::{DbInfo, TableInfo};
+use serde_json::Value;
+
+#[tokio::test]
+async fn info() -> reql::Result<()> {
+    env_logger::init();
+    let conn = r.connect(()).await?;
+
+    let _ = r
+        .table_create(r.args(("info", table_create::Options::new().primary_key("name"))))
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await;
+    let _ = r
+        .table("info")
+        .index_create("age")
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await;
+
+    let info: Option<TableInfo> = r.table("info").info().run(&conn).try_next().await?;
+    let info = info.unwrap();
+    assert_eq!(info.typ, "TABLE");
+    assert_eq!(info.name, "info");
+    assert_eq!(info.primary_key, "name");
+    assert_eq!(info.db.name, "test");
+    assert!(info.indexes.contains(&"age".to_owned()));
+
+    let info: Option<DbInfo> = r.db("test").info().run(&conn).try_next().await?;
+    assert_eq!(info.unwrap().typ, "DB");
+
+    let typ: Option<String> = r
+        .expr(1)
+        .info()
+        .get_field("type")
+        .run(&conn)
+        .try_next()
+        .await?;
+    assert_eq!(typ.as_deref(), Some("NUMBER"));
+
+    Ok(())
+}
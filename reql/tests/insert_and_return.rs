@@ -0,0 +1,60 @@
+use futures::TryStreamExt;
+use reql::r;
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn insert_and_return() -> reql::Result<()> {
+    env_logger::init();
+    let conn = r.connect(()).await?;
+
+    let _ = r
+        .table_create("insert_and_return")
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await;
+
+    // Without an `id` the server generates a UUID
+    let id: Option<String> = r
+        .table("insert_and_return")
+        .insert_and_return(json!({"name": "generated"}))
+        .run(&conn)
+        .try_next()
+        .await?;
+    let id = id.unwrap();
+    assert_eq!(id.len(), 36);
+    let name: Option<String> = r
+        .table("insert_and_return")
+        .get(id.as_str())
+        .get_field("name")
+        .run(&conn)
+        .try_next()
+        .await?;
+    assert_eq!(name.as_deref(), Some("generated"));
+
+    // An explicit key comes back as it was given
+    let _ = r
+        .table("insert_and_return")
+        .get(7)
+        .delete(())
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await?;
+    let id: Option<u32> = r
+        .table("insert_and_return")
+        .insert_and_return(json!({"id": 7, "name": "explicit"}))
+        .run(&conn)
+        .try_next()
+        .await?;
+    assert_eq!(id, Some(7));
+
+    // Inserting it again fails with the server's message
+    let res = r
+        .table("insert_and_return")
+        .insert_and_return(json!({"id": 7}))
+        .run::<_, u32>(&conn)
+        .try_next()
+        .await;
+    assert!(res.is_err());
+
+    Ok(())
+}
@@ -0,0 +1,26 @@
+use futures::TryStreamExt;
+use reql::cmd::js::Options;
+use reql::{r, Error};
+
+#[tokio::test]
+async fn js() -> reql::Result<()> {
+    env_logger::init();
+    let conn = r.connect(()).await?;
+
+    let two: Option<u32> = r.js("1 + 1").run(&conn).try_next().await?;
+    assert_eq!(two, Some(2));
+
+    let res = r
+        .js(r.args(("while(true) {}", Options::new().timeout(0.1))))
+        .run::<_, u32>(&conn)
+        .try_next()
+        .await;
+    match res {
+        Err(Error::Runtime(error)) => {
+            assert!(error.to_string().contains("timed out"), "{}", error);
+        }
+        res => panic!("unexpected result: {:?}", res),
+    }
+
+    Ok(())
+}
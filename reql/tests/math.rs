@@ -0,0 +1,35 @@
+use futures::TryStreamExt;
+use reql::cmd::random::Options;
+use reql::r;
+use serde_json::Value;
+
+#[tokio::test]
+async fn math() -> reql::Result<()> {
+    env_logger::init();
+    let conn = r.connect(()).await?;
+
+    let rounded: Option<f64> = r.round(12.5).run(&conn).try_next().await?;
+    assert_eq!(rounded, Some(13.0));
+    let floor: Option<f64> = r.floor(-12.5).run(&conn).try_next().await?;
+    assert_eq!(floor, Some(-13.0));
+    let ceil: Option<f64> = r.expr(12.1).ceil().run(&conn).try_next().await?;
+    assert_eq!(ceil, Some(13.0));
+
+    let n: Option<f64> = r.random(()).run(&conn).try_next().await?;
+    let n = n.unwrap();
+    assert!((0.0..1.0).contains(&n));
+
+    let n: Option<i64> = r.random(r.args((1, 7))).run(&conn).try_next().await?;
+    assert!((1..7).contains(&n.unwrap()));
+
+    let n: Option<Value> = r
+        .random(r.args((1, 7, Options::new().float(true))))
+        .run(&conn)
+        .try_next()
+        .await?;
+    let n = n.unwrap();
+    assert!(n.is_f64());
+    assert!((1.0..7.0).contains(&n.as_f64().unwrap()));
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use futures::TryStreamExt;
+use reql::migrate::{Migration, Migrator};
+use reql::r;
+use serde_json::Value;
+
+#[tokio::test]
+async fn migrate() -> reql::Result<()> {
+    env_logger::init();
+    let mut conn = r.connect(()).await?;
+
+    let _ = r.db_drop("migrate").run::<_, Value>(&conn).try_next().await;
+    let _ = r
+        .db_create("migrate")
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await?;
+    conn.use_("migrate").await;
+
+    let migrator = Migrator::new(vec![
+        Migration::new(1)
+            .up(r.table_create("users"))
+            .down(r.db("migrate").table_drop("users")),
+        Migration::new(2)
+            .up(r.table("users").index_create("email"))
+            .up(r.table("users").index_wait(()))
+            .down(r.table("users").index_drop("email")),
+    ]);
+
+    assert_eq!(migrator.up(&conn).await?, [1, 2]);
+    assert_eq!(migrator.applied(&conn).await?, [1, 2]);
+    // Nothing is left to apply
+    assert_eq!(migrator.up(&conn).await?, Vec::<u64>::new());
+
+    assert_eq!(migrator.down(&conn).await?, Some(2));
+    assert_eq!(migrator.applied(&conn).await?, [1]);
+    let indexes: Option<Vec<String>> = r.table("users").index_list().run(&conn).try_next().await?;
+    assert_eq!(indexes, Some(Vec::new()));
+
+    Ok(())
+}
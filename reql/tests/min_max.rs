@@ -0,0 +1,55 @@
+use futures::TryStreamExt;
+use reql::r;
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn min_max_index() -> reql::Result<()> {
+    env_logger::init();
+    let conn = r.connect(()).await?;
+
+    let _ = r
+        .table_create("min_max")
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await;
+    let _ = r
+        .table("min_max")
+        .index_create("score")
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await;
+    let _ = r
+        .table("min_max")
+        .index_wait(())
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await?;
+    let _ = r
+        .table("min_max")
+        .insert(json!([
+            {"id": 1, "score": 30},
+            {"id": 2, "score": 90},
+            {"id": 3, "score": 10},
+        ]))
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await?;
+
+    let top: Option<Value> = r
+        .table("min_max")
+        .max(r.index("score"))
+        .run(&conn)
+        .try_next()
+        .await?;
+    assert_eq!(top, Some(json!({"id": 2, "score": 90})));
+
+    let bottom: Option<Value> = r
+        .table("min_max")
+        .min(r.index("score"))
+        .run(&conn)
+        .try_next()
+        .await?;
+    assert_eq!(bottom, Some(json!({"id": 3, "score": 10})));
+
+    Ok(())
+}
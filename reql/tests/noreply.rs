@@ -0,0 +1,39 @@
+use futures::TryStreamExt;
+use reql::cmd::run::Options;
+use reql::r;
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn noreply_wait() -> reql::Result<()> {
+    env_logger::init();
+    let conn = r.connect(()).await?;
+
+    let _ = r
+        .table_create("noreply")
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await;
+
+    let opts = Options::new().noreply(true);
+    for id in 1..=100 {
+        // No response is read for the insert, so nothing is yielded
+        let status = r
+            .table("noreply")
+            .insert(json!({ "id": id }))
+            .run::<_, Value>(r.args((&conn, opts.clone())))
+            .try_next()
+            .await?;
+        assert!(status.is_none());
+    }
+
+    conn.noreply_wait().await?;
+
+    let doc: Option<Value> = r.table("noreply").get(1).run(&conn).try_next().await?;
+    assert!(doc.is_some());
+
+    // Any replies left unread would be mistaken for this one
+    let count: Option<u32> = r.table("noreply").count(()).run(&conn).try_next().await?;
+    assert_eq!(count, Some(100));
+
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use futures::TryStreamExt;
+use reql::{r, Error};
+
+#[tokio::test]
+async fn nth() -> reql::Result<()> {
+    env_logger::init();
+    let conn = r.connect(()).await?;
+
+    let last: Option<u32> = r.expr([1, 2, 3]).nth(-1).run(&conn).try_next().await?;
+    assert_eq!(last, Some(3));
+
+    let page: Vec<u32> = r
+        .range(r.expr(10))
+        .skip(2)
+        .limit(3)
+        .run::<_, u32>(&conn)
+        .try_collect()
+        .await?;
+    assert_eq!(page, [2, 3, 4]);
+
+    let result = r
+        .expr([1, 2, 3])
+        .nth(5)
+        .run::<_, u32>(&conn)
+        .try_next()
+        .await;
+    // The server reports indices out of range
+    assert!(matches!(result, Err(Error::Runtime(_))));
+
+    Ok(())
+}
@@ -1,6 +1,6 @@
 use futures::TryStreamExt;
 use reql::r;
-use serde_json::Value;
+use serde_json::{json, Value};
 
 #[tokio::test]
 async fn order_by() -> reql::Result<()> {
@@ -13,5 +13,53 @@ async fn order_by() -> reql::Result<()> {
         .run(&conn);
     let user: Option<Value> = query.try_next().await?;
     assert!(user.is_some());
+
+    // Ordering a small sequence happens in memory, without an index
+    let sorted: Option<Vec<u32>> = r
+        .expr(json!([{"n": 2}, {"n": 3}, {"n": 1}]))
+        .order_by(r.desc("n"))
+        .get_field("n")
+        .run(&conn)
+        .try_next()
+        .await?;
+    assert_eq!(sorted, Some(vec![3, 2, 1]));
+
+    let _ = r
+        .table_create("order_by")
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await;
+    let _ = r
+        .table("order_by")
+        .index_create("age")
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await;
+    let _ = r
+        .table("order_by")
+        .index_wait(())
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await?;
+    let _ = r
+        .table("order_by")
+        .insert(json!([
+            {"id": 1, "age": 30},
+            {"id": 2, "age": 10},
+            {"id": 3, "age": 20},
+        ]))
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await?;
+
+    let ids: Vec<u32> = r
+        .table("order_by")
+        .order_by(r.index(r.desc("age")))
+        .get_field("id")
+        .run::<_, u32>(&conn)
+        .try_collect()
+        .await?;
+    assert_eq!(ids, [1, 3, 2]);
+
     Ok(())
 }
@@ -0,0 +1,53 @@
+use futures::TryStreamExt;
+use reql::cmd::between::get_page;
+use reql::r;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Post {
+    id: u32,
+}
+
+#[tokio::test]
+async fn get_page_walks_the_index() -> reql::Result<()> {
+    env_logger::init();
+    let conn = r.connect(()).await?;
+
+    let _ = r
+        .table_create("page")
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await;
+    let _ = r
+        .table("page")
+        .delete(())
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await?;
+    let posts: Vec<_> = (1..=7).map(|id| json!({ "id": id })).collect();
+    let _ = r
+        .table("page")
+        .insert(posts)
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await?;
+
+    let page = get_page::<Post, Value>(&conn, r.table("page"), "id", None, 3).await?;
+    let ids: Vec<_> = page.rows.iter().map(|post| post.id).collect();
+    assert_eq!(ids, [1, 2, 3]);
+    assert_eq!(page.next, Some(json!(3)));
+
+    let page = get_page::<Post, _>(&conn, r.table("page"), "id", page.next, 3).await?;
+    let ids: Vec<_> = page.rows.iter().map(|post| post.id).collect();
+    assert_eq!(ids, [4, 5, 6]);
+    assert_eq!(page.next, Some(json!(6)));
+
+    // The last page is short and has no next key
+    let page = get_page::<Post, _>(&conn, r.table("page"), "id", page.next, 3).await?;
+    let ids: Vec<_> = page.rows.iter().map(|post| post.id).collect();
+    assert_eq!(ids, [7]);
+    assert_eq!(page.next, None);
+
+    Ok(())
+}
@@ -0,0 +1,24 @@
+use futures::TryStreamExt;
+use reql::cmd::run;
+use reql::r;
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn run_raw() -> reql::Result<()> {
+    env_logger::init();
+    let conn = r.connect(()).await?;
+
+    // r.db("rethinkdb").table("server_status").count()
+    let term = json!([43, [[15, [[14, ["rethinkdb"]], "server_status"]]]]);
+    let count: Option<u32> = conn.run_raw(term, run::Options::new()).try_next().await?;
+    assert!(count.unwrap() > 0);
+
+    let term = json!({"table": "server_status"});
+    let res: reql::Result<Vec<Value>> = conn
+        .run_raw::<Value>(term, run::Options::new())
+        .try_collect()
+        .await;
+    assert!(res.is_err());
+
+    Ok(())
+}
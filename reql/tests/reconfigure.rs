@@ -0,0 +1,54 @@
+use futures::TryStreamExt;
+use reql::cmd::{reconfigure, wait};
+use reql::r;
+use reql::types::ReconfigureStatus;
+use serde_json::Value;
+
+#[tokio::test]
+async fn reconfigure() -> reql::Result<()> {
+    let conn = r.connect(()).await?;
+
+    let _ = r
+        .table_create("reconfigure")
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await;
+
+    let opts = wait::Options::new()
+        .wait_for(wait::WaitFor::ReadyForWrites)
+        .timeout(30.0);
+    let ready: Option<Value> = r
+        .table("reconfigure")
+        .wait(opts)
+        .run(&conn)
+        .try_next()
+        .await?;
+    assert_eq!(ready.map(|ready| ready["ready"].clone()), Some(1.into()));
+
+    let shards = || async {
+        let config: Option<Value> = r
+            .table("reconfigure")
+            .config()
+            .run(&conn)
+            .try_next()
+            .await?;
+        reql::Result::Ok(config.map(|config| config["shards"].as_array().map(Vec::len)))
+    };
+    let before = shards().await?;
+
+    let opts = reconfigure::Options::new().shards(2).dry_run(true);
+    let status: Option<ReconfigureStatus> = r
+        .table("reconfigure")
+        .reconfigure(opts)
+        .run(&conn)
+        .try_next()
+        .await?;
+    let status = status.unwrap();
+    assert_eq!(status.reconfigured, 0);
+    assert_eq!(status.config_changes.len(), 1);
+
+    // A dry run leaves the table as it was
+    assert_eq!(shards().await?, before);
+
+    Ok(())
+}
@@ -0,0 +1,17 @@
+use futures::TryStreamExt;
+use reql::{func, r};
+
+#[tokio::test]
+async fn reduce_sum() -> reql::Result<()> {
+    env_logger::init();
+    let conn = r.connect(()).await?;
+    let sum: Option<u32> = r
+        .range(r.expr(10))
+        .map(func!(|num| num + 1))
+        .reduce(func!(|acc, num| acc + num))
+        .run(&conn)
+        .try_next()
+        .await?;
+    assert_eq!(sum, Some(55));
+    Ok(())
+}
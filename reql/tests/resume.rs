@@ -0,0 +1,79 @@
+use futures::{pin_mut, TryStreamExt};
+use reql::cmd::changes;
+use reql::r;
+use reql::types::ChangeType;
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn resume_from() -> reql::Result<()> {
+    env_logger::init();
+    let feed_conn = r.connect(()).await?;
+    let conn = r.connect(()).await?;
+
+    let _ = r
+        .table_create("resume")
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await;
+    let _ = r
+        .table("resume")
+        .index_create("seq")
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await;
+    let _ = r
+        .table("resume")
+        .index_wait(())
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await?;
+    let _ = r
+        .table("resume")
+        .delete(())
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await?;
+
+    // Processed before the consumer went down
+    let _ = r
+        .table("resume")
+        .insert(json!([{"id": 1, "seq": 1}, {"id": 2, "seq": 2}]))
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await?;
+    let checkpoint = 2;
+
+    // Written while it was down
+    let _ = r
+        .table("resume")
+        .insert(json!([{"id": 3, "seq": 3}, {"id": 4, "seq": 4}]))
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await?;
+
+    let feed = changes::resume_from::<Value, _>(
+        r.table("resume"),
+        "seq",
+        Some(checkpoint),
+        &feed_conn,
+        &conn,
+    );
+    pin_mut!(feed);
+
+    for id in [3, 4] {
+        let change = feed.try_next().await?.unwrap();
+        assert_eq!(change.result_type, Some(ChangeType::Initial));
+        assert_eq!(change.new_val, Some(json!({"id": id, "seq": id})));
+    }
+
+    let _ = r
+        .table("resume")
+        .insert(json!({"id": 5, "seq": 5}))
+        .run::<_, Value>(&r.connect(()).await?)
+        .try_next()
+        .await?;
+    let change = feed.try_next().await?.unwrap();
+    assert_eq!(change.new_val, Some(json!({"id": 5, "seq": 5})));
+
+    Ok(())
+}
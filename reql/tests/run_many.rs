@@ -0,0 +1,24 @@
+use futures::TryStreamExt;
+use reql::r;
+
+#[tokio::test]
+async fn run_many() -> reql::Result<()> {
+    let conn = r.connect(()).await?;
+    let num = 100;
+
+    let mut sequential = Vec::new();
+    for i in 0..num {
+        let n: Option<u32> = r.expr(i).run(&conn).try_next().await?;
+        sequential.push(n);
+    }
+
+    let queries = (0..num).map(|i| r.expr(i));
+    let mut pipelined = Vec::new();
+    for mut query in conn.run_many::<u32, _>(queries).await? {
+        pipelined.push(query.try_next().await?);
+    }
+
+    assert_eq!(pipelined, sequential);
+
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use futures::TryStreamExt;
+use reql::r;
+
+#[tokio::test]
+async fn sample() -> reql::Result<()> {
+    let conn = r.connect(()).await?;
+
+    let mut picked: Vec<u32> = r
+        .range(r.expr(10))
+        .sample(3)
+        .run(&conn)
+        .try_next()
+        .await?
+        .unwrap_or_default();
+    assert_eq!(picked.len(), 3);
+    picked.sort_unstable();
+    picked.dedup();
+    assert_eq!(picked.len(), 3);
+    assert!(picked.iter().all(|n| *n < 10));
+
+    // There are only 5 elements to pick from
+    let all: Option<Vec<u32>> = r
+        .expr([1, 2, 3, 4, 5])
+        .sample(10)
+        .run(&conn)
+        .try_next()
+        .await?;
+    assert_eq!(all.map(|all| all.len()), Some(5));
+
+    Ok(())
+}
@@ -0,0 +1,28 @@
+use futures::TryStreamExt;
+use reql::cmd::run::Options;
+use reql::r;
+use serde_json::json;
+
+#[tokio::test]
+async fn session_run() -> reql::Result<()> {
+    env_logger::init();
+    let session = r.connect(()).await?;
+
+    let rows: Vec<u32> = session
+        .run::<u32>(r.expr(json!([1, 2, 3])), Options::new())
+        .try_collect()
+        .await?;
+    assert_eq!(rows, [1, 2, 3]);
+
+    let sum: Option<u32> = session
+        .run_value(r.expr(json!([1, 2, 3])).sum(()), Options::new())
+        .await?;
+    assert_eq!(sum, Some(6));
+
+    let profiled: Option<reql::types::Profiled<u32>> = session
+        .run_value(r.expr(1), Options::new().profile(true))
+        .await?;
+    assert_eq!(profiled.map(|row| row.value), Some(1));
+
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use futures::TryStreamExt;
+use reql::cmd::connect::Options;
+use reql::r;
+use serde_json::Value;
+
+// Sessions are owned by the caller, so two of them can point at different
+// databases, or clusters, without affecting each other
+#[tokio::test]
+async fn independent_sessions() -> reql::Result<()> {
+    env_logger::init();
+    let main = r.connect(()).await?;
+    let analytics = r.connect(Options::new().db("sessions")).await?;
+
+    let _ = r
+        .db_create("sessions")
+        .run::<_, Value>(&main)
+        .try_next()
+        .await;
+    let _ = r
+        .db("sessions")
+        .table_create("events")
+        .run::<_, Value>(&main)
+        .try_next()
+        .await;
+
+    let count: Option<u64> = r
+        .table("events")
+        .count(())
+        .run(&analytics)
+        .try_next()
+        .await?;
+    assert!(count.is_some());
+    // The same query looks in `test` on the other session
+    let res = r
+        .table("events")
+        .count(())
+        .run::<_, u64>(&main)
+        .try_next()
+        .await;
+    assert!(res.is_err());
+
+    main.close(()).await?;
+    assert!(main.is_broken());
+    let count: Option<u64> = r
+        .table("events")
+        .count(())
+        .run(&analytics)
+        .try_next()
+        .await?;
+    assert!(count.is_some());
+
+    Ok(())
+}
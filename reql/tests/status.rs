@@ -0,0 +1,38 @@
+use futures::TryStreamExt;
+use reql::r;
+use reql::types::{RebalanceStatus, TableConfig, TableStatus};
+use serde_json::Value;
+
+#[tokio::test]
+async fn status() -> reql::Result<()> {
+    let conn = r.connect(()).await?;
+
+    let _ = r
+        .table_create("status")
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await;
+    let _ = r
+        .table("status")
+        .wait(())
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await?;
+
+    let status: Option<TableStatus> = r.table("status").status().run(&conn).try_next().await?;
+    let status = status.unwrap();
+    assert_eq!(status.name, "status");
+    assert!(status.status.ready_for_reads);
+    assert!(status.shards.is_some());
+
+    let config: Option<TableConfig> = r.table("status").config().run(&conn).try_next().await?;
+    let config = config.unwrap();
+    assert_eq!(config.id, status.id);
+    assert_eq!(config.primary_key, "id");
+
+    let rebalanced: Option<RebalanceStatus> =
+        r.table("status").rebalance().run(&conn).try_next().await?;
+    assert_eq!(rebalanced.map(|status| status.rebalanced), Some(1));
+
+    Ok(())
+}
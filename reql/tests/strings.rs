@@ -0,0 +1,47 @@
+use futures::TryStreamExt;
+use reql::{func, r};
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn strings() -> reql::Result<()> {
+    let conn = r.connect(()).await?;
+
+    let users = json!([
+        {"id": 1, "email": "ada@example.com"},
+        {"id": 2, "email": "alan@example.org"},
+    ]);
+    let ids: Option<Vec<u32>> = r
+        .expr(users)
+        .filter(func!(|user| user
+            .bracket("email")
+            .match_("@example\\.com$")))
+        .get_field("id")
+        .run(&conn)
+        .try_next()
+        .await?;
+    assert_eq!(ids, Some(vec![1]));
+
+    // No match is `null`
+    let no_match: Option<Option<Value>> = r
+        .expr("ada@example.org")
+        .match_("@example\\.com$")
+        .run(&conn)
+        .try_next()
+        .await?;
+    assert_eq!(no_match, Some(None));
+
+    let parts: Option<Vec<String>> = r
+        .expr("a,b,c,d")
+        .split(r.args((",", 2)))
+        .run(&conn)
+        .try_next()
+        .await?;
+    assert_eq!(parts, Some(vec!["a".into(), "b".into(), "c,d".into()]));
+
+    let shout: Option<String> = r.expr("Hello").upcase().run(&conn).try_next().await?;
+    assert_eq!(shout.as_deref(), Some("HELLO"));
+    let whisper: Option<String> = r.expr("Hello").downcase().run(&conn).try_next().await?;
+    assert_eq!(whisper.as_deref(), Some("hello"));
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use futures::TryStreamExt;
+use reql::cmd::table_create::Options;
+use reql::r;
+use reql::types::TableWriteStatus;
+use serde_json::Value;
+
+#[tokio::test]
+async fn table_create_and_drop() -> reql::Result<()> {
+    env_logger::init();
+    let conn = r.connect(()).await?;
+
+    let _ = r
+        .db_create("table_create")
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await;
+
+    let opts = Options::new().primary_key("email");
+    let status: Option<TableWriteStatus> = r
+        .db("table_create")
+        .table_create(r.args(("users", opts)))
+        .run(&conn)
+        .try_next()
+        .await?;
+    assert_eq!(status.unwrap().tables_created, 1);
+
+    let tables: Option<Vec<String>> = r
+        .db("table_create")
+        .table_list()
+        .run(&conn)
+        .try_next()
+        .await?;
+    assert!(tables.unwrap().contains(&"users".to_owned()));
+
+    let status: Option<TableWriteStatus> = r
+        .db("table_create")
+        .table_drop("users")
+        .run(&conn)
+        .try_next()
+        .await?;
+    assert_eq!(status.unwrap().tables_dropped, 1);
+
+    let _ = r
+        .db_drop("table_create")
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await?;
+
+    Ok(())
+}
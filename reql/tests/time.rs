@@ -0,0 +1,59 @@
+use futures::TryStreamExt;
+use reql::{func, r};
+use serde_json::Value;
+
+#[tokio::test]
+async fn time() -> reql::Result<()> {
+    let conn = r.connect(()).await?;
+
+    let _ = r
+        .table_create("time")
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await;
+    let docs = vec![
+        (1, r.time(r.args((1999, 12, 31, "Z")))),
+        (2, r.epoch_time(1_600_000_000)),
+        (3, r.iso8601("2021-06-01T12:00:00Z")),
+    ];
+    for (id, created) in docs {
+        let doc = r.object(r.args([r.expr("id"), r.expr(id), r.expr("created"), created]));
+        let _ = r
+            .table("time")
+            .insert(doc)
+            .run::<_, Value>(&conn)
+            .try_next()
+            .await?;
+    }
+
+    let mut ids: Vec<u32> = r
+        .table("time")
+        .filter(func!(|doc| {
+            let start = r.time(r.args((2000, 1, 1, "Z")));
+            doc.bracket("created").during(r.args((start, r.now())))
+        }))
+        .get_field("id")
+        .run::<_, u32>(&conn)
+        .try_collect()
+        .await?;
+    ids.sort_unstable();
+    assert_eq!(ids, [2, 3]);
+
+    // Late on the 1st in UTC is early on the 2nd two hours ahead
+    let time = r
+        .time(r.args((2021, 6, 1, 23, 30, 0.0, "Z")))
+        .in_timezone("+02:00");
+    let mut parts = Vec::new();
+    for part in [
+        time.clone().year(),
+        time.clone().month(),
+        time.clone().day(),
+        time.hours(),
+    ] {
+        let part: Option<u32> = part.run(&conn).try_next().await?;
+        parts.push(part);
+    }
+    assert_eq!(parts, [Some(2021), Some(6), Some(2), Some(1)]);
+
+    Ok(())
+}
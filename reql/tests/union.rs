@@ -0,0 +1,33 @@
+use futures::TryStreamExt;
+use reql::r;
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn union() -> reql::Result<()> {
+    let conn = r.connect(()).await?;
+
+    let _ = r
+        .table_create("union")
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await;
+    let _ = r
+        .table("union")
+        .insert(json!([{"id": "a"}, {"id": "b"}, {"id": "c"}, {"id": "d"}]))
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await?;
+
+    let mut ids: Vec<String> = r
+        .table("union")
+        .get_all(r.args(["a", "b"]))
+        .union(r.table("union").get_all(r.args(["d"])))
+        .get_field("id")
+        .run(&conn)
+        .try_collect()
+        .await?;
+    ids.sort_unstable();
+    assert_eq!(ids, ["a", "b", "d"]);
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use futures::TryStreamExt;
+use reql::types::{DateTime, WriteStatus};
+use reql::{r, Command};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+#[derive(Deserialize)]
+struct Post {
+    title: String,
+    updated: DateTime,
+}
+
+#[tokio::test]
+async fn update_with_terms() -> reql::Result<()> {
+    env_logger::init();
+    let conn = r.connect(()).await?;
+
+    let _ = r
+        .table_create("update_with_terms")
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await;
+    let _ = r
+        .table("update_with_terms")
+        .insert(json!({"id": 1, "title": "Draft"}))
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await?;
+
+    let doc = Command::from([("title", r.expr("Hello")), ("updated", r.now())]);
+    let status: Option<WriteStatus> = r
+        .table("update_with_terms")
+        .get(1)
+        .update(doc)
+        .run(&conn)
+        .try_next()
+        .await?;
+    assert_eq!(status.unwrap().replaced, 1);
+
+    let post: Option<Post> = r
+        .table("update_with_terms")
+        .get(1)
+        .run(&conn)
+        .try_next()
+        .await?;
+    let post = post.unwrap();
+    assert_eq!(post.title, "Hello");
+    assert!(post.updated.timestamp() > 0);
+
+    Ok(())
+}
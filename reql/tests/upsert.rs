@@ -0,0 +1,70 @@
+use futures::TryStreamExt;
+use reql::types::WriteStatus;
+use reql::{func, r};
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn upsert() -> reql::Result<()> {
+    env_logger::init();
+    let conn = r.connect(()).await?;
+
+    let _ = r
+        .table_create("upsert")
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await;
+
+    let _ = r
+        .table("upsert")
+        .delete(())
+        .run::<_, WriteStatus>(&conn)
+        .try_next()
+        .await?;
+
+    let status: Option<WriteStatus> = r
+        .table("upsert")
+        .upsert(json!({"id": 1, "name": "first", "visits": 1}))
+        .run(&conn)
+        .try_next()
+        .await?;
+    assert_eq!(status.unwrap().inserted, 1);
+
+    // The existing document is merged rather than the write failing
+    let status: Option<WriteStatus> = r
+        .table("upsert")
+        .upsert(json!({"id": 1, "visits": 2}))
+        .run(&conn)
+        .try_next()
+        .await?;
+    let status = status.unwrap();
+    assert_eq!(status.errors, 0);
+    assert_eq!(status.replaced, 1);
+
+    let doc: Option<Value> = r.table("upsert").get(1).run(&conn).try_next().await?;
+    assert_eq!(doc, Some(json!({"id": 1, "name": "first", "visits": 2})));
+
+    // A resolver decides what gets stored instead
+    let _ = r
+        .table("upsert")
+        .upsert(r.args((
+            json!({"id": 1, "visits": 3}),
+            func!(|_id, old, new| {
+                let visits = old.clone().get_field("visits") + new.get_field("visits");
+                old.merge(r.object(r.args([r.expr("visits"), visits])))
+            }),
+        )))
+        .run::<_, WriteStatus>(&conn)
+        .try_next()
+        .await?;
+
+    let visits: Option<u32> = r
+        .table("upsert")
+        .get(1)
+        .get_field("visits")
+        .run(&conn)
+        .try_next()
+        .await?;
+    assert_eq!(visits, Some(5));
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use futures::TryStreamExt;
+use reql::r;
+use reql::types::WriteStatus;
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn update_and_delete() -> reql::Result<()> {
+    env_logger::init();
+    let conn = r.connect(()).await?;
+
+    let _ = r
+        .table_create("write")
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await;
+
+    let _ = r
+        .table("write")
+        .delete(())
+        .run::<_, WriteStatus>(&conn)
+        .try_next()
+        .await?;
+
+    let _ = r
+        .table("write")
+        .insert(json!([
+            {"id": 1, "visits": 1, "active": true},
+            {"id": 2, "visits": 5, "active": false},
+            {"id": 3, "visits": 9, "active": false},
+        ]))
+        .run::<_, WriteStatus>(&conn)
+        .try_next()
+        .await?;
+
+    let status: Option<WriteStatus> = r
+        .table("write")
+        .get(1)
+        .update(json!({"visits": 2}))
+        .run(&conn)
+        .try_next()
+        .await?;
+    let status = status.unwrap();
+    assert_eq!(status.replaced, 1);
+    assert_eq!(status.errors, 0);
+
+    let visits: Option<u32> = r
+        .table("write")
+        .get(1)
+        .get_field("visits")
+        .run(&conn)
+        .try_next()
+        .await?;
+    assert_eq!(visits, Some(2));
+
+    let status: Option<WriteStatus> = r
+        .table("write")
+        .filter(json!({"active": false}))
+        .delete(())
+        .run(&conn)
+        .try_next()
+        .await?;
+    assert_eq!(status.unwrap().deleted, 2);
+
+    Ok(())
+}
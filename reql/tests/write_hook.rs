@@ -0,0 +1,63 @@
+use futures::TryStreamExt;
+use reql::types::WriteStatus;
+use reql::{func, r, Command, Func};
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn write_hook() -> reql::Result<()> {
+    env_logger::init();
+    let conn = r.connect(()).await?;
+
+    let _ = r
+        .table_create("write_hook")
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await;
+    let hook = func!(
+        |_context, _old_val, new_val| new_val.merge(Command::from([("hooked", r.expr(true))]))
+    );
+    let _ = r
+        .table("write_hook")
+        .set_write_hook(hook)
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await?;
+    let hook: Option<Value> = r
+        .table("write_hook")
+        .get_write_hook()
+        .run(&conn)
+        .try_next()
+        .await?;
+    assert!(hook.unwrap()["query"].is_string());
+
+    let _ = r
+        .table("write_hook")
+        .insert(json!({"id": 1}))
+        .run::<_, WriteStatus>(&conn)
+        .try_next()
+        .await?;
+    let hooked: Option<bool> = r
+        .table("write_hook")
+        .get(1)
+        .get_field("hooked")
+        .run(&conn)
+        .try_next()
+        .await?;
+    assert_eq!(hooked, Some(true));
+
+    let _ = r
+        .table("write_hook")
+        .set_write_hook(None::<Func>)
+        .run::<_, Value>(&conn)
+        .try_next()
+        .await?;
+    let hook: Option<Value> = r
+        .table("write_hook")
+        .get_write_hook()
+        .run(&conn)
+        .try_next()
+        .await?;
+    assert_eq!(hook, Some(Value::Null));
+
+    Ok(())
+}
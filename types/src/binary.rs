@@ -0,0 +1,82 @@
+use crate::Binary;
+use serde::de::{self, Deserializer};
+use serde::{Deserialize, Serialize, Serializer};
+use std::ops::Deref;
+
+#[derive(Serialize, Deserialize)]
+struct Data {
+    #[serde(rename = "$reql_type$")]
+    reql_type: String,
+    data: String,
+}
+
+impl<'de> Deserialize<'de> for Binary {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let Data { reql_type, data } = Data::deserialize(deserializer)?;
+        if reql_type != "BINARY" {
+            return Err(de::Error::custom(format!(
+                "expected a BINARY pseudotype, found `{}`",
+                reql_type
+            )));
+        }
+        let bytes = base64::decode(&data)
+            .map_err(|error| de::Error::custom(format!("invalid binary data; {}", error)))?;
+        Ok(Binary(bytes))
+    }
+}
+
+impl Serialize for Binary {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let data = Data {
+            reql_type: String::from("BINARY"),
+            data: base64::encode(&self.0),
+        };
+        data.serialize(serializer)
+    }
+}
+
+impl Deref for Binary {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<Vec<u8>> for Binary {
+    fn from(bytes: Vec<u8>) -> Self {
+        Binary(bytes)
+    }
+}
+
+impl From<Binary> for Vec<u8> {
+    fn from(binary: Binary) -> Self {
+        binary.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Binary;
+    use serde_json::json;
+
+    #[test]
+    fn binary_round_trip() {
+        let value = json!({"$reql_type$": "BINARY", "data": "AQID"});
+        let binary: Binary = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(*binary, [1, 2, 3]);
+        assert_eq!(serde_json::to_value(&binary).unwrap(), value);
+    }
+
+    #[test]
+    fn malformed_binary_is_rejected() {
+        let value = json!({"$reql_type$": "BINARY", "data": "not base64!"});
+        assert!(serde_json::from_value::<Binary>(value).is_err());
+    }
+}
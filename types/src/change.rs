@@ -0,0 +1,65 @@
+use crate::{Change, ChangeType, State};
+
+impl<O, N> Change<O, N> {
+    /// A notification carrying only a state
+    pub fn from_state(state: State) -> Self {
+        Self {
+            old_val: None,
+            new_val: None,
+            result_type: Some(ChangeType::State),
+            old_offset: None,
+            new_offset: None,
+            state: Some(state),
+        }
+    }
+
+    /// A notification carrying an initial value, as sent to feeds started
+    /// with `include_initial`
+    pub fn from_initial(new_val: N) -> Self {
+        Self {
+            old_val: None,
+            new_val: Some(new_val),
+            result_type: Some(ChangeType::Initial),
+            old_offset: None,
+            new_offset: None,
+            state: None,
+        }
+    }
+
+    /// Whether this is a state notification rather than a change
+    pub fn is_state(&self) -> bool {
+        self.state.is_some()
+    }
+
+    /// Whether the initial values, if any, have all been sent
+    pub fn is_ready(&self) -> bool {
+        self.state == Some(State::Ready)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Change, ChangeType, State};
+    use serde_json::{json, Value};
+
+    #[test]
+    fn typed_change() {
+        let value =
+            json!({"old_val": {"id": 1, "n": 1}, "new_val": {"id": 1, "n": 2}, "type": "change"});
+        let change: Change<Value> = serde_json::from_value(value).unwrap();
+        assert_eq!(change.result_type, Some(ChangeType::Change));
+        assert_eq!(change.new_val.as_ref().unwrap()["n"], 2);
+        assert!(!change.is_state());
+    }
+
+    #[test]
+    fn state_change() {
+        let change: Change<Value> =
+            serde_json::from_value(json!({"state": "initializing", "type": "state"})).unwrap();
+        assert_eq!(change.state, Some(State::Initializing));
+        assert!(change.is_state());
+        assert!(!change.is_ready());
+        let change: Change<Value> = serde_json::from_value(json!({"state": "ready"})).unwrap();
+        assert!(change.is_ready());
+    }
+}
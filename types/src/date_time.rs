@@ -1,5 +1,7 @@
 use crate::{DateTime, Time};
-use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use chrono::{FixedOffset, Offset, TimeZone};
+use serde::de::{self, Deserialize, Deserializer};
+use serde::{Serialize, Serializer};
 use std::ops::Deref;
 
 impl<'de> Deserialize<'de> for DateTime {
@@ -8,12 +10,22 @@ impl<'de> Deserialize<'de> for DateTime {
         D: Deserializer<'de>,
     {
         let time = Time::deserialize(deserializer)?;
-        let secs = time.epoch_time.trunc() as i64;
-        // RethinkDB timestamps have millisecond precision so we need
-        // to convert the milliseconds to nanoseconds first
-        let msecs = time.epoch_time.fract().abs() as u32;
-        let naive = chrono::NaiveDateTime::from_timestamp(secs, msecs * 1_000_000);
-        let dt = chrono::DateTime::<chrono::Utc>::from_utc(naive, chrono::Utc);
+        if time.reql_type != "TIME" {
+            return Err(de::Error::custom(format!(
+                "expected a TIME pseudotype, found `{}`",
+                time.reql_type
+            )));
+        }
+        let offset = parse_offset(&time.timezone)
+            .ok_or_else(|| de::Error::custom(format!("invalid timezone `{}`", time.timezone)))?;
+        // RethinkDB timestamps have millisecond precision
+        let msecs = (time.epoch_time * 1000.0).round() as i64;
+        let secs = msecs.div_euclid(1000);
+        let nsecs = msecs.rem_euclid(1000) as u32 * 1_000_000;
+        let dt = offset
+            .timestamp_opt(secs, nsecs)
+            .single()
+            .ok_or_else(|| de::Error::custom(format!("invalid time `{}`", time.epoch_time)))?;
         Ok(DateTime(dt))
     }
 }
@@ -23,29 +35,96 @@ impl Serialize for DateTime {
     where
         S: Serializer,
     {
-        let reql_type = String::from("TIME");
-        let epoch_time = {
-            let t = format!(
-                "{}.{}",
-                self.0.timestamp(),
-                self.0.timestamp_subsec_millis()
-            );
-            t.parse().unwrap()
-        };
-        let timezone = String::from("+00:00");
         let time = Time {
-            reql_type,
-            epoch_time,
-            timezone,
+            reql_type: String::from("TIME"),
+            epoch_time: self.0.timestamp_millis() as f64 / 1000.0,
+            timezone: self.0.offset().to_string(),
         };
         time.serialize(serializer)
     }
 }
 
 impl Deref for DateTime {
-    type Target = chrono::DateTime<chrono::Utc>;
+    type Target = chrono::DateTime<FixedOffset>;
 
     fn deref(&self) -> &Self::Target {
         &self.0
     }
 }
+
+impl<Tz: TimeZone> From<chrono::DateTime<Tz>> for DateTime {
+    fn from(dt: chrono::DateTime<Tz>) -> Self {
+        let offset = dt.offset().fix();
+        DateTime(dt.with_timezone(&offset))
+    }
+}
+
+impl From<DateTime> for chrono::DateTime<FixedOffset> {
+    fn from(dt: DateTime) -> Self {
+        dt.0
+    }
+}
+
+// Parses offsets of the form `+HH:MM` or `-HH:MM`, which is what the
+// server sends
+fn parse_offset(timezone: &str) -> Option<FixedOffset> {
+    if timezone == "Z" {
+        return Some(FixedOffset::east(0));
+    }
+    let sign = match timezone.get(..1)? {
+        "+" => 1,
+        "-" => -1,
+        _ => return None,
+    };
+    let (hours, mins) = timezone.get(1..)?.split_once(':')?;
+    let hours = hours.parse::<i32>().ok()?;
+    let mins = mins.parse::<i32>().ok()?;
+    FixedOffset::east_opt(sign * (hours * 3600 + mins * 60))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::DateTime;
+    use chrono::{FixedOffset, TimeZone};
+    use serde_json::json;
+
+    #[test]
+    fn time_keeps_timezone() {
+        let value = json!({
+            "$reql_type$": "TIME",
+            "epoch_time": 1_376_436_769.923,
+            "timezone": "-07:00",
+        });
+        let dt: DateTime = serde_json::from_value(value.clone()).unwrap();
+        let expected = FixedOffset::west(7 * 3600).timestamp_millis(1_376_436_769_923);
+        assert_eq!(*dt, expected);
+        assert_eq!(serde_json::to_value(&dt).unwrap(), value);
+    }
+
+    #[test]
+    fn time_before_epoch() {
+        let value = json!({
+            "$reql_type$": "TIME",
+            "epoch_time": -1.5,
+            "timezone": "+00:00",
+        });
+        let dt: DateTime = serde_json::from_value(value).unwrap();
+        assert_eq!(dt.timestamp_millis(), -1500);
+    }
+
+    #[test]
+    fn time_from_chrono() {
+        let utc = chrono::Utc.timestamp(0, 5_000_000);
+        let value = serde_json::to_value(DateTime::from(utc)).unwrap();
+        assert_eq!(
+            value,
+            json!({"$reql_type$": "TIME", "epoch_time": 0.005, "timezone": "+00:00"})
+        );
+    }
+
+    #[test]
+    fn other_pseudotypes_are_rejected() {
+        let value = json!({"$reql_type$": "BINARY", "epoch_time": 0, "timezone": "+00:00"});
+        assert!(serde_json::from_value::<DateTime>(value).is_err());
+    }
+}
@@ -0,0 +1,210 @@
+use crate::{geo, Binary, DateTime, Datum, GroupedData, UnexpectedDatum};
+use serde::de::{self, Deserializer};
+use serde::{Deserialize, Serialize, Serializer};
+use serde_json::{Number, Value};
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::{error, fmt};
+
+impl Datum {
+    fn from_value<E: de::Error>(value: Value) -> Result<Self, E> {
+        let datum = match value {
+            Value::Null => Self::Null,
+            Value::Bool(boolean) => Self::Bool(boolean),
+            Value::Number(number) => Self::Number(number),
+            Value::String(string) => Self::String(string),
+            Value::Array(vec) => {
+                let vec = vec.into_iter().map(Self::from_value);
+                Self::Array(vec.collect::<Result<_, _>>()?)
+            }
+            Value::Object(map) => match map.get("$reql_type$").and_then(Value::as_str) {
+                Some("TIME") => Self::Time(pseudotype(map)?),
+                Some("BINARY") => Self::Binary(pseudotype(map)?),
+                Some("GEOMETRY") => Self::Geometry(pseudotype(map)?),
+                Some("GROUPED_DATA") => Self::Grouped(pseudotype(map)?),
+                _ => {
+                    let map = map
+                        .into_iter()
+                        .map(|(key, value)| Ok((key, Self::from_value(value)?)));
+                    Self::Object(map.collect::<Result<_, _>>()?)
+                }
+            },
+        };
+        Ok(datum)
+    }
+
+    fn kind(&self) -> &'static str {
+        match self {
+            Self::Null => "null",
+            Self::Bool(_) => "boolean",
+            Self::Number(_) => "number",
+            Self::String(_) => "string",
+            Self::Array(_) => "array",
+            Self::Object(_) => "object",
+            Self::Time(_) => "time",
+            Self::Binary(_) => "binary",
+            Self::Geometry(_) => "geometry",
+            Self::Grouped(_) => "grouped data",
+        }
+    }
+}
+
+fn pseudotype<T, E>(map: serde_json::Map<String, Value>) -> Result<T, E>
+where
+    T: de::DeserializeOwned,
+    E: de::Error,
+{
+    serde_json::from_value(Value::Object(map)).map_err(E::custom)
+}
+
+impl<'de> Deserialize<'de> for Datum {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Self::from_value(Value::deserialize(deserializer)?)
+    }
+}
+
+impl Serialize for Datum {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Self::Null => serializer.serialize_unit(),
+            Self::Bool(boolean) => boolean.serialize(serializer),
+            Self::Number(number) => number.serialize(serializer),
+            Self::String(string) => string.serialize(serializer),
+            Self::Array(vec) => vec.serialize(serializer),
+            Self::Object(map) => map.serialize(serializer),
+            Self::Time(time) => time.serialize(serializer),
+            Self::Binary(binary) => binary.serialize(serializer),
+            Self::Geometry(geometry) => geometry.serialize(serializer),
+            Self::Grouped(grouped) => grouped.serialize(serializer),
+        }
+    }
+}
+
+macro_rules! try_from {
+    ($($variant:ident => $typ:ty,)*) => {
+        $(
+            impl TryFrom<Datum> for $typ {
+                type Error = UnexpectedDatum;
+
+                fn try_from(datum: Datum) -> Result<Self, Self::Error> {
+                    match datum {
+                        Datum::$variant(value) => Ok(value),
+                        datum => Err(UnexpectedDatum(datum)),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+try_from! {
+    Bool => bool,
+    Number => Number,
+    String => String,
+    Array => Vec<Datum>,
+    Object => BTreeMap<String, Datum>,
+    Time => DateTime,
+    Binary => Binary,
+    Geometry => geo::Geometry,
+    Grouped => GroupedData<Datum, Datum>,
+}
+
+macro_rules! try_from_number {
+    ($($typ:ty => $method:ident,)*) => {
+        $(
+            impl TryFrom<Datum> for $typ {
+                type Error = UnexpectedDatum;
+
+                fn try_from(datum: Datum) -> Result<Self, Self::Error> {
+                    let number = match &datum {
+                        Datum::Number(number) => number.$method(),
+                        _ => None,
+                    };
+                    number.ok_or(UnexpectedDatum(datum))
+                }
+            }
+        )*
+    };
+}
+
+try_from_number! {
+    i64 => as_i64,
+    u64 => as_u64,
+    f64 => as_f64,
+}
+
+impl error::Error for UnexpectedDatum {}
+
+impl fmt::Display for UnexpectedDatum {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unexpected {}", self.0.kind())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{geo, Binary, Datum, UnexpectedDatum};
+    use serde_json::json;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn pseudotypes_are_kept_apart() {
+        let value = json!({
+            "at": {"$reql_type$": "TIME", "epoch_time": 1_600_000_000.0, "timezone": "+00:00"},
+            "bytes": {"$reql_type$": "BINARY", "data": "aGVsbG8="},
+            "place": {"$reql_type$": "GEOMETRY", "type": "Point", "coordinates": [0.0, 0.0]},
+            "doc": {"n": 1},
+        });
+        let datum: Datum = serde_json::from_value(value.clone()).unwrap();
+        let mut map = match datum.clone() {
+            Datum::Object(map) => map,
+            datum => panic!("unexpected datum: {:?}", datum),
+        };
+        assert!(matches!(map["at"], Datum::Time(_)));
+        assert_eq!(map["bytes"], Datum::Binary(Binary::new(b"hello")));
+        assert!(matches!(
+            map["place"],
+            Datum::Geometry(geo::Geometry::Point(_))
+        ));
+        assert!(matches!(map["doc"], Datum::Object(_)));
+        let bytes = Binary::try_from(map.remove("bytes").unwrap()).unwrap();
+        assert_eq!(&*bytes, b"hello");
+        assert_eq!(serde_json::to_value(&datum).unwrap(), value);
+    }
+
+    #[test]
+    fn grouped_data() {
+        let value = json!({"$reql_type$": "GROUPED_DATA", "data": [["a", 2]]});
+        let datum: Datum = serde_json::from_value(value).unwrap();
+        match datum {
+            Datum::Grouped(grouped) => {
+                assert_eq!(
+                    grouped.0,
+                    [(Datum::String("a".into()), Datum::Number(2.into()))]
+                );
+            }
+            datum => panic!("unexpected datum: {:?}", datum),
+        }
+    }
+
+    #[test]
+    fn numbers() {
+        let datum: Datum = serde_json::from_value(json!(-3)).unwrap();
+        assert_eq!(i64::try_from(datum.clone()), Ok(-3));
+        assert_eq!(f64::try_from(datum.clone()), Ok(-3.0));
+        assert_eq!(u64::try_from(datum.clone()), Err(UnexpectedDatum(datum)));
+    }
+
+    #[test]
+    fn unexpected_datum() {
+        let error = String::try_from(Datum::Bool(true)).unwrap_err();
+        assert_eq!(error, UnexpectedDatum(Datum::Bool(true)));
+        assert_eq!(error.to_string(), "unexpected boolean");
+    }
+}
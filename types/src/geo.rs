@@ -0,0 +1,300 @@
+//! ReQL geometry types
+//!
+//! These map to the `GEOMETRY` pseudotype the server uses for points,
+//! lines and polygons. Coordinates are given as longitude then latitude,
+//! in degrees.
+
+use serde::de::{self, Deserializer};
+use serde::{Deserialize, Serialize, Serializer};
+use serde_json::Value;
+use std::{error, fmt};
+
+/// A point on the Earth's surface
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+#[non_exhaustive]
+pub struct Point {
+    pub longitude: f64,
+    pub latitude: f64,
+}
+
+impl Point {
+    /// Creates a point, making sure the coordinates are within range
+    pub fn new(longitude: f64, latitude: f64) -> Result<Self, Error> {
+        if !(-180.0..=180.0).contains(&longitude) {
+            return Err(Error::Longitude(longitude));
+        }
+        if !(-90.0..=90.0).contains(&latitude) {
+            return Err(Error::Latitude(latitude));
+        }
+        Ok(Self {
+            longitude,
+            latitude,
+        })
+    }
+}
+
+/// A line made of two or more points
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct Line(pub Vec<Point>);
+
+/// A polygon
+///
+/// The first ring is the outline of the polygon, any following rings are
+/// holes cut out of it.
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct Polygon(pub Vec<Vec<Point>>);
+
+/// Any of the geometry types, for values that may be of more than one
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+#[non_exhaustive]
+pub enum Geometry {
+    Point(Point),
+    Line(Line),
+    Polygon(Polygon),
+}
+
+/// A document returned by `get_nearest`, along with its distance from
+/// the point, in the unit the query asked for
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, PartialOrd)]
+#[non_exhaustive]
+pub struct Nearest<T> {
+    pub dist: f64,
+    pub doc: T,
+}
+
+/// Coordinates that are out of range
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub enum Error {
+    Longitude(f64),
+    Latitude(f64),
+}
+
+impl error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Longitude(lon) => write!(f, "longitude `{}` is not within [-180, 180]", lon),
+            Self::Latitude(lat) => write!(f, "latitude `{}` is not within [-90, 90]", lat),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Data<T> {
+    #[serde(rename = "$reql_type$")]
+    reql_type: String,
+    #[serde(rename = "type")]
+    geo_type: String,
+    coordinates: T,
+}
+
+impl Data<Value> {
+    fn parse<'de, D, T>(deserializer: D, geo_type: &str) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: de::DeserializeOwned,
+    {
+        let geometry = Self::deserialize(deserializer)?;
+        if geometry.reql_type != "GEOMETRY" {
+            return Err(de::Error::custom(format!(
+                "expected a GEOMETRY pseudotype, found `{}`",
+                geometry.reql_type
+            )));
+        }
+        if geometry.geo_type != geo_type {
+            return Err(de::Error::custom(format!(
+                "expected a {}, found a {}",
+                geo_type, geometry.geo_type
+            )));
+        }
+        serde_json::from_value(geometry.coordinates).map_err(de::Error::custom)
+    }
+}
+
+fn serialize<S, T>(serializer: S, geo_type: &str, coordinates: T) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: Serialize,
+{
+    let geometry = Data {
+        reql_type: String::from("GEOMETRY"),
+        geo_type: geo_type.to_owned(),
+        coordinates,
+    };
+    geometry.serialize(serializer)
+}
+
+// The `[longitude, latitude]` pair GeoJSON uses for each position
+#[derive(Serialize, Deserialize)]
+struct Position(f64, f64);
+
+impl From<&Point> for Position {
+    fn from(point: &Point) -> Self {
+        Position(point.longitude, point.latitude)
+    }
+}
+
+impl Position {
+    fn into_point<E: de::Error>(self) -> Result<Point, E> {
+        Point::new(self.0, self.1).map_err(E::custom)
+    }
+}
+
+fn positions(points: &[Point]) -> Vec<Position> {
+    points.iter().map(Position::from).collect()
+}
+
+fn points<E: de::Error>(positions: Vec<Position>) -> Result<Vec<Point>, E> {
+    positions.into_iter().map(Position::into_point).collect()
+}
+
+impl Serialize for Point {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize(serializer, "Point", Position::from(self))
+    }
+}
+
+impl<'de> Deserialize<'de> for Point {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Data::parse::<_, Position>(deserializer, "Point")?.into_point()
+    }
+}
+
+impl Serialize for Line {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize(serializer, "LineString", positions(&self.0))
+    }
+}
+
+impl<'de> Deserialize<'de> for Line {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let positions = Data::parse(deserializer, "LineString")?;
+        Ok(Line(points(positions)?))
+    }
+}
+
+impl Serialize for Polygon {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let rings: Vec<_> = self.0.iter().map(|ring| positions(ring)).collect();
+        serialize(serializer, "Polygon", rings)
+    }
+}
+
+impl<'de> Deserialize<'de> for Polygon {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let rings: Vec<Vec<Position>> = Data::parse(deserializer, "Polygon")?;
+        let rings = rings.into_iter().map(points).collect::<Result<_, _>>()?;
+        Ok(Polygon(rings))
+    }
+}
+
+impl Serialize for Geometry {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Self::Point(point) => point.serialize(serializer),
+            Self::Line(line) => line.serialize(serializer),
+            Self::Polygon(polygon) => polygon.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Geometry {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = Value::deserialize(deserializer)?;
+        let geometry = match value.get("type").and_then(Value::as_str) {
+            Some("Point") => serde_json::from_value(value).map(Self::Point),
+            Some("LineString") => serde_json::from_value(value).map(Self::Line),
+            Some("Polygon") => serde_json::from_value(value).map(Self::Polygon),
+            _ => {
+                return Err(de::Error::custom(format!(
+                    "expected a Point, LineString or Polygon, found `{}`",
+                    value
+                )))
+            }
+        };
+        geometry.map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Error, Geometry, Line, Point, Polygon};
+    use serde_json::json;
+
+    #[test]
+    fn point_round_trip() {
+        let value = json!({"$reql_type$": "GEOMETRY", "type": "Point", "coordinates": [-122.423246, 37.779388]});
+        let point: Point = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(point, Point::new(-122.423246, 37.779388).unwrap());
+        assert_eq!(serde_json::to_value(point).unwrap(), value);
+    }
+
+    #[test]
+    fn line_round_trip() {
+        let value = json!({"$reql_type$": "GEOMETRY", "type": "LineString", "coordinates": [[0.0, 0.0], [1.0, 1.0]]});
+        let line: Line = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(line.0.len(), 2);
+        assert_eq!(serde_json::to_value(&line).unwrap(), value);
+    }
+
+    #[test]
+    fn polygon_round_trip() {
+        let value = json!({
+            "$reql_type$": "GEOMETRY",
+            "type": "Polygon",
+            "coordinates": [[[0.0, 0.0], [0.0, 1.0], [1.0, 1.0], [0.0, 0.0]]],
+        });
+        let polygon: Polygon = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(polygon.0[0].len(), 4);
+        assert_eq!(serde_json::to_value(&polygon).unwrap(), value);
+    }
+
+    #[test]
+    fn out_of_range_coordinates() {
+        assert_eq!(Point::new(0.0, 91.0), Err(Error::Latitude(91.0)));
+        assert_eq!(Point::new(-181.0, 0.0), Err(Error::Longitude(-181.0)));
+        let value =
+            json!({"$reql_type$": "GEOMETRY", "type": "Point", "coordinates": [0.0, -90.5]});
+        assert!(serde_json::from_value::<Point>(value).is_err());
+    }
+
+    #[test]
+    fn any_geometry() {
+        let value = json!({"$reql_type$": "GEOMETRY", "type": "LineString", "coordinates": [[0.0, 0.0], [1.0, 1.0]]});
+        let geometry: Geometry = serde_json::from_value(value.clone()).unwrap();
+        assert!(matches!(geometry, Geometry::Line(_)));
+        assert_eq!(serde_json::to_value(&geometry).unwrap(), value);
+    }
+
+    #[test]
+    fn wrong_geometry_type() {
+        let value = json!({"$reql_type$": "GEOMETRY", "type": "Point", "coordinates": [0.0, 0.0]});
+        assert!(serde_json::from_value::<Line>(value).is_err());
+    }
+}
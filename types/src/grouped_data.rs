@@ -0,0 +1,98 @@
+use crate::GroupedData;
+use serde::de::{self, DeserializeOwned, Deserializer};
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+#[derive(Serialize, Deserialize)]
+struct Data<K, V> {
+    #[serde(rename = "$reql_type$")]
+    reql_type: String,
+    data: Vec<(K, V)>,
+}
+
+impl<'de, K, V> Deserialize<'de> for GroupedData<K, V>
+where
+    K: DeserializeOwned,
+    V: DeserializeOwned,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let Data { reql_type, data } = Data::deserialize(deserializer)?;
+        if reql_type != "GROUPED_DATA" {
+            return Err(de::Error::custom(format!(
+                "expected a GROUPED_DATA pseudotype, found `{}`",
+                reql_type
+            )));
+        }
+        Ok(GroupedData(data))
+    }
+}
+
+impl<K, V> Serialize for GroupedData<K, V>
+where
+    K: Serialize,
+    V: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        #[derive(Serialize)]
+        struct DataRef<'a, K, V> {
+            #[serde(rename = "$reql_type$")]
+            reql_type: &'a str,
+            data: &'a [(K, V)],
+        }
+
+        let data = DataRef {
+            reql_type: "GROUPED_DATA",
+            data: &self.0,
+        };
+        data.serialize(serializer)
+    }
+}
+
+impl<K, V> From<GroupedData<K, V>> for Vec<(K, V)> {
+    fn from(grouped: GroupedData<K, V>) -> Self {
+        grouped.0
+    }
+}
+
+impl<K: Ord, V> From<GroupedData<K, V>> for BTreeMap<K, V> {
+    fn from(grouped: GroupedData<K, V>) -> Self {
+        grouped.0.into_iter().collect()
+    }
+}
+
+impl<K: Eq + Hash, V> From<GroupedData<K, V>> for HashMap<K, V> {
+    fn from(grouped: GroupedData<K, V>) -> Self {
+        grouped.0.into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::GroupedData;
+    use serde_json::{json, Value};
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn grouped_data_into_map() {
+        let value = json!({"$reql_type$": "GROUPED_DATA", "data": [["a", 2], ["b", 1]]});
+        let grouped: GroupedData<String, u32> = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&grouped).unwrap(), value);
+        let map = BTreeMap::from(grouped);
+        assert_eq!(map["a"], 2);
+        assert_eq!(map["b"], 1);
+    }
+
+    #[test]
+    fn grouped_data_with_compound_keys() {
+        let value = json!({"$reql_type$": "GROUPED_DATA", "data": [[["a", 1], 3]]});
+        let grouped: GroupedData<Value, u32> = serde_json::from_value(value).unwrap();
+        assert_eq!(grouped.0, [(json!(["a", 1]), 3)]);
+    }
+}
@@ -1,16 +1,61 @@
 //! Common ReQL data types
 
+mod binary;
+mod change;
 mod date_time;
+mod datum;
+pub mod geo;
+mod grouped_data;
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::net::IpAddr;
 use uuid::Uuid;
 
+/// A ReQL `TIME`, keeping the timezone offset it was stored with
+///
+/// Converts to and from `chrono::DateTime` using `From`.
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 #[non_exhaustive]
-pub struct DateTime(pub chrono::DateTime<chrono::Utc>);
+pub struct DateTime(pub chrono::DateTime<chrono::FixedOffset>);
+
+/// The result of a `group` query
+///
+/// Holds each group key along with its reduction, in the order the
+/// server sent them. Keys can be any ReQL value, including arrays and
+/// objects, so use `serde_json::Value` for keys that do not fit a more
+/// specific type. Converting into a `BTreeMap` or `HashMap` is only
+/// possible when the key type is orderable or hashable respectively.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct GroupedData<K, V>(pub Vec<(K, V)>);
+
+/// Any value returned by the server
+///
+/// Unlike `serde_json::Value`, this keeps the ReQL pseudotypes apart from
+/// the plain objects they are sent as, so a `TIME` deserialises into
+/// `Datum::Time` rather than an object with a `$reql_type$` field.
+/// Numbers are kept as sent. Use `TryFrom` to get at the value inside,
+/// which gives the datum back as an [UnexpectedDatum] when it's of
+/// another type.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum Datum {
+    Null,
+    Bool(bool),
+    Number(serde_json::Number),
+    String(String),
+    Array(Vec<Datum>),
+    Object(BTreeMap<String, Datum>),
+    Time(DateTime),
+    Binary(Binary),
+    Geometry(geo::Geometry),
+    Grouped(GroupedData<Datum, Datum>),
+}
+
+/// A [Datum] that is not of the type it was converted to
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnexpectedDatum(pub Datum);
 
 /// Status returned by a write command
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -28,6 +73,107 @@ pub struct WriteStatus {
     pub changes: Option<Vec<Change<Value, Value>>>,
 }
 
+/// Status returned by `db_create` and `db_drop`
+///
+/// Dropping a database also drops its tables, which are counted in
+/// `tables_dropped`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct DbWriteStatus {
+    #[serde(default)]
+    pub dbs_created: u32,
+    #[serde(default)]
+    pub dbs_dropped: u32,
+    #[serde(default)]
+    pub tables_dropped: u32,
+    pub config_changes: Vec<Change<Value, Value>>,
+}
+
+/// Status returned by `table_create` and `table_drop`
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct TableWriteStatus {
+    #[serde(default)]
+    pub tables_created: u32,
+    #[serde(default)]
+    pub tables_dropped: u32,
+    pub config_changes: Vec<Change<Value, Value>>,
+}
+
+/// Status of a secondary index, as returned by `index_status` and
+/// `index_wait`
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct IndexStatus {
+    pub index: String,
+    pub ready: bool,
+    /// How much of the index has been built, while it is not ready yet
+    pub progress: Option<f64>,
+    pub function: Binary,
+    pub multi: bool,
+    pub geo: bool,
+    pub outdated: bool,
+    pub query: String,
+}
+
+/// Status returned by `reconfigure`
+///
+/// With the `dry_run` option, `reconfigured` is `0` and the changes are
+/// only the ones that would have been made.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct ReconfigureStatus {
+    pub reconfigured: u32,
+    pub config_changes: Vec<Change<Value, Value>>,
+    pub status_changes: Vec<Change<Value, Value>>,
+}
+
+/// Status returned by `rebalance`
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct RebalanceStatus {
+    pub rebalanced: u32,
+    pub status_changes: Vec<Change<Value, Value>>,
+}
+
+/// What `info` returns for a table
+#[derive(Debug, Clone, Deserialize, Serialize, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[non_exhaustive]
+pub struct TableInfo {
+    /// Always `TABLE`
+    #[serde(rename = "type")]
+    pub typ: String,
+    pub id: Uuid,
+    pub name: String,
+    pub db: DbInfo,
+    pub primary_key: String,
+    /// The names of the table's secondary indexes
+    pub indexes: Vec<String>,
+    /// An estimate of the number of documents in each shard
+    #[serde(default)]
+    pub doc_count_estimates: Vec<u64>,
+}
+
+/// What `info` returns for a database
+#[derive(Debug, Clone, Deserialize, Serialize, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[non_exhaustive]
+pub struct DbInfo {
+    /// Always `DB`
+    #[serde(rename = "type")]
+    pub typ: String,
+    pub id: Uuid,
+    pub name: String,
+}
+
+/// The result of a query run with the `profile` option, along with
+/// the profile of its execution
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct Profiled<T> {
+    pub profile: Value,
+    pub value: T,
+}
+
 /// Structure of data in `cluster_config` table
 #[derive(Debug, Clone, Deserialize, Serialize, Eq, PartialEq, Ord, PartialOrd, Hash)]
 #[non_exhaustive]
@@ -41,10 +187,14 @@ pub struct ClusterConfig {
 #[non_exhaustive]
 pub struct CurrentIssue {}
 
-/// Structure of data in `db_config` table
+/// Structure of data in `db_config` table, also returned by `config`
+/// on a database
 #[derive(Debug, Clone, Deserialize, Serialize, Eq, PartialEq, Ord, PartialOrd, Hash)]
 #[non_exhaustive]
-pub struct DbConfig {}
+pub struct DbConfig {
+    pub id: Uuid,
+    pub name: String,
+}
 
 /// Structure of data in `jobs` table
 #[derive(Debug, Clone, Deserialize, Serialize, Eq, PartialEq, Ord, PartialOrd, Hash)]
@@ -110,31 +260,122 @@ pub struct ServerStatus {
 #[non_exhaustive]
 pub struct Stat {}
 
-/// Structure of data in `cluster_config` table
+/// Structure of data in `table_config` table, also returned by `config`
+/// on a table
+#[derive(Debug, Clone, Deserialize, Serialize, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[non_exhaustive]
+pub struct TableConfig {
+    pub id: Uuid,
+    pub name: String,
+    pub db: String,
+    pub primary_key: String,
+    pub shards: Vec<ShardConfig>,
+    pub indexes: Vec<String>,
+    pub write_acks: String,
+    pub durability: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[non_exhaustive]
+pub struct ShardConfig {
+    pub primary_replica: String,
+    pub replicas: Vec<String>,
+    #[serde(default)]
+    pub nonvoting_replicas: Vec<String>,
+}
+
+/// Structure of data in `table_status` table, also returned by `status`
+/// on a table
+#[derive(Debug, Clone, Deserialize, Serialize, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[non_exhaustive]
+pub struct TableStatus {
+    pub id: Uuid,
+    pub name: String,
+    pub db: String,
+    pub status: TableAvailability,
+    /// `None` while the table is unavailable
+    pub shards: Option<Vec<ShardStatus>>,
+    pub raft_leader: Option<String>,
+}
+
+/// How available a table is, from least to most
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[non_exhaustive]
+pub struct TableAvailability {
+    pub ready_for_outdated_reads: bool,
+    pub ready_for_reads: bool,
+    pub ready_for_writes: bool,
+    pub all_replicas_ready: bool,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, Eq, PartialEq, Ord, PartialOrd, Hash)]
 #[non_exhaustive]
-pub struct TableConfig {}
+pub struct ShardStatus {
+    pub primary_replicas: Vec<String>,
+    pub replicas: Vec<ReplicaStatus>,
+}
 
-/// Structure of data in `table_status` table
 #[derive(Debug, Clone, Deserialize, Serialize, Eq, PartialEq, Ord, PartialOrd, Hash)]
 #[non_exhaustive]
-pub struct TableStatus {}
+pub struct ReplicaStatus {
+    pub server: String,
+    /// Such as `ready`, `transitioning`, `backfilling` or `disconnected`
+    pub state: String,
+}
 
 /// Structure of data in `uses` table
 #[derive(Debug, Clone, Deserialize, Serialize, Eq, PartialEq, Ord, PartialOrd, Hash)]
 #[non_exhaustive]
 pub struct User {}
 
+/// A notification from a changefeed
+///
+/// `result_type` is only sent when the feed is started with
+/// `include_types`, and the offsets with `include_offsets`. Feeds started
+/// with `include_states` also send items with only `state` set, which
+/// mark where the initial values end and live changes begin.
 #[derive(Debug, Clone, Deserialize, Serialize, Eq, PartialEq, Ord, PartialOrd, Hash)]
 #[non_exhaustive]
-pub struct Change<O, N> {
+pub struct Change<O, N = O> {
     pub old_val: Option<O>,
     pub new_val: Option<N>,
     #[serde(rename = "type")]
-    pub result_type: Option<String>,
+    pub result_type: Option<ChangeType>,
     pub old_offset: Option<usize>,
     pub new_offset: Option<usize>,
-    pub state: Option<String>,
+    pub state: Option<State>,
+}
+
+/// The kind of change a changefeed notification represents
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum ChangeType {
+    Add,
+    Remove,
+    Change,
+    /// A value from the initial results of an `include_initial` feed
+    Initial,
+    /// An initial value that has left the result set before being sent
+    Uninitial,
+    State,
+}
+
+/// The state of a changefeed, sent when it is started with `include_states`
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum State {
+    /// The notifications that follow are initial values
+    Initializing,
+    /// The notifications that follow are live changes
+    Ready,
+    /// The feed was started again after its connection failed, so
+    /// changes made in between have been missed
+    ///
+    /// This is never sent by the server, only by feeds run with
+    /// `reql::cmd::changes::reconnecting`.
+    Reconnected,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -154,22 +395,13 @@ pub struct ServerInfo {
     pub name: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-#[allow(clippy::upper_case_acronyms)]
-struct BINARY;
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct Binary {
-    #[serde(rename = "$reql_type$")]
-    reql_type: BINARY,
-    pub data: String,
-}
+/// Bytes stored as a ReQL `BINARY` value
+#[derive(Debug, Clone, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[non_exhaustive]
+pub struct Binary(pub Vec<u8>);
 
 impl Binary {
     pub fn new(bytes: &[u8]) -> Self {
-        Self {
-            reql_type: BINARY,
-            data: base64::encode(bytes),
-        }
+        Self(bytes.to_vec())
     }
 }